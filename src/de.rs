@@ -4,6 +4,11 @@ mod google_firestore_function_map_access;
 mod google_firestore_pipeline_map_access;
 mod google_firestore_pipeline_stage_map_access;
 mod google_type_lat_lng_map_access;
+mod owned_deserializer;
+mod owned_firestore_enum_deserializer;
+mod owned_google_firestore_function_map_access;
+mod owned_google_firestore_pipeline_map_access;
+mod owned_google_firestore_pipeline_stage_map_access;
 pub(crate) mod private;
 mod prost_types_timestamp_map_access;
 pub(crate) mod with;
@@ -12,11 +17,15 @@ use crate::Error;
 use crate::google::firestore::v1::Value;
 
 pub use self::deserializer::Deserializer;
+pub use self::owned_deserializer::OwnedDeserializer;
 
 pub(crate) use self::google_firestore_function_map_access::GoogleFirestoreFunctionMapAccess;
 pub(crate) use self::google_firestore_pipeline_map_access::GoogleFirestorePipelineMapAccess;
 pub(crate) use self::google_firestore_pipeline_stage_map_access::GoogleFirestorePipelineStageMapAccess;
 pub(crate) use self::google_type_lat_lng_map_access::GoogleTypeLatLngMapAccess;
+pub(crate) use self::owned_google_firestore_function_map_access::OwnedGoogleFirestoreFunctionMapAccess;
+pub(crate) use self::owned_google_firestore_pipeline_map_access::OwnedGoogleFirestorePipelineMapAccess;
+pub(crate) use self::owned_google_firestore_pipeline_stage_map_access::OwnedGoogleFirestorePipelineStageMapAccess;
 pub(crate) use self::prost_types_timestamp_map_access::ProstTypesTimestampMapAccess;
 
 /// Deserialize an instance of type `T` from a Firestore Value.
@@ -86,6 +95,33 @@ where
     T::deserialize(Deserializer::new(value))
 }
 
+/// Deserialize an instance of type `T` from an owned Firestore Value.
+///
+/// Strings, bytes and nested values are moved into `T` instead of being
+/// copied. See [`from_value`] for the mapping.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// #     use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// #     use serde_firestore_value::from_value_owned;
+/// assert_eq!(
+///     from_value_owned::<String>(Value {
+///         value_type: Some(ValueType::StringValue("s".to_string()))
+///     })?,
+///     "s"
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+pub fn from_value_owned<T>(value: Value) -> Result<T, Error>
+where
+    T: serde::de::DeserializeOwned,
+{
+    T::deserialize(OwnedDeserializer::new(value))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::google::firestore::v1::Value;
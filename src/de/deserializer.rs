@@ -14,12 +14,16 @@ use crate::{
 #[derive(Debug)]
 pub struct Deserializer<'a> {
     value: &'a Value,
+    human_readable: bool,
 }
 
 impl<'de> Deserializer<'de> {
     /// Creates a new [`Deserializer`].
     pub fn new(value: &'de Value) -> Self {
-        Self { value }
+        Self {
+            value,
+            human_readable: true,
+        }
     }
 }
 
@@ -39,35 +43,38 @@ impl<'a> serde::Deserializer<'a> for Deserializer<'a> {
                 ValueType::TimestampValue(v) => {
                     visitor.visit_map(ProstTypesTimestampMapAccess::new(v))
                 }
-                ValueType::StringValue(v) => visitor.visit_str(v),
-                ValueType::BytesValue(v) => visitor.visit_bytes(v),
+                ValueType::StringValue(v) => visitor.visit_borrowed_str(v),
+                ValueType::BytesValue(v) => visitor.visit_borrowed_bytes(v),
                 ValueType::ReferenceValue(v) => {
                     visitor.visit_map(serde::de::value::MapDeserializer::new(std::iter::once((
-                        crate::Reference::NAME,
-                        serde::de::value::StrDeserializer::new(v),
+                        serde::de::value::BorrowedStrDeserializer::new(crate::Reference::NAME),
+                        serde::de::value::BorrowedStrDeserializer::new(v),
                     ))))
                 }
                 ValueType::GeoPointValue(v) => visitor.visit_map(GoogleTypeLatLngMapAccess::new(v)),
                 ValueType::ArrayValue(v) => visitor.visit_seq(
                     serde::de::value::SeqDeserializer::new(v.values.iter().map(Deserializer::new)),
                 ),
-                ValueType::MapValue(map) => {
-                    visitor.visit_map(serde::de::value::MapDeserializer::new(
-                        map.fields
-                            .iter()
-                            .map(|(k, v)| (k.as_str(), Deserializer::new(v))),
-                    ))
-                }
+                ValueType::MapValue(map) => visitor.visit_map(
+                    serde::de::value::MapDeserializer::new(map.fields.iter().map(|(k, v)| {
+                        (
+                            serde::de::value::BorrowedStrDeserializer::new(k.as_str()),
+                            Deserializer::new(v),
+                        )
+                    })),
+                ),
                 ValueType::FieldReferenceValue(v) => {
                     visitor.visit_map(serde::de::value::MapDeserializer::new(std::iter::once((
-                        crate::FieldReference::NAME,
-                        serde::de::value::StrDeserializer::new(v),
+                        serde::de::value::BorrowedStrDeserializer::new(crate::FieldReference::NAME),
+                        serde::de::value::BorrowedStrDeserializer::new(v),
                     ))))
                 }
                 ValueType::VariableReferenceValue(v) => {
                     visitor.visit_map(serde::de::value::MapDeserializer::new(std::iter::once((
-                        crate::VariableReference::NAME,
-                        serde::de::value::StrDeserializer::new(v),
+                        serde::de::value::BorrowedStrDeserializer::new(
+                            crate::VariableReference::NAME,
+                        ),
+                        serde::de::value::BorrowedStrDeserializer::new(v),
                     ))))
                 }
                 ValueType::FunctionValue(v) => {
@@ -187,7 +194,7 @@ impl<'a> serde::Deserializer<'a> for Deserializer<'a> {
         V: serde::de::Visitor<'a>,
     {
         let value = self.value.as_string()?;
-        visitor.visit_str(value)
+        visitor.visit_borrowed_str(value)
     }
 
     fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -202,7 +209,7 @@ impl<'a> serde::Deserializer<'a> for Deserializer<'a> {
         V: serde::de::Visitor<'a>,
     {
         let value = self.value.as_bytes()?;
-        visitor.visit_bytes(value)
+        visitor.visit_borrowed_bytes(value)
     }
 
     fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -251,17 +258,23 @@ impl<'a> serde::Deserializer<'a> for Deserializer<'a> {
         V: serde::de::Visitor<'a>,
     {
         if name == FieldReference::NAME {
-            visitor.visit_newtype_struct(serde::de::value::StrDeserializer::new(
+            visitor.visit_newtype_struct(serde::de::value::BorrowedStrDeserializer::new(
                 self.value.as_field_reference_value_as_string()?,
             ))
         } else if name == VariableReference::NAME {
-            visitor.visit_newtype_struct(serde::de::value::StrDeserializer::new(
+            visitor.visit_newtype_struct(serde::de::value::BorrowedStrDeserializer::new(
                 self.value.as_variable_reference_value_as_string()?,
             ))
         } else if name == Reference::NAME {
-            visitor.visit_newtype_struct(serde::de::value::StrDeserializer::new(
+            visitor.visit_newtype_struct(serde::de::value::BorrowedStrDeserializer::new(
                 self.value.as_reference_value_as_string()?,
             ))
+        } else if name == Timestamp::NAME || name == LatLng::NAME {
+            // The inner value uses the encoding for this crate.
+            visitor.visit_newtype_struct(Self {
+                value: self.value,
+                human_readable: false,
+            })
         } else {
             visitor.visit_newtype_struct(self)
         }
@@ -304,10 +317,12 @@ impl<'a> serde::Deserializer<'a> for Deserializer<'a> {
         V: serde::de::Visitor<'a>,
     {
         visitor.visit_map(serde::de::value::MapDeserializer::new(
-            self.value
-                .as_fields()?
-                .iter()
-                .map(|(k, v)| (k.as_str(), Deserializer::new(v))),
+            self.value.as_fields()?.iter().map(|(k, v)| {
+                (
+                    serde::de::value::BorrowedStrDeserializer::new(k.as_str()),
+                    Deserializer::new(v),
+                )
+            }),
         ))
     }
 
@@ -340,7 +355,12 @@ impl<'a> serde::Deserializer<'a> for Deserializer<'a> {
                     .as_fields()?
                     .iter()
                     .filter(|(k, _)| fields.contains(&k.as_str()))
-                    .map(|(k, v)| (k.as_str(), Deserializer::new(v))),
+                    .map(|(k, v)| {
+                        (
+                            serde::de::value::BorrowedStrDeserializer::new(k.as_str()),
+                            Deserializer::new(v),
+                        )
+                    }),
             ))
         }
     }
@@ -362,10 +382,10 @@ impl<'a> serde::Deserializer<'a> for Deserializer<'a> {
         V: serde::de::Visitor<'a>,
     {
         match self.value.value_type()? {
-            ValueType::StringValue(s) => visitor.visit_str(s.as_str()),
+            ValueType::StringValue(s) => visitor.visit_borrowed_str(s.as_str()),
             ValueType::MapValue(_) => {
                 let (variant, _) = self.value.as_variant_value()?;
-                visitor.visit_str(variant.as_str())
+                visitor.visit_borrowed_str(variant.as_str())
             }
             _ => todo!(),
         }
@@ -377,6 +397,10 @@ impl<'a> serde::Deserializer<'a> for Deserializer<'a> {
     {
         visitor.visit_unit()
     }
+
+    fn is_human_readable(&self) -> bool {
+        self.human_readable
+    }
 }
 
 impl<'de> serde::de::IntoDeserializer<'de, Error> for Deserializer<'de> {
@@ -85,10 +85,12 @@ impl<'de> serde::de::VariantAccess<'de> for FirestoreEnumDeserializer<'de> {
         let (variant, value) = self.value.as_variant_value()?;
         if self.variants.contains(&variant.as_str()) {
             visitor.visit_map(serde::de::value::MapDeserializer::new(
-                value
-                    .as_fields()?
-                    .iter()
-                    .map(|(k, v)| (k.as_str(), Deserializer::new(v))),
+                value.as_fields()?.iter().map(|(k, v)| {
+                    (
+                        serde::de::value::BorrowedStrDeserializer::new(k.as_str()),
+                        Deserializer::new(v),
+                    )
+                }),
             ))
         } else {
             Err(<Error as serde::de::Error>::unknown_variant(
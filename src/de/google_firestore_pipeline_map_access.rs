@@ -27,10 +27,12 @@ impl<'de> serde::de::MapAccess<'de> for GoogleFirestorePipelineMapAccess<'de> {
             None => Ok(None),
             Some(field) => {
                 self.next = Some(field);
-                seed.deserialize(serde::de::value::StrDeserializer::new(match field {
-                    Fields::Marker => crate::Pipeline::NAME,
-                    Fields::Stages => "stages",
-                }))
+                seed.deserialize(serde::de::value::BorrowedStrDeserializer::new(
+                    match field {
+                        Fields::Marker => crate::Pipeline::NAME,
+                        Fields::Stages => "stages",
+                    },
+                ))
                 .map(Some)
             }
         }
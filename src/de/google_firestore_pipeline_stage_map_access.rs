@@ -27,12 +27,14 @@ impl<'de> serde::de::MapAccess<'de> for GoogleFirestorePipelineStageMapAccess<'d
             None => Ok(None),
             Some(field) => {
                 self.next = Some(field);
-                seed.deserialize(serde::de::value::StrDeserializer::new(match field {
-                    Fields::Marker => crate::Stage::NAME,
-                    Fields::Name => "name",
-                    Fields::Args => "args",
-                    Fields::Options => "options",
-                }))
+                seed.deserialize(serde::de::value::BorrowedStrDeserializer::new(
+                    match field {
+                        Fields::Marker => crate::Stage::NAME,
+                        Fields::Name => "name",
+                        Fields::Args => "args",
+                        Fields::Options => "options",
+                    },
+                ))
                 .map(Some)
             }
         }
@@ -46,17 +48,19 @@ impl<'de> serde::de::MapAccess<'de> for GoogleFirestorePipelineStageMapAccess<'d
             None => panic!("next_value_seed called before next_key_seed"),
             Some(field) => match field {
                 Fields::Marker => seed.deserialize(serde::de::value::UnitDeserializer::new()),
-                Fields::Name => {
-                    seed.deserialize(serde::de::value::StrDeserializer::new(&self.value.name))
-                }
+                Fields::Name => seed.deserialize(serde::de::value::BorrowedStrDeserializer::new(
+                    &self.value.name,
+                )),
                 Fields::Args => seed.deserialize(serde::de::value::SeqDeserializer::new(
                     self.value.args.iter().map(crate::de::Deserializer::new),
                 )),
                 Fields::Options => seed.deserialize(serde::de::value::MapDeserializer::new(
-                    self.value
-                        .options
-                        .iter()
-                        .map(|(k, v)| (k.as_str(), crate::de::Deserializer::new(v))),
+                    self.value.options.iter().map(|(k, v)| {
+                        (
+                            serde::de::value::BorrowedStrDeserializer::new(k.as_str()),
+                            crate::de::Deserializer::new(v),
+                        )
+                    }),
                 )),
             },
         }
@@ -1,22 +1,22 @@
 crate::de::private::enum_fields!(Marker, Latitude, Longitude);
 
-pub(crate) struct GoogleTypeLatLngMapAccess<'de> {
-    iter: std::slice::Iter<'de, Fields>,
-    next: Option<&'de Fields>,
-    value: &'de crate::google::r#type::LatLng,
+pub(crate) struct GoogleTypeLatLngMapAccess {
+    iter: std::slice::Iter<'static, Fields>,
+    next: Option<&'static Fields>,
+    value: crate::google::r#type::LatLng,
 }
 
-impl<'de> GoogleTypeLatLngMapAccess<'de> {
-    pub(crate) fn new(value: &'de crate::google::r#type::LatLng) -> Self {
+impl GoogleTypeLatLngMapAccess {
+    pub(crate) fn new(value: &crate::google::r#type::LatLng) -> Self {
         Self {
             iter: Fields::VALUES.iter(),
             next: None,
-            value,
+            value: *value,
         }
     }
 }
 
-impl<'de> serde::de::MapAccess<'de> for GoogleTypeLatLngMapAccess<'de> {
+impl<'de> serde::de::MapAccess<'de> for GoogleTypeLatLngMapAccess {
     type Error = crate::de::Error;
 
     fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
@@ -27,11 +27,13 @@ impl<'de> serde::de::MapAccess<'de> for GoogleTypeLatLngMapAccess<'de> {
             None => Ok(None),
             Some(field) => {
                 self.next = Some(field);
-                seed.deserialize(serde::de::value::StrDeserializer::new(match field {
-                    Fields::Marker => crate::LatLng::NAME,
-                    Fields::Latitude => "latitude",
-                    Fields::Longitude => "longitude",
-                }))
+                seed.deserialize(serde::de::value::BorrowedStrDeserializer::new(
+                    match field {
+                        Fields::Marker => crate::LatLng::NAME,
+                        Fields::Latitude => "latitude",
+                        Fields::Longitude => "longitude",
+                    },
+                ))
                 .map(Some)
             }
         }
@@ -0,0 +1,409 @@
+use crate::de::GoogleTypeLatLngMapAccess;
+use crate::de::OwnedGoogleFirestoreFunctionMapAccess;
+use crate::de::OwnedGoogleFirestorePipelineMapAccess;
+use crate::de::ProstTypesTimestampMapAccess;
+use crate::de::owned_firestore_enum_deserializer::OwnedFirestoreEnumDeserializer;
+use crate::google::firestore::v1::{Value, value::ValueType};
+use crate::value_type_name::ValueTypeName;
+use crate::{
+    Error, FieldReference, Function, LatLng, Pipeline, Reference, Timestamp, VariableReference,
+    error::ErrorCode, value_ext::ValueExt,
+};
+
+/// A Deserializer type which implements [`serde::Deserializer`] for an owned [`Value`].
+///
+/// Unlike [`Deserializer`](crate::Deserializer), strings, bytes and nested
+/// values are moved into the target type instead of being copied.
+#[derive(Debug)]
+pub struct OwnedDeserializer {
+    value: Value,
+    human_readable: bool,
+}
+
+impl OwnedDeserializer {
+    /// Creates a new [`OwnedDeserializer`].
+    pub fn new(value: Value) -> Self {
+        Self {
+            value,
+            human_readable: true,
+        }
+    }
+}
+
+impl<'de> serde::Deserializer<'de> for OwnedDeserializer {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        match self.value.into_value_type()? {
+            ValueType::NullValue(_) => visitor.visit_unit(),
+            ValueType::BooleanValue(v) => visitor.visit_bool(v),
+            ValueType::IntegerValue(v) => visitor.visit_i64(v),
+            ValueType::DoubleValue(v) => visitor.visit_f64(v),
+            ValueType::TimestampValue(v) => {
+                visitor.visit_map(ProstTypesTimestampMapAccess::new(&v))
+            }
+            ValueType::StringValue(v) => visitor.visit_string(v),
+            ValueType::BytesValue(v) => {
+                #[cfg(feature = "bytes")]
+                let v = Vec::from(v);
+                visitor.visit_byte_buf(v)
+            }
+            ValueType::ReferenceValue(v) => {
+                visitor.visit_map(serde::de::value::MapDeserializer::new(std::iter::once((
+                    Reference::NAME,
+                    serde::de::value::StringDeserializer::new(v),
+                ))))
+            }
+            ValueType::GeoPointValue(v) => visitor.visit_map(GoogleTypeLatLngMapAccess::new(&v)),
+            ValueType::ArrayValue(v) => visitor.visit_seq(serde::de::value::SeqDeserializer::new(
+                v.values.into_iter().map(OwnedDeserializer::new),
+            )),
+            ValueType::MapValue(map) => visitor.visit_map(serde::de::value::MapDeserializer::new(
+                map.fields
+                    .into_iter()
+                    .map(|(k, v)| (k, OwnedDeserializer::new(v))),
+            )),
+            ValueType::FieldReferenceValue(v) => {
+                visitor.visit_map(serde::de::value::MapDeserializer::new(std::iter::once((
+                    FieldReference::NAME,
+                    serde::de::value::StringDeserializer::new(v),
+                ))))
+            }
+            ValueType::VariableReferenceValue(v) => {
+                visitor.visit_map(serde::de::value::MapDeserializer::new(std::iter::once((
+                    VariableReference::NAME,
+                    serde::de::value::StringDeserializer::new(v),
+                ))))
+            }
+            ValueType::FunctionValue(v) => {
+                visitor.visit_map(OwnedGoogleFirestoreFunctionMapAccess::new(v))
+            }
+            ValueType::PipelineValue(v) => {
+                visitor.visit_map(OwnedGoogleFirestorePipelineMapAccess::new(v))
+            }
+        }
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        let value = self.value.as_boolean()?;
+        visitor.visit_bool(value)
+    }
+
+    fn deserialize_i8<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        let value = self.value.as_integer()?;
+        visitor.visit_i8(i8::try_from(value).map_err(|_| Error::from(ErrorCode::I8OutOfRange))?)
+    }
+
+    fn deserialize_i16<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        let value = self.value.as_integer()?;
+        visitor.visit_i16(i16::try_from(value).map_err(|_| Error::from(ErrorCode::I16OutOfRange))?)
+    }
+
+    fn deserialize_i32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        let value = self.value.as_integer()?;
+        visitor.visit_i32(i32::try_from(value).map_err(|_| Error::from(ErrorCode::I32OutOfRange))?)
+    }
+
+    fn deserialize_i64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        let value = self.value.as_integer()?;
+        visitor.visit_i64(value)
+    }
+
+    fn deserialize_u8<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        let value = self.value.as_integer()?;
+        visitor.visit_u8(u8::try_from(value).map_err(|_| Error::from(ErrorCode::U8OutOfRange))?)
+    }
+
+    fn deserialize_u16<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        let value = self.value.as_integer()?;
+        visitor.visit_u16(u16::try_from(value).map_err(|_| Error::from(ErrorCode::U16OutOfRange))?)
+    }
+
+    fn deserialize_u32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        let value = self.value.as_integer()?;
+        visitor.visit_u32(u32::try_from(value).map_err(|_| Error::from(ErrorCode::U32OutOfRange))?)
+    }
+
+    fn deserialize_u64<V>(self, _: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        Err(Error::from(ErrorCode::U64IsNotSupported))
+    }
+
+    fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        let value = self.value.as_double()?;
+        visitor.visit_f32(value as f32)
+    }
+
+    fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        let value = self.value.as_double()?;
+        visitor.visit_f64(value)
+    }
+
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        let value = self.value.as_string()?;
+        let mut chars = value.chars();
+        match (chars.next(), chars.next()) {
+            (None, None) => Err(Error::from(ErrorCode::StringIsEmpty)),
+            (None, Some(_)) => unreachable!(),
+            (Some(c), None) => visitor.visit_char(c),
+            (Some(_), Some(_)) => Err(Error::from(ErrorCode::TooManyChars)),
+        }
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        let value = self.value.into_string()?;
+        visitor.visit_string(value)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        let value = self.value.as_bytes()?;
+        visitor.visit_bytes(value)
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        let value = self.value.into_bytes()?;
+        visitor.visit_byte_buf(value)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        match self.value.value_type()? {
+            ValueType::NullValue(_) => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        self.value.as_null()?;
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        if name == FieldReference::NAME {
+            visitor.visit_newtype_struct(serde::de::value::StringDeserializer::new(
+                self.value.into_field_reference_value_as_string()?,
+            ))
+        } else if name == VariableReference::NAME {
+            visitor.visit_newtype_struct(serde::de::value::StringDeserializer::new(
+                self.value.into_variable_reference_value_as_string()?,
+            ))
+        } else if name == Reference::NAME {
+            visitor.visit_newtype_struct(serde::de::value::StringDeserializer::new(
+                self.value.into_reference_value_as_string()?,
+            ))
+        } else if name == Timestamp::NAME || name == LatLng::NAME {
+            // The inner value uses the encoding for this crate.
+            visitor.visit_newtype_struct(Self {
+                value: self.value,
+                human_readable: false,
+            })
+        } else {
+            visitor.visit_newtype_struct(self)
+        }
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        visitor.visit_seq(serde::de::value::SeqDeserializer::new(
+            self.value
+                .into_values()?
+                .into_iter()
+                .map(OwnedDeserializer::new),
+        ))
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        visitor.visit_map(serde::de::value::MapDeserializer::new(
+            self.value
+                .into_fields()?
+                .into_iter()
+                .map(|(k, v)| (k, OwnedDeserializer::new(v))),
+        ))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        if name == Function::NAME {
+            visitor.visit_map(OwnedGoogleFirestoreFunctionMapAccess::new(
+                self.value.into_function()?,
+            ))
+        } else if name == LatLng::NAME {
+            visitor.visit_map(GoogleTypeLatLngMapAccess::new(self.value.as_lat_lng()?))
+        } else if name == Pipeline::NAME {
+            visitor.visit_map(OwnedGoogleFirestorePipelineMapAccess::new(
+                self.value.into_pipeline()?,
+            ))
+        } else if name == Timestamp::NAME {
+            visitor.visit_map(ProstTypesTimestampMapAccess::new(
+                self.value.as_timestamp()?,
+            ))
+        } else {
+            visitor.visit_map(serde::de::value::MapDeserializer::new(
+                self.value
+                    .into_fields()?
+                    .into_iter()
+                    .filter(|(k, _)| fields.contains(&k.as_str()))
+                    .map(|(k, v)| (k, OwnedDeserializer::new(v))),
+            ))
+        }
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        visitor.visit_enum(OwnedFirestoreEnumDeserializer::new(self.value, variants)?)
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        match self.value.into_value_type()? {
+            ValueType::StringValue(s) => visitor.visit_string(s),
+            ValueType::MapValue(map) => {
+                let (variant, _) = Value {
+                    value_type: Some(ValueType::MapValue(map)),
+                }
+                .into_variant_value()?;
+                visitor.visit_string(variant)
+            }
+            value_type => Err(Error::invalid_value_type(
+                &value_type,
+                ValueTypeName::String,
+            )),
+        }
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.human_readable
+    }
+}
+
+impl<'de> serde::de::IntoDeserializer<'de, Error> for OwnedDeserializer {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
@@ -0,0 +1,109 @@
+use crate::google::firestore::v1::{Value, value::ValueType};
+use crate::{Error, error::ErrorCode, value_ext::ValueExt};
+
+use super::OwnedDeserializer;
+
+pub(super) struct OwnedFirestoreEnumDeserializer {
+    value: Value,
+    variants: &'static [&'static str],
+}
+
+impl OwnedFirestoreEnumDeserializer {
+    pub(super) fn new(value: Value, variants: &'static [&'static str]) -> Result<Self, Error> {
+        Ok(Self { value, variants })
+    }
+}
+
+impl<'de> serde::de::EnumAccess<'de> for OwnedFirestoreEnumDeserializer {
+    type Error = Error;
+    type Variant = OwnedFirestoreEnumDeserializer;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: serde::de::DeserializeSeed<'de>,
+    {
+        let variant = match self.value.value_type()? {
+            ValueType::StringValue(s) => s.as_str(),
+            ValueType::MapValue(_) => self.value.as_variant_value()?.0.as_str(),
+            _ => {
+                return Err(Error::from(ErrorCode::Custom(
+                    "enum must be a stringValue or a mapValue".to_string(),
+                )));
+            }
+        };
+        seed.deserialize(serde::de::value::StrDeserializer::<Error>::new(variant))
+            .map(|v| (v, self))
+    }
+}
+
+impl<'de> serde::de::VariantAccess<'de> for OwnedFirestoreEnumDeserializer {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        let variant_name = self.value.as_string()?;
+        if self.variants.contains(&variant_name.as_str()) {
+            Ok(())
+        } else {
+            Err(<Error as serde::de::Error>::unknown_variant(
+                variant_name.as_str(),
+                self.variants,
+            ))
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: serde::de::DeserializeSeed<'de>,
+    {
+        let (variant, value) = self.value.into_variant_value()?;
+        if self.variants.contains(&variant.as_str()) {
+            seed.deserialize(OwnedDeserializer::new(value))
+        } else {
+            Err(<Error as serde::de::Error>::unknown_variant(
+                &variant,
+                self.variants,
+            ))
+        }
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        let (variant, value) = self.value.into_variant_value()?;
+        if self.variants.contains(&variant.as_str()) {
+            visitor.visit_seq(serde::de::value::SeqDeserializer::new(
+                value.into_values()?.into_iter().map(OwnedDeserializer::new),
+            ))
+        } else {
+            Err(<Error as serde::de::Error>::unknown_variant(
+                &variant,
+                self.variants,
+            ))
+        }
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        let (variant, value) = self.value.into_variant_value()?;
+        if self.variants.contains(&variant.as_str()) {
+            visitor.visit_map(serde::de::value::MapDeserializer::new(
+                value
+                    .into_fields()?
+                    .into_iter()
+                    .map(|(k, v)| (k, OwnedDeserializer::new(v))),
+            ))
+        } else {
+            Err(<Error as serde::de::Error>::unknown_variant(
+                &variant,
+                self.variants,
+            ))
+        }
+    }
+}
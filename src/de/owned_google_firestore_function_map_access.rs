@@ -0,0 +1,65 @@
+crate::de::private::enum_fields!(Marker, Name, Args, Options);
+
+pub(crate) struct OwnedGoogleFirestoreFunctionMapAccess {
+    iter: std::slice::Iter<'static, Fields>,
+    next: Option<&'static Fields>,
+    value: crate::google::firestore::v1::Function,
+}
+
+impl OwnedGoogleFirestoreFunctionMapAccess {
+    pub(crate) fn new(value: crate::google::firestore::v1::Function) -> Self {
+        Self {
+            iter: Fields::VALUES.iter(),
+            next: None,
+            value,
+        }
+    }
+}
+
+impl<'de> serde::de::MapAccess<'de> for OwnedGoogleFirestoreFunctionMapAccess {
+    type Error = crate::de::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: serde::de::DeserializeSeed<'de>,
+    {
+        match self.iter.next() {
+            None => Ok(None),
+            Some(field) => {
+                self.next = Some(field);
+                seed.deserialize(serde::de::value::StrDeserializer::new(match field {
+                    Fields::Marker => crate::Function::NAME,
+                    Fields::Name => "name",
+                    Fields::Args => "args",
+                    Fields::Options => "options",
+                }))
+                .map(Some)
+            }
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::DeserializeSeed<'de>,
+    {
+        match self.next.take() {
+            None => panic!("next_value_seed called before next_key_seed"),
+            Some(field) => match field {
+                Fields::Marker => seed.deserialize(serde::de::value::UnitDeserializer::new()),
+                Fields::Name => seed.deserialize(serde::de::value::StringDeserializer::new(
+                    std::mem::take(&mut self.value.name),
+                )),
+                Fields::Args => seed.deserialize(serde::de::value::SeqDeserializer::new(
+                    std::mem::take(&mut self.value.args)
+                        .into_iter()
+                        .map(crate::de::OwnedDeserializer::new),
+                )),
+                Fields::Options => seed.deserialize(serde::de::value::MapDeserializer::new(
+                    std::mem::take(&mut self.value.options)
+                        .into_iter()
+                        .map(|(k, v)| (k, crate::de::OwnedDeserializer::new(v))),
+                )),
+            },
+        }
+    }
+}
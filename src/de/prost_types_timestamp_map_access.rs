@@ -1,22 +1,22 @@
 crate::de::private::enum_fields!(Marker, Seconds, Nanos);
 
-pub(crate) struct ProstTypesTimestampMapAccess<'de> {
-    iter: std::slice::Iter<'de, Fields>,
-    next: Option<&'de Fields>,
-    value: &'de prost_types::Timestamp,
+pub(crate) struct ProstTypesTimestampMapAccess {
+    iter: std::slice::Iter<'static, Fields>,
+    next: Option<&'static Fields>,
+    value: prost_types::Timestamp,
 }
 
-impl<'de> ProstTypesTimestampMapAccess<'de> {
-    pub(crate) fn new(value: &'de prost_types::Timestamp) -> Self {
+impl ProstTypesTimestampMapAccess {
+    pub(crate) fn new(value: &prost_types::Timestamp) -> Self {
         Self {
             iter: Fields::VALUES.iter(),
             next: None,
-            value,
+            value: *value,
         }
     }
 }
 
-impl<'de> serde::de::MapAccess<'de> for ProstTypesTimestampMapAccess<'de> {
+impl<'de> serde::de::MapAccess<'de> for ProstTypesTimestampMapAccess {
     type Error = crate::de::Error;
 
     fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
@@ -27,11 +27,13 @@ impl<'de> serde::de::MapAccess<'de> for ProstTypesTimestampMapAccess<'de> {
             None => Ok(None),
             Some(field) => {
                 self.next = Some(field);
-                seed.deserialize(serde::de::value::StrDeserializer::new(match field {
-                    Fields::Marker => crate::Timestamp::NAME,
-                    Fields::Seconds => "seconds",
-                    Fields::Nanos => "nanos",
-                }))
+                seed.deserialize(serde::de::value::BorrowedStrDeserializer::new(
+                    match field {
+                        Fields::Marker => crate::Timestamp::NAME,
+                        Fields::Seconds => "seconds",
+                        Fields::Nanos => "nanos",
+                    },
+                ))
                 .map(Some)
             }
         }
@@ -1,4 +1,10 @@
+pub(crate) mod bytes;
 pub(crate) mod lat_lng_as_geo_point;
+pub(crate) mod raw_value;
 pub(crate) mod string_as_field_reference;
 pub(crate) mod string_as_reference;
 pub(crate) mod timestamp_as_timestamp;
+#[cfg(feature = "uuid")]
+pub(crate) mod uuid_as_bytes;
+#[cfg(feature = "uuid")]
+pub(crate) mod uuid_as_string;
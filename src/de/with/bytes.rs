@@ -0,0 +1,28 @@
+use serde::Deserialize;
+
+use crate::Bytes;
+
+pub(crate) fn deserialize_bytes<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: From<Vec<u8>>,
+{
+    Bytes::deserialize(deserializer).map(|Bytes(v)| T::from(v))
+}
+
+pub(crate) fn deserialize_option_bytes<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: From<Vec<u8>>,
+{
+    Option::<Bytes>::deserialize(deserializer).map(|o| o.map(|Bytes(v)| T::from(v)))
+}
+
+pub(crate) fn deserialize_vec_bytes<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: From<Vec<u8>>,
+{
+    Vec::<Bytes>::deserialize(deserializer)
+        .map(|o| o.into_iter().map(|Bytes(v)| T::from(v)).collect())
+}
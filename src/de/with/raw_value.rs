@@ -0,0 +1,8 @@
+use crate::google::firestore::v1::Value;
+
+pub(crate) fn deserialize_raw_value<'de, D>(deserializer: D) -> Result<Value, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    crate::typ::raw_value::deserialize_raw_value(deserializer)
+}
@@ -0,0 +1,57 @@
+use serde::Deserialize;
+
+struct UuidAsBytes(uuid::Uuid);
+
+impl<'de> serde::Deserialize<'de> for UuidAsBytes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct UuidAsBytesVisitor;
+
+        impl serde::de::Visitor<'_> for UuidAsBytesVisitor {
+            type Value = UuidAsBytes;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("16 bytes")
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                uuid::Uuid::from_slice(v)
+                    .map(UuidAsBytes)
+                    .map_err(|_| E::invalid_length(v.len(), &self))
+            }
+        }
+
+        deserializer.deserialize_bytes(UuidAsBytesVisitor)
+    }
+}
+
+pub(crate) fn deserialize_uuid_as_bytes<'de, D>(deserializer: D) -> Result<uuid::Uuid, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    UuidAsBytes::deserialize(deserializer).map(|UuidAsBytes(uuid)| uuid)
+}
+
+pub(crate) fn deserialize_option_uuid_as_bytes<'de, D>(
+    deserializer: D,
+) -> Result<Option<uuid::Uuid>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Option::<UuidAsBytes>::deserialize(deserializer).map(|o| o.map(|UuidAsBytes(uuid)| uuid))
+}
+
+pub(crate) fn deserialize_vec_uuid_as_bytes<'de, D>(
+    deserializer: D,
+) -> Result<Vec<uuid::Uuid>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Vec::<UuidAsBytes>::deserialize(deserializer)
+        .map(|o| o.into_iter().map(|UuidAsBytes(uuid)| uuid).collect())
+}
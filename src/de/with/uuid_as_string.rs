@@ -0,0 +1,57 @@
+use serde::Deserialize;
+
+struct UuidAsString(uuid::Uuid);
+
+impl<'de> serde::Deserialize<'de> for UuidAsString {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct UuidAsStringVisitor;
+
+        impl serde::de::Visitor<'_> for UuidAsStringVisitor {
+            type Value = UuidAsString;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a UUID string")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                uuid::Uuid::parse_str(v)
+                    .map(UuidAsString)
+                    .map_err(|e| E::custom(format!("invalid UUID `{}`: {}", v, e)))
+            }
+        }
+
+        deserializer.deserialize_str(UuidAsStringVisitor)
+    }
+}
+
+pub(crate) fn deserialize_uuid_as_string<'de, D>(deserializer: D) -> Result<uuid::Uuid, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    UuidAsString::deserialize(deserializer).map(|UuidAsString(uuid)| uuid)
+}
+
+pub(crate) fn deserialize_option_uuid_as_string<'de, D>(
+    deserializer: D,
+) -> Result<Option<uuid::Uuid>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Option::<UuidAsString>::deserialize(deserializer).map(|o| o.map(|UuidAsString(uuid)| uuid))
+}
+
+pub(crate) fn deserialize_vec_uuid_as_string<'de, D>(
+    deserializer: D,
+) -> Result<Vec<uuid::Uuid>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Vec::<UuidAsString>::deserialize(deserializer)
+        .map(|o| o.into_iter().map(|UuidAsString(uuid)| uuid).collect())
+}
@@ -0,0 +1,46 @@
+//! Canonical decimal strings shared by the decimal `with` modules.
+//!
+//! The canonical form is `-?(0|[1-9][0-9]*)(\.[0-9]+)?` without negative
+//! zero. Exponents, signs other than a leading `-`, whitespace, separators
+//! and redundant leading zeros are rejected because they are ambiguous or
+//! not round-trippable.
+
+/// Returns `true` if `s` is a canonical decimal string.
+pub(crate) fn is_canonical(s: &str) -> bool {
+    let unsigned = s.strip_prefix('-').unwrap_or(s);
+    let (integer, fraction) = match unsigned.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (unsigned, None),
+    };
+    if integer.is_empty()
+        || !integer.bytes().all(|b| b.is_ascii_digit())
+        || (integer.len() > 1 && integer.starts_with('0'))
+    {
+        return false;
+    }
+    if let Some(fraction) = fraction
+        && (fraction.is_empty() || !fraction.bytes().all(|b| b.is_ascii_digit()))
+    {
+        return false;
+    }
+    let is_zero = unsigned.bytes().all(|b| b == b'0' || b == b'.');
+    !(s.starts_with('-') && is_zero)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_canonical() {
+        for s in ["0", "0.0", "1", "10", "1.50", "-1", "-0.01", "123.456"] {
+            assert!(is_canonical(s), "{}", s);
+        }
+        for s in [
+            "", "-", "+1", " 1", "1 ", "01", "-01", ".5", "1.", "1e3", "1E3", "1_000", "1,5", "-0",
+            "-0.00", "NaN", "inf",
+        ] {
+            assert!(!is_canonical(s), "{}", s);
+        }
+    }
+}
@@ -38,6 +38,10 @@ impl serde::ser::Error for Error {
 pub(crate) enum ErrorCode {
     #[error("{0}")]
     Custom(String),
+    #[error("evaluation failed: {0}")]
+    EvaluationFailed(String),
+    #[error("expression is not a function")]
+    ExprIsNotAFunction,
     #[error("field reference value must be a string")]
     FieldReferenceValueMustBeAString,
     #[error("i16 out of range")]
@@ -46,14 +50,31 @@ pub(crate) enum ErrorCode {
     I32OutOfRange,
     #[error("i8 out of range")]
     I8OutOfRange,
+    #[cfg(feature = "serde_json")]
+    #[error("invalid JSON: {0}")]
+    InvalidJson(String),
+    #[error("invalid query: {0}")]
+    InvalidQuery(String),
+    #[cfg(feature = "rest-json")]
+    #[error("invalid REST JSON: {0}")]
+    InvalidRestJson(String),
+    #[error("invalid RFC 3339 timestamp: {0}")]
+    InvalidRfc3339(String),
+    #[error("invalid stage: {0}")]
+    InvalidStage(String),
     #[error("key must be a string")]
     KeyMustBeAString,
+    #[cfg(feature = "geo-types")]
+    #[error("latitude must be in [-90, 90] and longitude must be in [-180, 180]: {0}")]
+    LatLngOutOfRange(String),
     #[error("maximum byte length (1,048,487 bytes = 1MiB - 89 bytes) exceeded")]
     MaximumByteLengthExceeded,
     #[error("reference value must be a string")]
     ReferenceValueMustBeAString,
     #[error("string is empty")]
     StringIsEmpty,
+    #[error("timestamp out of range (0001-01-01T00:00:00Z to 9999-12-31T23:59:59.999999999Z)")]
+    TimestampOutOfRange,
     #[error("too many chars")]
     TooManyChars,
     #[error("u16 out of range")]
@@ -64,6 +85,8 @@ pub(crate) enum ErrorCode {
     U64IsNotSupported,
     #[error("u8 out of range")]
     U8OutOfRange,
+    #[error("unsupported conversion: {0}")]
+    UnsupportedConversion(String),
     #[error("value type must be some")]
     ValueTypeMustBeSome,
     #[error("variable reference value must be a string")]
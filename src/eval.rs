@@ -0,0 +1,1093 @@
+//! In-memory evaluation of pipelines and [`StructuredQuery`]s over
+//! [`Document`]s.
+//!
+//! Intended for unit tests. The evaluator follows Firestore's value
+//! ordering and type semantics, but is not a complete reimplementation of
+//! the backend.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # fn main() -> anyhow::Result<()> {
+//! use serde_firestore_value::eval::execute_pipeline;
+//! use serde_firestore_value::expr::{field, lit};
+//! use serde_firestore_value::google::firestore::v1::Document;
+//! use serde_firestore_value::{to_value, Pipeline};
+//!
+//! let document = Document {
+//!     name: "projects/p/databases/(default)/documents/users/alice".to_string(),
+//!     fields: [("age".to_string(), to_value(&20)?)].into_iter().collect(),
+//!     create_time: None,
+//!     update_time: None,
+//! };
+//! let pipeline = Pipeline::collection("users").where_(field("age").gte(lit(18)));
+//! let rows = execute_pipeline(&pipeline, &[document])?;
+//! assert_eq!(rows.len(), 1);
+//! #     Ok(())
+//! # }
+//! ```
+
+use std::cmp::Ordering as CmpOrdering;
+use std::collections::BTreeMap;
+
+use crate::expr::{Expr, FunctionExpr, names};
+use crate::google::firestore::v1::{
+    ArrayValue, Cursor, Document, MapValue, StructuredQuery, Value,
+    structured_query::{
+        CollectionSelector, CompositeFilter, FieldFilter, Filter, Projection, UnaryFilter,
+        composite_filter, field_filter, filter::FilterType, unary_filter,
+    },
+    value::ValueType,
+};
+use crate::stage::{Direction, Ordering, TypedStage};
+use crate::structured_query::{limit_to_last, order_by, parent_path};
+use crate::value_ext::ValueExt as _;
+use crate::value_order::{compare, equals, is_nan, type_order};
+use crate::{Error, Pipeline, error::ErrorCode};
+
+/// Runs `pipeline` over `documents` and returns the result rows as
+/// `mapValue`s.
+///
+/// The pipeline must start with a `collection`, `collection_group`,
+/// `database` or `documents` stage and may be followed by `where`,
+/// `select`, `add_fields`, `remove_fields`, `sort`, `limit`, `offset`,
+/// `aggregate` and `distinct` stages.
+///
+/// Without a `sort` stage the rows are in document name order. Evaluation
+/// errors (e.g. an unknown function or arithmetic on a string) fail the
+/// whole pipeline. Comparisons with a missing field evaluate to `false`,
+/// and `is_nan` and `is_null` of a missing field are missing, so neither
+/// they nor their `not` match it.
+pub fn execute_pipeline(pipeline: &Pipeline, documents: &[Document]) -> Result<Vec<Value>, Error> {
+    let stages = pipeline.typed_stages()?;
+    let (source, stages) = stages
+        .split_first()
+        .ok_or_else(|| eval_error("pipeline has no stages".to_string()))?;
+    let mut rows = source_rows(source, documents)?;
+    for stage in stages {
+        rows = apply_stage(stage, rows)?;
+    }
+    Ok(rows
+        .into_iter()
+        .map(|row| Value::from_fields(row.fields))
+        .collect())
+}
+
+/// Runs `query` over `documents` and returns the matching documents, as
+/// the backend would for `query` under `parent`.
+///
+/// `parent` is the parent resource name of the query (e.g.
+/// `projects/p/databases/d/documents/users/alice`) or a document path
+/// relative to the database.
+///
+/// Filters compare values in Firestore's value ordering, as the index does:
+/// `1` equals `1.0`, NaN equals NaN and sorts before any other number, and
+/// range filters only match values of the same type. `NOT_EQUAL`, `NOT_IN`,
+/// `IS_NOT_NULL` and `IS_NOT_NAN` do not match null or missing fields, and
+/// `NOT_IN` with null matches nothing. A document is only returned if it
+/// has every field of the effective `order_by`, which ends with
+/// `__name__`.
+///
+/// `find_nearest` is not supported.
+pub fn execute_structured_query(
+    parent: &str,
+    query: &StructuredQuery,
+    documents: &[Document],
+) -> Result<Vec<Document>, Error> {
+    if query.find_nearest.is_some() {
+        return Err(eval_error("unsupported `find_nearest`".to_string()));
+    }
+    let parent = parent_path(parent);
+    let [selector] = query.from.as_slice() else {
+        return Err(eval_error(format!(
+            "`from` must have exactly one collection selector, got {}",
+            query.from.len()
+        )));
+    };
+    let orders = order_by(query)?;
+    for cursor in [&query.start_at, &query.end_at].into_iter().flatten() {
+        if cursor.values.len() > orders.len() {
+            return Err(eval_error(format!(
+                "cursor has {} values but the query has {} orders",
+                cursor.values.len(),
+                orders.len()
+            )));
+        }
+    }
+    let offset = usize::try_from(query.offset)
+        .map_err(|_| eval_error(format!("invalid offset {}", query.offset)))?;
+    let limit = query
+        .limit
+        .map(|limit| {
+            usize::try_from(limit).map_err(|_| eval_error(format!("invalid limit {limit}")))
+        })
+        .transpose()?;
+
+    let mut rows = vec![];
+    for document in documents {
+        if !in_scope(parent, selector, &document.name) {
+            continue;
+        }
+        let row = Row::from_document(document);
+        let Some(keys) = orders
+            .iter()
+            .map(|(path, _)| row.get(path))
+            .collect::<Option<Vec<_>>>()
+        else {
+            continue;
+        };
+        if let Some(filter) = &query.r#where
+            && !matches_filter(filter, &row)?
+        {
+            continue;
+        }
+        if let Some(cursor) = &query.start_at {
+            let ordering = compare_to_cursor(&keys, &orders, cursor);
+            if ordering == CmpOrdering::Less || (ordering == CmpOrdering::Equal && !cursor.before) {
+                continue;
+            }
+        }
+        if let Some(cursor) = &query.end_at {
+            let ordering = compare_to_cursor(&keys, &orders, cursor);
+            if ordering == CmpOrdering::Greater || (ordering == CmpOrdering::Equal && cursor.before)
+            {
+                continue;
+            }
+        }
+        rows.push((keys, document));
+    }
+    rows.sort_by(|(a, _), (b, _)| {
+        a.iter()
+            .zip(b)
+            .zip(&orders)
+            .map(|((a, b), (_, direction))| directed(compare(a, b), *direction))
+            .find(|ordering| *ordering != CmpOrdering::Equal)
+            .unwrap_or(CmpOrdering::Equal)
+    });
+
+    let projection = query
+        .select
+        .as_ref()
+        .filter(|projection| !projection.fields.is_empty());
+    Ok(rows
+        .into_iter()
+        .skip(offset)
+        .take(limit.unwrap_or(usize::MAX))
+        .map(|(_, document)| match projection {
+            Some(projection) => project(document, projection),
+            None => document.clone(),
+        })
+        .collect())
+}
+
+/// Runs `query` with `limit_to_last` as the client SDKs emulate it: runs
+/// the query of [`limit_to_last`] and reverses the results, so the last
+/// `limit` results are returned in the order of `query`.
+pub fn execute_structured_query_limit_to_last(
+    parent: &str,
+    query: &StructuredQuery,
+    limit: i32,
+    documents: &[Document],
+) -> Result<Vec<Document>, Error> {
+    let mut documents = execute_structured_query(parent, &limit_to_last(query, limit)?, documents)?;
+    documents.reverse();
+    Ok(documents)
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Row {
+    pub(crate) name: Option<String>,
+    pub(crate) fields: BTreeMap<String, Value>,
+}
+
+impl Row {
+    pub(crate) fn from_document(document: &Document) -> Self {
+        Self {
+            name: Some(document.name.clone()),
+            fields: document
+                .fields
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+        }
+    }
+
+    /// Returns the value at `path`, or `None` if it is missing.
+    pub(crate) fn get(&self, path: &str) -> Option<Value> {
+        if path == "__name__" {
+            return self.name.clone().map(Value::from_string_as_reference_value);
+        }
+        let segments = field_path_segments(path);
+        let (first, rest) = segments.split_first()?;
+        let mut value = self.fields.get(first)?;
+        for segment in rest {
+            match &value.value_type {
+                Some(ValueType::MapValue(MapValue { fields })) => value = fields.get(segment)?,
+                _ => return None,
+            }
+        }
+        Some(value.clone())
+    }
+}
+
+/// Splits a field path into segments. Segments may be quoted with
+/// backticks, in which `\` escapes the next character.
+pub(crate) fn field_path_segments(path: &str) -> Vec<String> {
+    let mut segments = vec![];
+    let mut segment = String::new();
+    let mut chars = path.chars();
+    let mut quoted = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '`' => quoted = !quoted,
+            '\\' if quoted => segment.extend(chars.next()),
+            '.' if !quoted => segments.push(std::mem::take(&mut segment)),
+            c => segment.push(c),
+        }
+    }
+    segments.push(segment);
+    segments
+}
+
+/// Returns the path of `name` relative to the `documents` root, without a
+/// leading `/`.
+pub(crate) fn relative_path(name: &str) -> &str {
+    match name.find("/documents/") {
+        Some(index) => &name[index + "/documents/".len()..],
+        None => name.trim_start_matches('/'),
+    }
+}
+
+/// Returns the parent collection path and the collection ID of a document
+/// path.
+pub(crate) fn parent_collection(path: &str) -> Option<(&str, &str)> {
+    let (parent, _) = path.rsplit_once('/')?;
+    let collection_id = parent.rsplit('/').next()?;
+    Some((parent, collection_id))
+}
+
+pub(crate) fn eval_error(message: String) -> Error {
+    Error::from(ErrorCode::EvaluationFailed(message))
+}
+
+fn source_rows(stage: &TypedStage, documents: &[Document]) -> Result<Vec<Row>, Error> {
+    let matches = |document: &&Document| -> bool {
+        let path = relative_path(&document.name);
+        match stage {
+            TypedStage::Collection(collection) => parent_collection(path)
+                .is_some_and(|(parent, _)| parent == collection.trim_start_matches('/')),
+            TypedStage::CollectionGroup {
+                parent,
+                collection_id,
+            } => {
+                let parent = parent.trim_start_matches('/');
+                parent_collection(path).is_some_and(|(_, id)| id == collection_id)
+                    && (parent.is_empty()
+                        || path
+                            .strip_prefix(parent)
+                            .is_some_and(|rest| rest.starts_with('/')))
+            }
+            TypedStage::Database => true,
+            TypedStage::Documents(paths) => paths.iter().any(|p| p.trim_start_matches('/') == path),
+            _ => false,
+        }
+    };
+    match stage {
+        TypedStage::Collection(_)
+        | TypedStage::CollectionGroup { .. }
+        | TypedStage::Database
+        | TypedStage::Documents(_) => {}
+        _ => {
+            return Err(eval_error(
+                "pipeline must start with a source stage".to_string(),
+            ));
+        }
+    }
+    let mut rows = documents
+        .iter()
+        .filter(matches)
+        .map(Row::from_document)
+        .collect::<Vec<_>>();
+    rows.sort_by(|a, b| compare(&name_value(a), &name_value(b)));
+    Ok(rows)
+}
+
+fn name_value(row: &Row) -> Value {
+    row.get("__name__").unwrap_or_else(Value::null)
+}
+
+fn apply_stage(stage: &TypedStage, rows: Vec<Row>) -> Result<Vec<Row>, Error> {
+    match stage {
+        TypedStage::AddFields(fields) => rows
+            .into_iter()
+            .map(|mut row| {
+                for (alias, expr) in fields {
+                    match evaluate(expr, &row)? {
+                        Some(value) => row.fields.insert(alias.clone(), value),
+                        None => row.fields.remove(alias),
+                    };
+                }
+                Ok(row)
+            })
+            .collect(),
+        TypedStage::Aggregate {
+            accumulators,
+            groups,
+        } => aggregate(&rows, accumulators, groups),
+        TypedStage::Distinct(groups) => aggregate(&rows, &BTreeMap::new(), groups),
+        TypedStage::Limit(limit) => {
+            let limit = usize::try_from(*limit)
+                .map_err(|_| eval_error(format!("invalid limit {limit}")))?;
+            Ok(rows.into_iter().take(limit).collect())
+        }
+        TypedStage::Offset(offset) => {
+            let offset = usize::try_from(*offset)
+                .map_err(|_| eval_error(format!("invalid offset {offset}")))?;
+            Ok(rows.into_iter().skip(offset).collect())
+        }
+        TypedStage::RemoveFields(fields) => Ok(rows
+            .into_iter()
+            .map(|mut row| {
+                for field in fields {
+                    remove_path(&mut row.fields, &field_path_segments(&field.0));
+                }
+                row
+            })
+            .collect()),
+        TypedStage::Select(fields) => rows
+            .into_iter()
+            .map(|row| {
+                let mut selected = BTreeMap::new();
+                for (alias, expr) in fields {
+                    if let Some(value) = evaluate(expr, &row)? {
+                        selected.insert(alias.clone(), value);
+                    }
+                }
+                Ok(Row {
+                    name: None,
+                    fields: selected,
+                })
+            })
+            .collect(),
+        TypedStage::Sort(orderings) => sort(rows, orderings),
+        TypedStage::Where(condition) => {
+            let mut filtered = vec![];
+            for row in rows {
+                if is_true(&evaluate(condition, &row)?) {
+                    filtered.push(row);
+                }
+            }
+            Ok(filtered)
+        }
+        TypedStage::Collection(_)
+        | TypedStage::CollectionGroup { .. }
+        | TypedStage::Database
+        | TypedStage::Documents(_) => Err(eval_error(
+            "a source stage must be the first stage".to_string(),
+        )),
+        TypedStage::FindNearest(_) => {
+            Err(eval_error("unsupported stage `find_nearest`".to_string()))
+        }
+        TypedStage::Other(stage) => Err(eval_error(format!("unsupported stage `{}`", stage.name))),
+    }
+}
+
+fn remove_path(fields: &mut BTreeMap<String, Value>, segments: &[String]) {
+    match segments {
+        [] => {}
+        [last] => {
+            fields.remove(last);
+        }
+        [first, rest @ ..] => {
+            if let Some(Value {
+                value_type: Some(ValueType::MapValue(MapValue { fields: nested })),
+            }) = fields.get_mut(first)
+            {
+                let mut map = std::mem::take(nested)
+                    .into_iter()
+                    .collect::<BTreeMap<_, _>>();
+                remove_path(&mut map, rest);
+                *nested = map.into_iter().collect();
+            }
+        }
+    }
+}
+
+fn sort(rows: Vec<Row>, orderings: &[Ordering]) -> Result<Vec<Row>, Error> {
+    let mut keyed = rows
+        .into_iter()
+        .map(|row| {
+            let keys = orderings
+                .iter()
+                .map(|ordering| evaluate(&ordering.expr, &row))
+                .collect::<Result<Vec<_>, Error>>()?;
+            Ok((keys, row))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    keyed.sort_by(|(a, _), (b, _)| {
+        for ((a, b), ordering) in a.iter().zip(b).zip(orderings) {
+            // A missing value sorts before any value.
+            let result = match (a, b) {
+                (None, None) => CmpOrdering::Equal,
+                (None, Some(_)) => CmpOrdering::Less,
+                (Some(_), None) => CmpOrdering::Greater,
+                (Some(a), Some(b)) => compare(a, b),
+            };
+            let result = match ordering.direction {
+                Direction::Ascending => result,
+                Direction::Descending => result.reverse(),
+            };
+            if result != CmpOrdering::Equal {
+                return result;
+            }
+        }
+        CmpOrdering::Equal
+    });
+    Ok(keyed.into_iter().map(|(_, row)| row).collect())
+}
+
+fn aggregate(
+    rows: &[Row],
+    accumulators: &BTreeMap<String, Expr>,
+    groups: &BTreeMap<String, Expr>,
+) -> Result<Vec<Row>, Error> {
+    let mut grouped: Vec<(Vec<Value>, Vec<&Row>)> = vec![];
+    for row in rows {
+        let keys = groups
+            .values()
+            .map(|expr| Ok(evaluate(expr, row)?.unwrap_or_else(Value::null)))
+            .collect::<Result<Vec<_>, Error>>()?;
+        match grouped.iter_mut().find(|(k, _)| {
+            k.iter()
+                .zip(&keys)
+                .all(|(a, b)| compare(a, b) == CmpOrdering::Equal)
+        }) {
+            Some((_, members)) => members.push(row),
+            None => grouped.push((keys, vec![row])),
+        }
+    }
+    if grouped.is_empty() && groups.is_empty() && !accumulators.is_empty() {
+        grouped.push((vec![], vec![]));
+    }
+    grouped.sort_by(|(a, _), (b, _)| {
+        a.iter()
+            .zip(b)
+            .map(|(a, b)| compare(a, b))
+            .find(|ordering| *ordering != CmpOrdering::Equal)
+            .unwrap_or(CmpOrdering::Equal)
+    });
+
+    grouped
+        .into_iter()
+        .map(|(keys, members)| {
+            let mut fields = groups.keys().cloned().zip(keys).collect::<BTreeMap<_, _>>();
+            for (alias, expr) in accumulators {
+                fields.insert(alias.clone(), accumulate(expr, &members)?);
+            }
+            Ok(Row { name: None, fields })
+        })
+        .collect()
+}
+
+fn accumulate(expr: &Expr, rows: &[&Row]) -> Result<Value, Error> {
+    let Expr::Function(FunctionExpr { name, args, .. }) = expr else {
+        return Err(eval_error("accumulator must be a function".to_string()));
+    };
+    let values = match args.as_slice() {
+        [] if name == names::COUNT => return Ok(Value::from_i64(rows.len() as i64)),
+        [arg] => rows
+            .iter()
+            .map(|row| evaluate(arg, row))
+            .collect::<Result<Vec<_>, Error>>()?
+            .into_iter()
+            .flatten()
+            .filter(|v| type_order(v) != 0)
+            .collect::<Vec<_>>(),
+        _ => return Err(eval_error(format!("`{name}` expects 1 argument"))),
+    };
+    match name.as_str() {
+        names::COUNT => Ok(Value::from_i64(values.len() as i64)),
+        names::COUNT_DISTINCT => {
+            let mut distinct: Vec<&Value> = vec![];
+            for value in &values {
+                if !distinct
+                    .iter()
+                    .any(|d| compare(d, value) == CmpOrdering::Equal)
+                {
+                    distinct.push(value);
+                }
+            }
+            Ok(Value::from_i64(distinct.len() as i64))
+        }
+        names::SUM | names::AVERAGE => {
+            let numbers = values
+                .iter()
+                .filter_map(Number::from_value)
+                .collect::<Vec<_>>();
+            if numbers.is_empty() {
+                return Ok(Value::null());
+            }
+            if name == names::AVERAGE {
+                let sum = numbers.iter().map(|n| n.as_f64()).sum::<f64>();
+                return Ok(Value::from_f64(sum / numbers.len() as f64));
+            }
+            let mut sum = Number::Integer(0);
+            for n in numbers {
+                sum = sum.add(n);
+            }
+            Ok(sum.into_value())
+        }
+        names::MINIMUM => Ok(values
+            .into_iter()
+            .min_by(compare)
+            .unwrap_or_else(Value::null)),
+        names::MAXIMUM => Ok(values
+            .into_iter()
+            .max_by(compare)
+            .unwrap_or_else(Value::null)),
+        _ => Err(eval_error(format!("unknown accumulator `{name}`"))),
+    }
+}
+
+fn is_true(value: &Option<Value>) -> bool {
+    matches!(
+        value,
+        Some(Value {
+            value_type: Some(ValueType::BooleanValue(true))
+        })
+    )
+}
+
+/// Returns `true` if the document `name` is in a collection `selector`
+/// selects below `parent`.
+fn in_scope(parent: &str, selector: &CollectionSelector, name: &str) -> bool {
+    let Some((collection, collection_id)) = parent_collection(relative_path(name)) else {
+        return false;
+    };
+    if selector.all_descendants {
+        (selector.collection_id.is_empty() || collection_id == selector.collection_id)
+            && (parent.is_empty()
+                || collection
+                    .strip_prefix(parent)
+                    .is_some_and(|rest| rest.starts_with('/')))
+    } else {
+        collection_id == selector.collection_id
+            && match collection.rsplit_once('/') {
+                Some((collection_parent, _)) => collection_parent == parent,
+                None => parent.is_empty(),
+            }
+    }
+}
+
+fn matches_filter(filter: &Filter, row: &Row) -> Result<bool, Error> {
+    match &filter.filter_type {
+        Some(FilterType::CompositeFilter(CompositeFilter { op, filters })) => {
+            match composite_filter::Operator::try_from(*op) {
+                Ok(composite_filter::Operator::And) => {
+                    for filter in filters {
+                        if !matches_filter(filter, row)? {
+                            return Ok(false);
+                        }
+                    }
+                    Ok(true)
+                }
+                Ok(composite_filter::Operator::Or) => {
+                    for filter in filters {
+                        if matches_filter(filter, row)? {
+                            return Ok(true);
+                        }
+                    }
+                    Ok(false)
+                }
+                _ => Err(eval_error(format!("unknown composite operator {op}"))),
+            }
+        }
+        Some(FilterType::FieldFilter(FieldFilter { field, op, value })) => {
+            let (Some(field), Some(value)) = (field, value) else {
+                return Err(eval_error(
+                    "field filter must have a field and a value".to_string(),
+                ));
+            };
+            matches_field_filter(*op, row.get(&field.field_path).as_ref(), value)
+        }
+        Some(FilterType::UnaryFilter(UnaryFilter {
+            op,
+            operand_type: Some(unary_filter::OperandType::Field(field)),
+        })) => {
+            let other = row.get(&field.field_path);
+            let present = other.as_ref().is_some_and(|v| type_order(v) != 0);
+            Ok(match unary_filter::Operator::try_from(*op) {
+                Ok(unary_filter::Operator::IsNan) => other.as_ref().is_some_and(is_nan),
+                Ok(unary_filter::Operator::IsNull) => {
+                    other.as_ref().is_some_and(|v| type_order(v) == 0)
+                }
+                Ok(unary_filter::Operator::IsNotNan) => {
+                    present && !other.as_ref().is_some_and(is_nan)
+                }
+                Ok(unary_filter::Operator::IsNotNull) => present,
+                _ => return Err(eval_error(format!("unknown unary operator {op}"))),
+            })
+        }
+        Some(FilterType::UnaryFilter(_)) => {
+            Err(eval_error("unary filter must have a field".to_string()))
+        }
+        None => Err(eval_error("filter has no type".to_string())),
+    }
+}
+
+fn matches_field_filter(op: i32, other: Option<&Value>, value: &Value) -> Result<bool, Error> {
+    use field_filter::Operator;
+    let same = |a: &Value, b: &Value| compare(a, b) == CmpOrdering::Equal;
+    let is_null = |v: &Value| type_order(v) == 0;
+    let op = match Operator::try_from(op) {
+        Ok(Operator::Unspecified) | Err(_) => {
+            return Err(eval_error(format!("unknown field filter operator {op}")));
+        }
+        Ok(op) => op,
+    };
+    let list = match op {
+        Operator::In | Operator::NotIn | Operator::ArrayContainsAny => {
+            array(op.as_str_name(), value)?
+        }
+        _ => &[],
+    };
+    if op == Operator::NotIn && list.iter().any(is_null) {
+        return Ok(false);
+    }
+    let Some(other) = other else {
+        return Ok(false);
+    };
+    let range = |expected: &[CmpOrdering]| {
+        type_order(other) == type_order(value) && expected.contains(&compare(other, value))
+    };
+    Ok(match op {
+        Operator::LessThan => range(&[CmpOrdering::Less]),
+        Operator::LessThanOrEqual => range(&[CmpOrdering::Less, CmpOrdering::Equal]),
+        Operator::GreaterThan => range(&[CmpOrdering::Greater]),
+        Operator::GreaterThanOrEqual => range(&[CmpOrdering::Greater, CmpOrdering::Equal]),
+        Operator::Equal => same(other, value),
+        Operator::NotEqual => !is_null(other) && !same(other, value),
+        Operator::ArrayContains => array_values(other).iter().any(|v| same(v, value)),
+        Operator::In => list.iter().any(|v| same(other, v)),
+        Operator::ArrayContainsAny => array_values(other)
+            .iter()
+            .any(|v| list.iter().any(|l| same(v, l))),
+        Operator::NotIn => !is_null(other) && !list.iter().any(|v| same(other, v)),
+        Operator::Unspecified => false,
+    })
+}
+
+/// Returns the elements of `value`, or nothing if it is not an array.
+fn array_values(value: &Value) -> &[Value] {
+    match &value.value_type {
+        Some(ValueType::ArrayValue(ArrayValue { values })) => values,
+        _ => &[],
+    }
+}
+
+/// Compares the `order_by` values of a document with the prefix of them in
+/// `cursor`.
+fn compare_to_cursor(
+    keys: &[Value],
+    orders: &[(String, Direction)],
+    cursor: &Cursor,
+) -> CmpOrdering {
+    keys.iter()
+        .zip(orders)
+        .zip(&cursor.values)
+        .map(|((key, (_, direction)), value)| directed(compare(key, value), *direction))
+        .find(|ordering| *ordering != CmpOrdering::Equal)
+        .unwrap_or(CmpOrdering::Equal)
+}
+
+fn directed(ordering: CmpOrdering, direction: Direction) -> CmpOrdering {
+    match direction {
+        Direction::Ascending => ordering,
+        Direction::Descending => ordering.reverse(),
+    }
+}
+
+/// Returns `document` with only the fields of `projection`.
+fn project(document: &Document, projection: &Projection) -> Document {
+    let row = Row::from_document(document);
+    let mut fields = BTreeMap::new();
+    for field in &projection.fields {
+        if field.field_path != "__name__"
+            && let Some(value) = row.get(&field.field_path)
+        {
+            insert_path(&mut fields, &field_path_segments(&field.field_path), value);
+        }
+    }
+    Document {
+        name: document.name.clone(),
+        fields: fields.into_iter().collect(),
+        create_time: document.create_time,
+        update_time: document.update_time,
+    }
+}
+
+fn insert_path(fields: &mut BTreeMap<String, Value>, segments: &[String], value: Value) {
+    match segments {
+        [] => {}
+        [last] => {
+            fields.insert(last.clone(), value);
+        }
+        [first, rest @ ..] => {
+            let entry = fields
+                .entry(first.clone())
+                .or_insert_with(|| Value::from_fields(Vec::<(String, Value)>::new()));
+            if let Some(ValueType::MapValue(MapValue { fields: nested })) = &mut entry.value_type {
+                let mut map = std::mem::take(nested)
+                    .into_iter()
+                    .collect::<BTreeMap<_, _>>();
+                insert_path(&mut map, rest, value);
+                *nested = map.into_iter().collect();
+            }
+        }
+    }
+}
+
+/// Evaluates `expr` against `row`. Returns `None` if the result is missing
+/// (e.g. a reference to a missing field).
+pub(crate) fn evaluate(expr: &Expr, row: &Row) -> Result<Option<Value>, Error> {
+    match expr {
+        Expr::Constant(value) => Ok(Some(value.clone())),
+        Expr::Field(field_reference) => Ok(row.get(&field_reference.0)),
+        Expr::Function(function) => call(function, row),
+        Expr::Variable(variable) => Err(eval_error(format!("unbound variable `{}`", variable.0))),
+    }
+}
+
+fn call(function: &FunctionExpr, row: &Row) -> Result<Option<Value>, Error> {
+    let name = function.name.as_str();
+    let arity = |n: usize| -> Result<(), Error> {
+        if function.args.len() == n {
+            Ok(())
+        } else {
+            Err(eval_error(format!(
+                "`{name}` expects {n} argument(s), got {}",
+                function.args.len()
+            )))
+        }
+    };
+
+    // Functions which do not evaluate all of their arguments.
+    match name {
+        names::AND | names::OR => {
+            let short_circuit = name == names::OR;
+            let mut unknown = false;
+            for arg in &function.args {
+                match boolean(name, evaluate(arg, row)?)? {
+                    Some(b) if b == short_circuit => return Ok(Some(Value::from_bool(b))),
+                    Some(_) => {}
+                    None => unknown = true,
+                }
+            }
+            return Ok(Some(if unknown {
+                Value::null()
+            } else {
+                Value::from_bool(!short_circuit)
+            }));
+        }
+        names::CONDITIONAL => {
+            arity(3)?;
+            let condition = evaluate(&function.args[0], row)?;
+            return evaluate(&function.args[if is_true(&condition) { 1 } else { 2 }], row);
+        }
+        names::EXISTS => {
+            arity(1)?;
+            return Ok(Some(Value::from_bool(
+                evaluate(&function.args[0], row)?.is_some(),
+            )));
+        }
+        _ => {}
+    }
+
+    let args = function
+        .args
+        .iter()
+        .map(|arg| evaluate(arg, row))
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let result = match name {
+        names::EQUAL
+        | names::NOT_EQUAL
+        | names::LESS_THAN
+        | names::LESS_THAN_OR_EQUAL
+        | names::GREATER_THAN
+        | names::GREATER_THAN_OR_EQUAL => {
+            arity(2)?;
+            let (Some(a), Some(b)) = (&args[0], &args[1]) else {
+                return Ok(Some(Value::from_bool(false)));
+            };
+            let result = match name {
+                names::EQUAL => equals(a, b),
+                names::NOT_EQUAL => !equals(a, b),
+                _ if type_order(a) != type_order(b) || is_nan(a) || is_nan(b) => false,
+                names::LESS_THAN => compare(a, b) == CmpOrdering::Less,
+                names::LESS_THAN_OR_EQUAL => compare(a, b) != CmpOrdering::Greater,
+                names::GREATER_THAN => compare(a, b) == CmpOrdering::Greater,
+                _ => compare(a, b) != CmpOrdering::Less,
+            };
+            Value::from_bool(result)
+        }
+        names::EQUAL_ANY | names::NOT_EQUAL_ANY => {
+            arity(2)?;
+            let (Some(a), Some(list)) = (&args[0], &args[1]) else {
+                return Ok(Some(Value::from_bool(false)));
+            };
+            let found = array(name, list)?.iter().any(|v| equals(a, v));
+            Value::from_bool(found == (name == names::EQUAL_ANY))
+        }
+        names::IS_NAN => {
+            arity(1)?;
+            let Some(value) = &args[0] else {
+                return Ok(None);
+            };
+            Value::from_bool(is_nan(value))
+        }
+        names::IS_NULL => {
+            arity(1)?;
+            let Some(value) = &args[0] else {
+                return Ok(None);
+            };
+            Value::from_bool(type_order(value) == 0)
+        }
+        names::NOT => {
+            arity(1)?;
+            match boolean(name, args[0].clone())? {
+                Some(b) => Value::from_bool(!b),
+                None => Value::null(),
+            }
+        }
+        names::XOR => {
+            let mut result = false;
+            for arg in args {
+                match boolean(name, arg)? {
+                    Some(b) => result ^= b,
+                    None => return Ok(Some(Value::null())),
+                }
+            }
+            Value::from_bool(result)
+        }
+        names::ADD | names::SUBTRACT | names::MULTIPLY | names::DIVIDE | names::MOD => {
+            arity(2)?;
+            let (Some(a), Some(b)) = (&args[0], &args[1]) else {
+                return Ok(None);
+            };
+            if type_order(a) == 0 || type_order(b) == 0 {
+                return Ok(Some(Value::null()));
+            }
+            let (Some(a), Some(b)) = (Number::from_value(a), Number::from_value(b)) else {
+                return Err(eval_error(format!("`{name}` expects numbers")));
+            };
+            arithmetic(name, a, b)?.into_value()
+        }
+        names::ARRAY_CONTAINS => {
+            arity(2)?;
+            let (Some(list), Some(v)) = (&args[0], &args[1]) else {
+                return Ok(Some(Value::from_bool(false)));
+            };
+            Value::from_bool(array(name, list)?.iter().any(|e| equals(e, v)))
+        }
+        names::ARRAY_CONTAINS_ALL | names::ARRAY_CONTAINS_ANY => {
+            arity(2)?;
+            let (Some(list), Some(values)) = (&args[0], &args[1]) else {
+                return Ok(Some(Value::from_bool(false)));
+            };
+            let (list, values) = (array(name, list)?, array(name, values)?);
+            let contains = |v: &Value| list.iter().any(|e| equals(e, v));
+            Value::from_bool(if name == names::ARRAY_CONTAINS_ALL {
+                values.iter().all(contains)
+            } else {
+                values.iter().any(contains)
+            })
+        }
+        names::ARRAY_CONCAT => {
+            let mut values = vec![];
+            for arg in &args {
+                let Some(arg) = arg else { return Ok(None) };
+                values.extend(array(name, arg)?.iter().cloned());
+            }
+            Value::from_values(values)
+        }
+        names::ARRAY_LENGTH => {
+            arity(1)?;
+            let Some(list) = &args[0] else {
+                return Ok(None);
+            };
+            Value::from_i64(array(name, list)?.len() as i64)
+        }
+        names::ARRAY_REVERSE => {
+            arity(1)?;
+            let Some(list) = &args[0] else {
+                return Ok(None);
+            };
+            Value::from_values(array(name, list)?.iter().rev().cloned().collect())
+        }
+        names::MAP_GET => {
+            arity(2)?;
+            let (Some(map), Some(key)) = (&args[0], &args[1]) else {
+                return Ok(None);
+            };
+            let key = string(name, key)?;
+            match &map.value_type {
+                Some(ValueType::MapValue(MapValue { fields })) => {
+                    return Ok(fields.get(key).cloned());
+                }
+                _ => return Err(eval_error(format!("`{name}` expects a map"))),
+            }
+        }
+        names::STARTS_WITH | names::ENDS_WITH | names::STRING_CONTAINS => {
+            arity(2)?;
+            let (Some(s), Some(t)) = (&args[0], &args[1]) else {
+                return Ok(Some(Value::from_bool(false)));
+            };
+            let (s, t) = (string(name, s)?, string(name, t)?);
+            Value::from_bool(match name {
+                names::STARTS_WITH => s.starts_with(t),
+                names::ENDS_WITH => s.ends_with(t),
+                _ => s.contains(t),
+            })
+        }
+        names::STRING_CONCAT => {
+            let mut result = String::new();
+            for arg in &args {
+                let Some(arg) = arg else { return Ok(None) };
+                result.push_str(string(name, arg)?);
+            }
+            Value::from_string(result)
+        }
+        names::TO_LOWER | names::TO_UPPER | names::TRIM | names::CHAR_LENGTH => {
+            arity(1)?;
+            let Some(s) = &args[0] else { return Ok(None) };
+            let s = string(name, s)?;
+            match name {
+                names::TO_LOWER => Value::from_string(s.to_lowercase()),
+                names::TO_UPPER => Value::from_string(s.to_uppercase()),
+                names::TRIM => Value::from_string(s.trim().to_string()),
+                _ => Value::from_i64(s.chars().count() as i64),
+            }
+        }
+        names::BYTE_LENGTH => {
+            arity(1)?;
+            let Some(v) = &args[0] else { return Ok(None) };
+            match &v.value_type {
+                Some(ValueType::StringValue(s)) => Value::from_i64(s.len() as i64),
+                Some(ValueType::BytesValue(b)) => Value::from_i64(b.len() as i64),
+                _ => return Err(eval_error(format!("`{name}` expects a string or bytes"))),
+            }
+        }
+        names::AVERAGE
+        | names::COUNT
+        | names::COUNT_DISTINCT
+        | names::MAXIMUM
+        | names::MINIMUM
+        | names::SUM => {
+            return Err(eval_error(format!(
+                "accumulator `{name}` is only allowed in `aggregate`"
+            )));
+        }
+        _ => return Err(eval_error(format!("unknown function `{name}`"))),
+    };
+    Ok(Some(result))
+}
+
+fn array<'a>(name: &str, value: &'a Value) -> Result<&'a [Value], Error> {
+    match &value.value_type {
+        Some(ValueType::ArrayValue(ArrayValue { values })) => Ok(values),
+        _ => Err(eval_error(format!("`{name}` expects an array"))),
+    }
+}
+
+fn boolean(name: &str, value: Option<Value>) -> Result<Option<bool>, Error> {
+    match value.and_then(|v| v.value_type) {
+        Some(ValueType::BooleanValue(b)) => Ok(Some(b)),
+        None | Some(ValueType::NullValue(_)) => Ok(None),
+        Some(_) => Err(eval_error(format!("`{name}` expects booleans"))),
+    }
+}
+
+fn string<'a>(name: &str, value: &'a Value) -> Result<&'a str, Error> {
+    match &value.value_type {
+        Some(ValueType::StringValue(s)) => Ok(s),
+        _ => Err(eval_error(format!("`{name}` expects a string"))),
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Number {
+    Double(f64),
+    Integer(i64),
+}
+
+impl Number {
+    fn from_value(value: &Value) -> Option<Self> {
+        match value.value_type {
+            Some(ValueType::DoubleValue(d)) => Some(Number::Double(d)),
+            Some(ValueType::IntegerValue(i)) => Some(Number::Integer(i)),
+            _ => None,
+        }
+    }
+
+    fn as_f64(&self) -> f64 {
+        match self {
+            Number::Double(d) => *d,
+            Number::Integer(i) => *i as f64,
+        }
+    }
+
+    fn add(self, other: Number) -> Number {
+        match (self, other) {
+            (Number::Integer(a), Number::Integer(b)) => a
+                .checked_add(b)
+                .map(Number::Integer)
+                .unwrap_or(Number::Double(a as f64 + b as f64)),
+            (a, b) => Number::Double(a.as_f64() + b.as_f64()),
+        }
+    }
+
+    fn into_value(self) -> Value {
+        match self {
+            Number::Double(d) => Value::from_f64(d),
+            Number::Integer(i) => Value::from_i64(i),
+        }
+    }
+}
+
+fn arithmetic(name: &str, a: Number, b: Number) -> Result<Number, Error> {
+    match (a, b) {
+        (Number::Integer(a), Number::Integer(b)) => {
+            let result = match name {
+                names::ADD => a.checked_add(b),
+                names::SUBTRACT => a.checked_sub(b),
+                names::MULTIPLY => a.checked_mul(b),
+                names::DIVIDE => {
+                    if b == 0 {
+                        return Err(eval_error("division by zero".to_string()));
+                    }
+                    a.checked_div(b)
+                }
+                _ => {
+                    if b == 0 {
+                        return Err(eval_error("division by zero".to_string()));
+                    }
+                    a.checked_rem(b)
+                }
+            };
+            result
+                .map(Number::Integer)
+                .ok_or_else(|| eval_error(format!("`{name}` overflowed")))
+        }
+        (a, b) => {
+            let (a, b) = (a.as_f64(), b.as_f64());
+            Ok(Number::Double(match name {
+                names::ADD => a + b,
+                names::SUBTRACT => a - b,
+                names::MULTIPLY => a * b,
+                names::DIVIDE => a / b,
+                _ => a % b,
+            }))
+        }
+    }
+}
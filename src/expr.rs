@@ -0,0 +1,480 @@
+//! Typed expressions for `functionValue`.
+//!
+//! [`Expr`] is a tree of constants, field references, variable references
+//! and functions. Build it with [`field`], [`lit`] and the methods of
+//! [`Expr`], then convert it to a [`Value`] or a [`Function`]. Any
+//! [`Value`] or [`Function`] can be converted back to an [`Expr`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! # fn main() -> anyhow::Result<()> {
+//! use serde_firestore_value::expr::{field, lit, Expr};
+//! use serde_firestore_value::Function;
+//!
+//! let expr = field("price")
+//!     .multiply(lit(2))
+//!     .gt(lit(100))
+//!     .and(field("active").eq(lit(true)));
+//! let function = Function::try_from(expr.clone())?;
+//! assert_eq!(function.name, "and");
+//! assert_eq!(Expr::from(function), expr);
+//! #     Ok(())
+//! # }
+//! ```
+
+pub mod names;
+
+#[cfg(feature = "btree-map")]
+use std::collections::BTreeMap;
+#[cfg(feature = "hash-map")]
+use std::collections::HashMap;
+
+use crate::google::firestore::v1::{Value, value::ValueType};
+use crate::value_ext::ValueExt as _;
+use crate::{
+    Bytes, Error, FieldReference, Function, LatLng, Reference, Timestamp, VariableReference,
+    error::ErrorCode,
+};
+
+/// An expression.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    /// A constant value. `fieldReferenceValue`, `variableReferenceValue`
+    /// and `functionValue` are never stored here.
+    Constant(Value),
+    /// A `fieldReferenceValue`.
+    Field(FieldReference),
+    /// A `functionValue`.
+    Function(FunctionExpr),
+    /// A `variableReferenceValue`.
+    Variable(VariableReference),
+}
+
+/// A `functionValue` whose arguments are [`Expr`]s.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FunctionExpr {
+    /// The name of the function. See [`names`].
+    pub name: String,
+    /// Ordered list of arguments.
+    pub args: Vec<Expr>,
+    /// Optional named arguments.
+    #[cfg(feature = "btree-map")]
+    pub options: BTreeMap<String, Value>,
+    /// Optional named arguments.
+    #[cfg(feature = "hash-map")]
+    pub options: HashMap<String, Value>,
+}
+
+/// Creates a field reference expression.
+pub fn field<S: Into<String>>(path: S) -> Expr {
+    Expr::Field(FieldReference(path.into()))
+}
+
+/// Creates a function expression without options.
+pub fn function<S, I>(name: S, args: I) -> Expr
+where
+    S: Into<String>,
+    I: IntoIterator,
+    I::Item: Into<Expr>,
+{
+    Expr::Function(FunctionExpr {
+        name: name.into(),
+        args: args.into_iter().map(Into::into).collect(),
+        options: Default::default(),
+    })
+}
+
+/// Creates a constant expression from a [`Literal`].
+///
+/// Field references, variable references and functions become the
+/// corresponding expressions. Use [`try_lit`] for other serializable values.
+pub fn lit<T: Literal>(value: T) -> Expr {
+    Expr::from(value.to_literal_value())
+}
+
+/// Creates a constant expression from any serializable value via
+/// [`to_value`](crate::to_value).
+///
+/// Serialized field references, variable references and functions become
+/// the corresponding expressions.
+pub fn try_lit<T: serde::Serialize>(value: T) -> Result<Expr, Error> {
+    crate::to_value(&value).map(Expr::from)
+}
+
+/// Creates a variable reference expression.
+pub fn var<S: Into<String>>(name: S) -> Expr {
+    Expr::Variable(VariableReference(name.into()))
+}
+
+/// A value which [`lit`] converts to a [`Value`] without failing.
+///
+/// Values which [`to_value`](crate::to_value) may reject (e.g. `u64`, which
+/// may not fit in `integerValue`) do not implement this trait. Use
+/// [`try_lit`] for them. Unlike [`to_value`](crate::to_value), the length of
+/// strings is not checked.
+pub trait Literal {
+    /// Returns the [`Value`] of `self`.
+    fn to_literal_value(&self) -> Value;
+}
+
+macro_rules! integer_literal {
+    ($($ty:ty),*) => {
+        $(
+            impl Literal for $ty {
+                fn to_literal_value(&self) -> Value {
+                    Value::from_i64(i64::from(*self))
+                }
+            }
+        )*
+    };
+}
+
+integer_literal!(i8, i16, i32, i64, u8, u16, u32);
+
+impl Literal for f32 {
+    fn to_literal_value(&self) -> Value {
+        Value::from_f64(f64::from(*self))
+    }
+}
+
+impl Literal for f64 {
+    fn to_literal_value(&self) -> Value {
+        Value::from_f64(*self)
+    }
+}
+
+impl Literal for bool {
+    fn to_literal_value(&self) -> Value {
+        Value::from_bool(*self)
+    }
+}
+
+impl Literal for char {
+    fn to_literal_value(&self) -> Value {
+        Value::from_string(self.to_string())
+    }
+}
+
+impl Literal for str {
+    fn to_literal_value(&self) -> Value {
+        Value::from_string(self.to_owned())
+    }
+}
+
+impl Literal for String {
+    fn to_literal_value(&self) -> Value {
+        Value::from_string(self.clone())
+    }
+}
+
+impl Literal for () {
+    fn to_literal_value(&self) -> Value {
+        Value::null()
+    }
+}
+
+impl<T: Literal> Literal for Option<T> {
+    fn to_literal_value(&self) -> Value {
+        match self {
+            Some(value) => value.to_literal_value(),
+            None => Value::null(),
+        }
+    }
+}
+
+impl<T: Literal> Literal for [T] {
+    fn to_literal_value(&self) -> Value {
+        Value::from_values(self.iter().map(Literal::to_literal_value).collect())
+    }
+}
+
+impl<T: Literal, const N: usize> Literal for [T; N] {
+    fn to_literal_value(&self) -> Value {
+        self.as_slice().to_literal_value()
+    }
+}
+
+impl<T: Literal> Literal for Vec<T> {
+    fn to_literal_value(&self) -> Value {
+        self.as_slice().to_literal_value()
+    }
+}
+
+impl<T: Literal + ?Sized> Literal for &T {
+    fn to_literal_value(&self) -> Value {
+        (**self).to_literal_value()
+    }
+}
+
+impl Literal for Bytes {
+    fn to_literal_value(&self) -> Value {
+        Value::from_bytes(self.0.clone())
+    }
+}
+
+impl Literal for FieldReference {
+    fn to_literal_value(&self) -> Value {
+        Value::from_string_as_field_reference_value(self.0.clone())
+    }
+}
+
+impl Literal for Function {
+    fn to_literal_value(&self) -> Value {
+        Value::from_function(self.clone().into())
+    }
+}
+
+impl Literal for LatLng {
+    fn to_literal_value(&self) -> Value {
+        Value::from_lat_lng((*self).into())
+    }
+}
+
+impl Literal for Reference {
+    fn to_literal_value(&self) -> Value {
+        Value::from_string_as_reference_value(self.0.clone())
+    }
+}
+
+impl Literal for Timestamp {
+    fn to_literal_value(&self) -> Value {
+        Value::from_timestamp((*self).into())
+    }
+}
+
+impl Literal for Value {
+    fn to_literal_value(&self) -> Value {
+        self.clone()
+    }
+}
+
+impl Literal for VariableReference {
+    fn to_literal_value(&self) -> Value {
+        Value::from_string_as_variable_reference_value(self.0.clone())
+    }
+}
+
+/// Creates a `conditional(condition, then, otherwise)` expression.
+pub fn conditional<C, T, E>(condition: C, then: T, otherwise: E) -> Expr
+where
+    C: Into<Expr>,
+    T: Into<Expr>,
+    E: Into<Expr>,
+{
+    function(
+        names::CONDITIONAL,
+        [condition.into(), then.into(), otherwise.into()],
+    )
+}
+
+/// Creates a `count()` expression, which counts all rows.
+pub fn count_all() -> Expr {
+    function(names::COUNT, std::iter::empty::<Expr>())
+}
+
+macro_rules! unary {
+    ($($(#[$attr:meta])* $method:ident => $name:ident;)*) => {
+        $(
+            $(#[$attr])*
+            #[doc = concat!("Creates a `", stringify!($method), "(self)` expression.")]
+            pub fn $method(self) -> Expr {
+                function(names::$name, [self])
+            }
+        )*
+    };
+}
+
+macro_rules! binary {
+    ($($(#[$attr:meta])* $method:ident => $name:ident;)*) => {
+        $(
+            $(#[$attr])*
+            #[doc = concat!("Creates a `", stringify!($name), "` expression of `self` and `other`.")]
+            pub fn $method<E: Into<Expr>>(self, other: E) -> Expr {
+                function(names::$name, [self, other.into()])
+            }
+        )*
+    };
+}
+
+impl Expr {
+    unary! {
+        array_length => ARRAY_LENGTH;
+        array_reverse => ARRAY_REVERSE;
+        average => AVERAGE;
+        byte_length => BYTE_LENGTH;
+        char_length => CHAR_LENGTH;
+        count => COUNT;
+        count_distinct => COUNT_DISTINCT;
+        exists => EXISTS;
+        is_nan => IS_NAN;
+        is_null => IS_NULL;
+        maximum => MAXIMUM;
+        minimum => MINIMUM;
+        #[allow(clippy::should_implement_trait)]
+        not => NOT;
+        sum => SUM;
+        to_lower => TO_LOWER;
+        to_upper => TO_UPPER;
+        trim => TRIM;
+    }
+
+    binary! {
+        #[allow(clippy::should_implement_trait)]
+        add => ADD;
+        array_concat => ARRAY_CONCAT;
+        array_contains => ARRAY_CONTAINS;
+        array_contains_all => ARRAY_CONTAINS_ALL;
+        array_contains_any => ARRAY_CONTAINS_ANY;
+        divide => DIVIDE;
+        ends_with => ENDS_WITH;
+        eq => EQUAL;
+        equal_any => EQUAL_ANY;
+        gt => GREATER_THAN;
+        gte => GREATER_THAN_OR_EQUAL;
+        lt => LESS_THAN;
+        lte => LESS_THAN_OR_EQUAL;
+        map_get => MAP_GET;
+        modulo => MOD;
+        multiply => MULTIPLY;
+        neq => NOT_EQUAL;
+        not_equal_any => NOT_EQUAL_ANY;
+        starts_with => STARTS_WITH;
+        string_concat => STRING_CONCAT;
+        string_contains => STRING_CONTAINS;
+        subtract => SUBTRACT;
+    }
+
+    /// Creates an `and` expression of `self` and `other`.
+    ///
+    /// If `self` is already an `and` expression without options, `other` is
+    /// appended to its arguments.
+    pub fn and<E: Into<Expr>>(self, other: E) -> Expr {
+        self.variadic(names::AND, other.into())
+    }
+
+    /// Creates an `or` expression of `self` and `other`.
+    ///
+    /// If `self` is already an `or` expression without options, `other` is
+    /// appended to its arguments.
+    pub fn or<E: Into<Expr>>(self, other: E) -> Expr {
+        self.variadic(names::OR, other.into())
+    }
+
+    /// Creates a `xor` expression of `self` and `other`.
+    ///
+    /// If `self` is already a `xor` expression without options, `other` is
+    /// appended to its arguments.
+    pub fn xor<E: Into<Expr>>(self, other: E) -> Expr {
+        self.variadic(names::XOR, other.into())
+    }
+
+    /// Returns the function name if `self` is a function.
+    pub fn function_name(&self) -> Option<&str> {
+        match self {
+            Expr::Function(FunctionExpr { name, .. }) => Some(name),
+            _ => None,
+        }
+    }
+
+    fn variadic(self, name: &str, other: Expr) -> Expr {
+        match self {
+            Expr::Function(mut f) if f.name == name && f.options.is_empty() => {
+                f.args.push(other);
+                Expr::Function(f)
+            }
+            expr => function(name, [expr, other]),
+        }
+    }
+}
+
+impl From<FieldReference> for Expr {
+    fn from(field_reference: FieldReference) -> Self {
+        Expr::Field(field_reference)
+    }
+}
+
+impl From<Function> for Expr {
+    fn from(function: Function) -> Self {
+        Expr::Function(FunctionExpr::from(function))
+    }
+}
+
+impl From<FunctionExpr> for Expr {
+    fn from(function: FunctionExpr) -> Self {
+        Expr::Function(function)
+    }
+}
+
+impl From<Value> for Expr {
+    fn from(value: Value) -> Self {
+        match value.value_type {
+            Some(ValueType::FieldReferenceValue(s)) => Expr::Field(FieldReference(s)),
+            Some(ValueType::VariableReferenceValue(s)) => Expr::Variable(VariableReference(s)),
+            Some(ValueType::FunctionValue(f)) => Expr::from(Function::from(f)),
+            value_type => Expr::Constant(Value { value_type }),
+        }
+    }
+}
+
+impl From<VariableReference> for Expr {
+    fn from(variable_reference: VariableReference) -> Self {
+        Expr::Variable(variable_reference)
+    }
+}
+
+impl From<Expr> for Value {
+    fn from(expr: Expr) -> Self {
+        match expr {
+            Expr::Constant(value) => value,
+            Expr::Field(FieldReference(s)) => Value::from_string_as_field_reference_value(s),
+            Expr::Function(f) => Value::from_function(Function::from(f).into()),
+            Expr::Variable(VariableReference(s)) => {
+                Value::from_string_as_variable_reference_value(s)
+            }
+        }
+    }
+}
+
+impl From<Function> for FunctionExpr {
+    fn from(
+        Function {
+            name,
+            args,
+            options,
+        }: Function,
+    ) -> Self {
+        Self {
+            name,
+            args: args.into_iter().map(Expr::from).collect(),
+            options,
+        }
+    }
+}
+
+impl From<FunctionExpr> for Function {
+    fn from(
+        FunctionExpr {
+            name,
+            args,
+            options,
+        }: FunctionExpr,
+    ) -> Self {
+        Self {
+            name,
+            args: args.into_iter().map(Value::from).collect(),
+            options,
+        }
+    }
+}
+
+impl std::convert::TryFrom<Expr> for Function {
+    type Error = Error;
+
+    fn try_from(expr: Expr) -> Result<Self, Self::Error> {
+        match expr {
+            Expr::Function(f) => Ok(Function::from(f)),
+            _ => Err(Error::from(ErrorCode::ExprIsNotAFunction)),
+        }
+    }
+}
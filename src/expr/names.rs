@@ -0,0 +1,88 @@
+//! Names of the Firestore pipeline functions used by [`Expr`](super::Expr).
+
+/// `add(a, b)`: `a + b`.
+pub const ADD: &str = "add";
+/// `and(a, b, ...)`: logical AND.
+pub const AND: &str = "and";
+/// `array_concat(a, b, ...)`: concatenation of arrays.
+pub const ARRAY_CONCAT: &str = "array_concat";
+/// `array_contains(array, value)`: `array` contains `value`.
+pub const ARRAY_CONTAINS: &str = "array_contains";
+/// `array_contains_all(array, values)`: `array` contains all of `values`.
+pub const ARRAY_CONTAINS_ALL: &str = "array_contains_all";
+/// `array_contains_any(array, values)`: `array` contains any of `values`.
+pub const ARRAY_CONTAINS_ANY: &str = "array_contains_any";
+/// `array_length(array)`: the number of elements.
+pub const ARRAY_LENGTH: &str = "array_length";
+/// `array_reverse(array)`: the elements in reverse order.
+pub const ARRAY_REVERSE: &str = "array_reverse";
+/// `average(a)`: aggregate average of numbers.
+pub const AVERAGE: &str = "average";
+/// `byte_length(a)`: the number of bytes of a string or bytes.
+pub const BYTE_LENGTH: &str = "byte_length";
+/// `char_length(a)`: the number of Unicode code points of a string.
+pub const CHAR_LENGTH: &str = "char_length";
+/// `conditional(condition, then, else)`: `then` if `condition` is true, otherwise `else`.
+pub const CONDITIONAL: &str = "conditional";
+/// `count(a)` or `count()`: aggregate count of non-null values or of rows.
+pub const COUNT: &str = "count";
+/// `count_distinct(a)`: aggregate count of distinct values.
+pub const COUNT_DISTINCT: &str = "count_distinct";
+/// `divide(a, b)`: `a / b`.
+pub const DIVIDE: &str = "divide";
+/// `ends_with(a, suffix)`: `a` ends with `suffix`.
+pub const ENDS_WITH: &str = "ends_with";
+/// `equal(a, b)`: `a == b`.
+pub const EQUAL: &str = "equal";
+/// `equal_any(a, values)`: `a` is equal to any of `values`.
+pub const EQUAL_ANY: &str = "equal_any";
+/// `exists(a)`: the field `a` is present.
+pub const EXISTS: &str = "exists";
+/// `greater_than(a, b)`: `a > b`.
+pub const GREATER_THAN: &str = "greater_than";
+/// `greater_than_or_equal(a, b)`: `a >= b`.
+pub const GREATER_THAN_OR_EQUAL: &str = "greater_than_or_equal";
+/// `is_nan(a)`: `a` is NaN.
+pub const IS_NAN: &str = "is_nan";
+/// `is_null(a)`: `a` is null.
+pub const IS_NULL: &str = "is_null";
+/// `less_than(a, b)`: `a < b`.
+pub const LESS_THAN: &str = "less_than";
+/// `less_than_or_equal(a, b)`: `a <= b`.
+pub const LESS_THAN_OR_EQUAL: &str = "less_than_or_equal";
+/// `map_get(map, key)`: the value of `key` in `map`.
+pub const MAP_GET: &str = "map_get";
+/// `maximum(a)`: aggregate maximum.
+pub const MAXIMUM: &str = "maximum";
+/// `minimum(a)`: aggregate minimum.
+pub const MINIMUM: &str = "minimum";
+/// `mod(a, b)`: `a % b`.
+pub const MOD: &str = "mod";
+/// `multiply(a, b)`: `a * b`.
+pub const MULTIPLY: &str = "multiply";
+/// `not(a)`: logical NOT.
+pub const NOT: &str = "not";
+/// `not_equal(a, b)`: `a != b`.
+pub const NOT_EQUAL: &str = "not_equal";
+/// `not_equal_any(a, values)`: `a` is not equal to any of `values`.
+pub const NOT_EQUAL_ANY: &str = "not_equal_any";
+/// `or(a, b, ...)`: logical OR.
+pub const OR: &str = "or";
+/// `starts_with(a, prefix)`: `a` starts with `prefix`.
+pub const STARTS_WITH: &str = "starts_with";
+/// `string_concat(a, b, ...)`: concatenation of strings.
+pub const STRING_CONCAT: &str = "string_concat";
+/// `string_contains(a, substring)`: `a` contains `substring`.
+pub const STRING_CONTAINS: &str = "string_contains";
+/// `subtract(a, b)`: `a - b`.
+pub const SUBTRACT: &str = "subtract";
+/// `sum(a)`: aggregate sum of numbers.
+pub const SUM: &str = "sum";
+/// `to_lower(a)`: the lowercase string.
+pub const TO_LOWER: &str = "to_lower";
+/// `to_upper(a)`: the uppercase string.
+pub const TO_UPPER: &str = "to_upper";
+/// `trim(a)`: the string without leading and trailing whitespace.
+pub const TRIM: &str = "trim";
+/// `xor(a, b, ...)`: logical XOR.
+pub const XOR: &str = "xor";
@@ -0,0 +1,267 @@
+//! Rewriting of Firestore Values.
+//!
+//! Implement [`ValueFolder`] and override only the methods for the value
+//! types you want to rewrite. The default methods rebuild the value
+//! unchanged, recursing into arrays, maps, functions, and pipelines.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # fn main() -> anyhow::Result<()> {
+//! use serde_firestore_value::fold::ValueFolder;
+//! use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+//! use serde_firestore_value::{to_value, Reference};
+//!
+//! struct MoveDatabase;
+//!
+//! impl ValueFolder for MoveDatabase {
+//!     fn fold_reference(&mut self, value: String) -> Value {
+//!         Value {
+//!             value_type: Some(ValueType::ReferenceValue(
+//!                 value.replacen("/databases/d1/", "/databases/d2/", 1),
+//!             )),
+//!         }
+//!     }
+//! }
+//!
+//! let value = to_value(&vec![Reference(
+//!     "projects/p/databases/d1/documents/c/1".to_string(),
+//! )])?;
+//! assert_eq!(
+//!     MoveDatabase.fold_value(value),
+//!     to_value(&vec![Reference(
+//!         "projects/p/databases/d2/documents/c/1".to_string(),
+//!     )])?
+//! );
+//! #     Ok(())
+//! # }
+//! ```
+
+#[cfg(feature = "bytes")]
+use prost::bytes::Bytes;
+
+use crate::google::{
+    firestore::v1::{
+        ArrayValue, Function, MapValue, Pipeline, Value, pipeline::Stage, value::ValueType,
+    },
+    r#type::LatLng,
+};
+
+/// A folder which rewrites a [`Value`] tree.
+///
+/// All methods have default implementations which return the input
+/// unchanged. `fold_array`, `fold_map`, `fold_function`, `fold_pipeline` and
+/// `fold_stage` recurse via the functions of the same name in this module.
+///
+/// The leaf methods return a [`Value`], so a folder may replace a value with
+/// a value of a different type (e.g. redact a `stringValue` as `nullValue`).
+pub trait ValueFolder {
+    /// Folds any value. Dispatches to the method for its value type.
+    fn fold_value(&mut self, value: Value) -> Value {
+        fold_value(self, value)
+    }
+
+    /// Folds a `nullValue`.
+    fn fold_null(&mut self) -> Value {
+        wrap(ValueType::NullValue(0))
+    }
+
+    /// Folds a `booleanValue`.
+    fn fold_boolean(&mut self, value: bool) -> Value {
+        wrap(ValueType::BooleanValue(value))
+    }
+
+    /// Folds an `integerValue`.
+    fn fold_integer(&mut self, value: i64) -> Value {
+        wrap(ValueType::IntegerValue(value))
+    }
+
+    /// Folds a `doubleValue`.
+    fn fold_double(&mut self, value: f64) -> Value {
+        wrap(ValueType::DoubleValue(value))
+    }
+
+    /// Folds a `timestampValue`.
+    fn fold_timestamp(&mut self, value: prost_types::Timestamp) -> Value {
+        wrap(ValueType::TimestampValue(value))
+    }
+
+    /// Folds a `stringValue`.
+    fn fold_string(&mut self, value: String) -> Value {
+        wrap(ValueType::StringValue(value))
+    }
+
+    /// Folds a `bytesValue`.
+    #[cfg(feature = "bytes")]
+    fn fold_bytes(&mut self, value: Bytes) -> Value {
+        wrap(ValueType::BytesValue(value))
+    }
+
+    /// Folds a `bytesValue`.
+    #[cfg(feature = "vec-u8")]
+    fn fold_bytes(&mut self, value: Vec<u8>) -> Value {
+        wrap(ValueType::BytesValue(value))
+    }
+
+    /// Folds a `referenceValue`.
+    fn fold_reference(&mut self, value: String) -> Value {
+        wrap(ValueType::ReferenceValue(value))
+    }
+
+    /// Folds a `geoPointValue`.
+    fn fold_geo_point(&mut self, value: LatLng) -> Value {
+        wrap(ValueType::GeoPointValue(value))
+    }
+
+    /// Folds an `arrayValue`.
+    fn fold_array(&mut self, value: ArrayValue) -> Value {
+        wrap(ValueType::ArrayValue(fold_array(self, value)))
+    }
+
+    /// Folds a `mapValue`.
+    fn fold_map(&mut self, value: MapValue) -> Value {
+        wrap(ValueType::MapValue(fold_map(self, value)))
+    }
+
+    /// Folds a `fieldReferenceValue`.
+    fn fold_field_reference(&mut self, value: String) -> Value {
+        wrap(ValueType::FieldReferenceValue(value))
+    }
+
+    /// Folds a `variableReferenceValue`.
+    fn fold_variable_reference(&mut self, value: String) -> Value {
+        wrap(ValueType::VariableReferenceValue(value))
+    }
+
+    /// Folds a `functionValue`.
+    fn fold_function(&mut self, value: Function) -> Value {
+        wrap(ValueType::FunctionValue(fold_function(self, value)))
+    }
+
+    /// Folds a `pipelineValue`.
+    fn fold_pipeline(&mut self, value: Pipeline) -> Value {
+        wrap(ValueType::PipelineValue(fold_pipeline(self, value)))
+    }
+
+    /// Folds a stage of a `pipelineValue`.
+    fn fold_stage(&mut self, value: Stage) -> Stage {
+        fold_stage(self, value)
+    }
+}
+
+/// Dispatches `value` to the [`ValueFolder`] method for its value type.
+///
+/// A value without a value type is returned unchanged.
+pub fn fold_value<F>(folder: &mut F, value: Value) -> Value
+where
+    F: ValueFolder + ?Sized,
+{
+    let Some(value_type) = value.value_type else {
+        return Value { value_type: None };
+    };
+    match value_type {
+        ValueType::NullValue(_) => folder.fold_null(),
+        ValueType::BooleanValue(v) => folder.fold_boolean(v),
+        ValueType::IntegerValue(v) => folder.fold_integer(v),
+        ValueType::DoubleValue(v) => folder.fold_double(v),
+        ValueType::TimestampValue(v) => folder.fold_timestamp(v),
+        ValueType::StringValue(v) => folder.fold_string(v),
+        ValueType::BytesValue(v) => folder.fold_bytes(v),
+        ValueType::ReferenceValue(v) => folder.fold_reference(v),
+        ValueType::GeoPointValue(v) => folder.fold_geo_point(v),
+        ValueType::ArrayValue(v) => folder.fold_array(v),
+        ValueType::MapValue(v) => folder.fold_map(v),
+        ValueType::FieldReferenceValue(v) => folder.fold_field_reference(v),
+        ValueType::VariableReferenceValue(v) => folder.fold_variable_reference(v),
+        ValueType::FunctionValue(v) => folder.fold_function(v),
+        ValueType::PipelineValue(v) => folder.fold_pipeline(v),
+    }
+}
+
+/// Folds each element of an `arrayValue`.
+pub fn fold_array<F>(folder: &mut F, value: ArrayValue) -> ArrayValue
+where
+    F: ValueFolder + ?Sized,
+{
+    ArrayValue {
+        values: value
+            .values
+            .into_iter()
+            .map(|value| folder.fold_value(value))
+            .collect(),
+    }
+}
+
+/// Folds each field of a `mapValue`.
+pub fn fold_map<F>(folder: &mut F, value: MapValue) -> MapValue
+where
+    F: ValueFolder + ?Sized,
+{
+    MapValue {
+        fields: value
+            .fields
+            .into_iter()
+            .map(|(key, value)| (key, folder.fold_value(value)))
+            .collect(),
+    }
+}
+
+/// Folds the args and options of a `functionValue`.
+pub fn fold_function<F>(folder: &mut F, value: Function) -> Function
+where
+    F: ValueFolder + ?Sized,
+{
+    Function {
+        name: value.name,
+        args: value
+            .args
+            .into_iter()
+            .map(|value| folder.fold_value(value))
+            .collect(),
+        options: value
+            .options
+            .into_iter()
+            .map(|(key, value)| (key, folder.fold_value(value)))
+            .collect(),
+    }
+}
+
+/// Folds each stage of a `pipelineValue`.
+pub fn fold_pipeline<F>(folder: &mut F, value: Pipeline) -> Pipeline
+where
+    F: ValueFolder + ?Sized,
+{
+    Pipeline {
+        stages: value
+            .stages
+            .into_iter()
+            .map(|stage| folder.fold_stage(stage))
+            .collect(),
+    }
+}
+
+/// Folds the args and options of a stage.
+pub fn fold_stage<F>(folder: &mut F, value: Stage) -> Stage
+where
+    F: ValueFolder + ?Sized,
+{
+    Stage {
+        name: value.name,
+        args: value
+            .args
+            .into_iter()
+            .map(|value| folder.fold_value(value))
+            .collect(),
+        options: value
+            .options
+            .into_iter()
+            .map(|(key, value)| (key, folder.fold_value(value)))
+            .collect(),
+    }
+}
+
+fn wrap(value_type: ValueType) -> Value {
+    Value {
+        value_type: Some(value_type),
+    }
+}
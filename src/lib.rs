@@ -175,20 +175,43 @@
 //! [serde]: https://crates.io/crates/serde
 //! [Firestore Value]: https://firebase.google.com/docs/firestore/reference/rest/v1/Value
 mod de;
+#[cfg(any(feature = "bigdecimal", feature = "decimal"))]
+mod decimal;
 mod error;
+pub mod eval;
+pub mod expr;
+pub mod fold;
+#[cfg(feature = "rest-json")]
+mod rest_json;
+mod rfc3339;
 mod ser;
+#[cfg(feature = "serde_with")]
+pub mod serde_as;
 mod serde_json;
+pub mod stage;
+pub mod structured_query;
+pub mod text;
 mod typ;
+pub mod validate;
 mod value_ext;
+mod value_order;
 mod value_type_ext;
 mod value_type_name;
+pub mod visit;
 pub mod with;
 
-pub use self::de::{Deserializer, from_value};
+pub use self::de::{Deserializer, OwnedDeserializer, from_value, from_value_owned};
 pub use self::error::{Error, Result};
+#[cfg(feature = "rest-json")]
+pub use self::rest_json::{RestJson, from_rest_json, to_rest_json, to_rest_json_pretty};
 pub use self::ser::{Serializer, to_value};
+#[cfg(feature = "serde_json")]
+pub use self::serde_json::{
+    BigNumberStrategy, JsonOptions, Representation, json_to_value, value_to_json,
+};
 pub use self::typ::{
-    FieldReference, Function, LatLng, Pipeline, Reference, Stage, Timestamp, VariableReference,
+    Bytes, FieldReference, Function, LatLng, Pipeline, RawValue, Reference, Stage, Timestamp,
+    VariableReference,
 };
 
 pub use googleapis_tonic_google_firestore_v1::google;
@@ -0,0 +1,607 @@
+//! Firestore REST API JSON encoding of [`Value`] and [`Document`].
+//!
+//! The encoding follows the [proto3 JSON mapping] used by the Firestore REST
+//! API and the emulator:
+//!
+//! - `integerValue` is a string (e.g. `"1"`)
+//! - `doubleValue` is a number, or `"NaN"`, `"Infinity"` or `"-Infinity"`
+//! - `timestampValue` is an RFC 3339 string in UTC (e.g. `"1970-01-01T00:00:01.000000002Z"`)
+//! - `bytesValue` is a base64 string
+//! - fields with default values (e.g. empty `values`, zero `latitude`) are omitted
+//!
+//! [proto3 JSON mapping]: https://protobuf.dev/programming-guides/proto3/#json
+
+use base64::Engine as _;
+
+use crate::google::{
+    firestore::v1::{
+        ArrayValue, Document, Function, MapValue, Pipeline, Value, pipeline::Stage,
+        value::ValueType,
+    },
+    r#type::LatLng,
+};
+use crate::{Error, error::ErrorCode, rfc3339, value_ext::ValueExt};
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for super::Document {}
+    impl Sealed for super::Value {}
+}
+
+/// A type which can be encoded in the Firestore REST API JSON format.
+///
+/// This trait is sealed. It is implemented for [`Value`] and [`Document`].
+pub trait RestJson: private::Sealed + Sized {
+    #[doc(hidden)]
+    fn serialize_rest_json<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer;
+
+    #[doc(hidden)]
+    fn from_json_value(json: &serde_json::Value) -> Result<Self, Error>;
+}
+
+impl RestJson for Value {
+    fn serialize_rest_json<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serde::Serialize::serialize(&RestValue(self), serializer)
+    }
+
+    fn from_json_value(json: &serde_json::Value) -> Result<Self, Error> {
+        value_from_json(json)
+    }
+}
+
+impl RestJson for Document {
+    fn serialize_rest_json<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serde::Serialize::serialize(&RestDocument(self), serializer)
+    }
+
+    fn from_json_value(json: &serde_json::Value) -> Result<Self, Error> {
+        document_from_json(json)
+    }
+}
+
+/// Encode a [`Value`] or [`Document`] as a Firestore REST API JSON string.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::{to_rest_json, to_value};
+///
+/// #[derive(serde::Serialize)]
+/// struct T {
+///     n: i64,
+/// }
+/// assert_eq!(
+///     to_rest_json(&to_value(&T { n: 1 })?)?,
+///     r#"{"mapValue":{"fields":{"n":{"integerValue":"1"}}}}"#
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+pub fn to_rest_json<T>(value: &T) -> Result<String, Error>
+where
+    T: RestJson,
+{
+    let mut buf = Vec::new();
+    value
+        .serialize_rest_json(&mut serde_json::Serializer::new(&mut buf))
+        .map_err(invalid)?;
+    String::from_utf8(buf).map_err(invalid)
+}
+
+/// Encode a [`Value`] or [`Document`] as a pretty-printed Firestore REST API JSON string.
+pub fn to_rest_json_pretty<T>(value: &T) -> Result<String, Error>
+where
+    T: RestJson,
+{
+    let mut buf = Vec::new();
+    value
+        .serialize_rest_json(&mut serde_json::Serializer::pretty(&mut buf))
+        .map_err(invalid)?;
+    String::from_utf8(buf).map_err(invalid)
+}
+
+/// Decode a [`Value`] or [`Document`] from a Firestore REST API JSON string.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::Value;
+/// use serde_firestore_value::{from_rest_json, from_value};
+///
+/// let value = from_rest_json::<Value>(r#"{"integerValue":"1"}"#)?;
+/// assert_eq!(from_value::<'_, i64>(&value)?, 1);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn from_rest_json<T>(s: &str) -> Result<T, Error>
+where
+    T: RestJson,
+{
+    let json = serde_json::from_str::<serde_json::Value>(s).map_err(invalid)?;
+    T::from_json_value(&json)
+}
+
+fn invalid<T: std::fmt::Display>(e: T) -> Error {
+    Error::from(ErrorCode::InvalidRestJson(e.to_string()))
+}
+
+struct RestValue<'a>(&'a Value);
+
+impl serde::Serialize for RestValue<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::{Error as _, SerializeMap};
+
+        let value_type = self.0.value_type().map_err(S::Error::custom)?;
+        let mut map = serializer.serialize_map(Some(1))?;
+        match value_type {
+            ValueType::NullValue(_) => map.serialize_entry("nullValue", &())?,
+            ValueType::BooleanValue(v) => map.serialize_entry("booleanValue", v)?,
+            ValueType::IntegerValue(v) => map.serialize_entry("integerValue", &v.to_string())?,
+            ValueType::DoubleValue(v) => map.serialize_entry("doubleValue", &RestDouble(*v))?,
+            ValueType::TimestampValue(v) => map.serialize_entry(
+                "timestampValue",
+                &rfc3339::format(v.seconds, v.nanos).map_err(S::Error::custom)?,
+            )?,
+            ValueType::StringValue(v) => map.serialize_entry("stringValue", v)?,
+            ValueType::BytesValue(v) => map.serialize_entry(
+                "bytesValue",
+                &base64::engine::general_purpose::STANDARD.encode(v),
+            )?,
+            ValueType::ReferenceValue(v) => map.serialize_entry("referenceValue", v)?,
+            ValueType::GeoPointValue(v) => map.serialize_entry("geoPointValue", &RestLatLng(v))?,
+            ValueType::ArrayValue(ArrayValue { values }) => {
+                map.serialize_entry("arrayValue", &Object([("values", RestValues(values))]))?
+            }
+            ValueType::MapValue(MapValue { fields }) => map.serialize_entry(
+                "mapValue",
+                &Object([("fields", RestFields::new(fields.iter()))]),
+            )?,
+            ValueType::FieldReferenceValue(v) => map.serialize_entry("fieldReferenceValue", v)?,
+            ValueType::VariableReferenceValue(v) => {
+                map.serialize_entry("variableReferenceValue", v)?
+            }
+            ValueType::FunctionValue(Function {
+                name,
+                args,
+                options,
+            }) => map.serialize_entry(
+                "functionValue",
+                &RestCall {
+                    name,
+                    args,
+                    options: RestFields::new(options.iter()),
+                },
+            )?,
+            ValueType::PipelineValue(Pipeline { stages }) => map.serialize_entry(
+                "pipelineValue",
+                &Object([(
+                    "stages",
+                    RestStages(
+                        stages
+                            .iter()
+                            .map(|stage| RestCall {
+                                name: &stage.name,
+                                args: &stage.args,
+                                options: RestFields::new(stage.options.iter()),
+                            })
+                            .collect(),
+                    ),
+                )]),
+            )?,
+        }
+        map.end()
+    }
+}
+
+/// Whether the value is omitted as a proto3 default value.
+trait IsDefault {
+    fn is_default(&self) -> bool;
+}
+
+/// A JSON object whose entries with default values are omitted.
+struct Object<T, const N: usize>([(&'static str, T); N]);
+
+impl<T, const N: usize> serde::Serialize for Object<T, N>
+where
+    T: IsDefault + serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(None)?;
+        for (k, v) in self.0.iter() {
+            if !v.is_default() {
+                map.serialize_entry(k, v)?;
+            }
+        }
+        map.end()
+    }
+}
+
+struct RestValues<'a>(&'a [Value]);
+
+impl IsDefault for RestValues<'_> {
+    fn is_default(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl serde::Serialize for RestValues<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_seq(self.0.iter().map(RestValue))
+    }
+}
+
+struct RestFields<'a>(Vec<(&'a String, &'a Value)>);
+
+impl<'a> RestFields<'a> {
+    fn new<I>(fields: I) -> Self
+    where
+        I: Iterator<Item = (&'a String, &'a Value)>,
+    {
+        let mut fields = fields.collect::<Vec<_>>();
+        fields.sort_by_key(|(k, _)| *k);
+        Self(fields)
+    }
+}
+
+impl IsDefault for RestFields<'_> {
+    fn is_default(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl serde::Serialize for RestFields<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_map(self.0.iter().map(|(k, v)| (k, RestValue(v))))
+    }
+}
+
+struct RestStages<'a>(Vec<RestCall<'a>>);
+
+impl IsDefault for RestStages<'_> {
+    fn is_default(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl serde::Serialize for RestStages<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_seq(self.0.iter())
+    }
+}
+
+/// `functionValue` or `pipelineValue.stages[]`
+struct RestCall<'a> {
+    name: &'a String,
+    args: &'a [Value],
+    options: RestFields<'a>,
+}
+
+impl serde::Serialize for RestCall<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(None)?;
+        if !self.name.is_empty() {
+            map.serialize_entry("name", self.name)?;
+        }
+        if !self.args.is_empty() {
+            map.serialize_entry("args", &RestValues(self.args))?;
+        }
+        if !self.options.is_default() {
+            map.serialize_entry("options", &self.options)?;
+        }
+        map.end()
+    }
+}
+
+struct RestDouble(f64);
+
+impl IsDefault for RestDouble {
+    fn is_default(&self) -> bool {
+        self.0 == 0_f64
+    }
+}
+
+impl serde::Serialize for RestDouble {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        // 2^53
+        const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_992_f64;
+        let v = self.0;
+        if v.is_nan() {
+            serializer.serialize_str("NaN")
+        } else if v.is_infinite() {
+            serializer.serialize_str(if v > 0_f64 { "Infinity" } else { "-Infinity" })
+        } else if v.fract() == 0_f64
+            && v.abs() <= MAX_SAFE_INTEGER
+            && (v != 0_f64 || v.is_sign_positive())
+        {
+            // `1` instead of `1.0`. `-0.0` keeps its sign.
+            serializer.serialize_i64(v as i64)
+        } else {
+            serializer.serialize_f64(v)
+        }
+    }
+}
+
+struct RestLatLng<'a>(&'a LatLng);
+
+impl serde::Serialize for RestLatLng<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        Object([
+            ("latitude", RestDouble(self.0.latitude)),
+            ("longitude", RestDouble(self.0.longitude)),
+        ])
+        .serialize(serializer)
+    }
+}
+
+struct RestDocument<'a>(&'a Document);
+
+impl serde::Serialize for RestDocument<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::{Error as _, SerializeMap};
+
+        let document = self.0;
+        let mut map = serializer.serialize_map(None)?;
+        if !document.name.is_empty() {
+            map.serialize_entry("name", &document.name)?;
+        }
+        let fields = RestFields::new(document.fields.iter());
+        if !fields.is_default() {
+            map.serialize_entry("fields", &fields)?;
+        }
+        if let Some(t) = document.create_time.as_ref() {
+            let s = rfc3339::format(t.seconds, t.nanos).map_err(S::Error::custom)?;
+            map.serialize_entry("createTime", &s)?;
+        }
+        if let Some(t) = document.update_time.as_ref() {
+            let s = rfc3339::format(t.seconds, t.nanos).map_err(S::Error::custom)?;
+            map.serialize_entry("updateTime", &s)?;
+        }
+        map.end()
+    }
+}
+
+fn value_from_json(json: &serde_json::Value) -> Result<Value, Error> {
+    let object = json
+        .as_object()
+        .ok_or_else(|| invalid("Value must be an object"))?;
+    let mut entries = object.iter();
+    let (key, json) = match (entries.next(), entries.next()) {
+        (Some(entry), None) => entry,
+        _ => return Err(invalid("Value must have exactly one field")),
+    };
+    Ok(match key.as_str() {
+        "nullValue" => match json {
+            serde_json::Value::Null => Value::null(),
+            serde_json::Value::String(s) if s == "NULL_VALUE" => Value::null(),
+            _ => return Err(invalid("nullValue must be null")),
+        },
+        "booleanValue" => Value::from_bool(
+            json.as_bool()
+                .ok_or_else(|| invalid("booleanValue must be a boolean"))?,
+        ),
+        "integerValue" => Value::from_i64(integer_from_json(json)?),
+        "doubleValue" => Value::from_f64(double_from_json(json)?),
+        "timestampValue" => Value::from_timestamp(timestamp_from_json(json)?),
+        "stringValue" => Value::from_string(string_from_json(json, key)?),
+        "bytesValue" => Value::from_bytes(bytes_from_json(json)?),
+        "referenceValue" => Value::from_string_as_reference_value(string_from_json(json, key)?),
+        "geoPointValue" => Value::from_lat_lng(lat_lng_from_json(json)?),
+        "arrayValue" => {
+            let object = object_from_json(json, key)?;
+            Value::from_values(values_from_json(object.get("values"))?)
+        }
+        "mapValue" => {
+            let object = object_from_json(json, key)?;
+            Value::from_fields(fields_from_json(object.get("fields"))?)
+        }
+        "fieldReferenceValue" => {
+            Value::from_string_as_field_reference_value(string_from_json(json, key)?)
+        }
+        "variableReferenceValue" => {
+            Value::from_string_as_variable_reference_value(string_from_json(json, key)?)
+        }
+        "functionValue" => {
+            let object = object_from_json(json, key)?;
+            Value::from_function(Function {
+                name: optional_string_from_json(object.get("name"), "name")?,
+                args: values_from_json(object.get("args"))?,
+                options: fields_from_json(object.get("options"))?
+                    .into_iter()
+                    .collect(),
+            })
+        }
+        "pipelineValue" => {
+            let object = object_from_json(json, key)?;
+            let stages = match object.get("stages") {
+                None | Some(serde_json::Value::Null) => Vec::new(),
+                Some(serde_json::Value::Array(stages)) => stages
+                    .iter()
+                    .map(stage_from_json)
+                    .collect::<Result<Vec<_>, Error>>()?,
+                Some(_) => return Err(invalid("stages must be an array")),
+            };
+            Value::from_pipeline(Pipeline { stages })
+        }
+        _ => return Err(invalid(format_args!("unknown value type `{}`", key))),
+    })
+}
+
+fn stage_from_json(json: &serde_json::Value) -> Result<Stage, Error> {
+    let object = object_from_json(json, "stage")?;
+    Ok(Stage {
+        name: optional_string_from_json(object.get("name"), "name")?,
+        args: values_from_json(object.get("args"))?,
+        options: fields_from_json(object.get("options"))?
+            .into_iter()
+            .collect(),
+    })
+}
+
+fn document_from_json(json: &serde_json::Value) -> Result<Document, Error> {
+    let object = object_from_json(json, "Document")?;
+    let timestamp = |key: &str| -> Result<Option<prost_types::Timestamp>, Error> {
+        match object.get(key) {
+            None | Some(serde_json::Value::Null) => Ok(None),
+            Some(json) => timestamp_from_json(json).map(Some),
+        }
+    };
+    Ok(Document {
+        name: optional_string_from_json(object.get("name"), "name")?,
+        fields: fields_from_json(object.get("fields"))?
+            .into_iter()
+            .collect(),
+        create_time: timestamp("createTime")?,
+        update_time: timestamp("updateTime")?,
+    })
+}
+
+fn object_from_json<'a>(
+    json: &'a serde_json::Value,
+    name: &str,
+) -> Result<&'a serde_json::Map<String, serde_json::Value>, Error> {
+    json.as_object()
+        .ok_or_else(|| invalid(format_args!("{} must be an object", name)))
+}
+
+fn string_from_json(json: &serde_json::Value, name: &str) -> Result<String, Error> {
+    json.as_str()
+        .map(str::to_string)
+        .ok_or_else(|| invalid(format_args!("{} must be a string", name)))
+}
+
+fn optional_string_from_json(
+    json: Option<&serde_json::Value>,
+    name: &str,
+) -> Result<String, Error> {
+    match json {
+        None | Some(serde_json::Value::Null) => Ok(String::new()),
+        Some(json) => string_from_json(json, name),
+    }
+}
+
+fn integer_from_json(json: &serde_json::Value) -> Result<i64, Error> {
+    match json {
+        serde_json::Value::String(s) => s
+            .parse::<i64>()
+            .map_err(|_| invalid(format_args!("invalid integerValue `{}`", s))),
+        serde_json::Value::Number(n) => n
+            .as_i64()
+            .ok_or_else(|| invalid(format_args!("invalid integerValue `{}`", n))),
+        _ => Err(invalid("integerValue must be a string or a number")),
+    }
+}
+
+fn double_from_json(json: &serde_json::Value) -> Result<f64, Error> {
+    match json {
+        serde_json::Value::Number(n) => n
+            .as_f64()
+            .ok_or_else(|| invalid(format_args!("invalid doubleValue `{}`", n))),
+        serde_json::Value::String(s) => match s.as_str() {
+            "NaN" => Ok(f64::NAN),
+            "Infinity" => Ok(f64::INFINITY),
+            "-Infinity" => Ok(f64::NEG_INFINITY),
+            _ => s
+                .parse::<f64>()
+                .map_err(|_| invalid(format_args!("invalid doubleValue `{}`", s))),
+        },
+        _ => Err(invalid("doubleValue must be a number or a string")),
+    }
+}
+
+fn timestamp_from_json(json: &serde_json::Value) -> Result<prost_types::Timestamp, Error> {
+    let s = json
+        .as_str()
+        .ok_or_else(|| invalid("timestampValue must be a string"))?;
+    let (seconds, nanos) = rfc3339::parse(s)?;
+    Ok(prost_types::Timestamp { seconds, nanos })
+}
+
+fn bytes_from_json(json: &serde_json::Value) -> Result<Vec<u8>, Error> {
+    let s = json
+        .as_str()
+        .ok_or_else(|| invalid("bytesValue must be a string"))?;
+    // proto3 JSON parsers accept both the standard and the URL-safe alphabet, with or without padding.
+    let config = base64::engine::GeneralPurposeConfig::new()
+        .with_decode_padding_mode(base64::engine::DecodePaddingMode::Indifferent);
+    let alphabet = if s.contains(['-', '_']) {
+        &base64::alphabet::URL_SAFE
+    } else {
+        &base64::alphabet::STANDARD
+    };
+    base64::engine::GeneralPurpose::new(alphabet, config)
+        .decode(s)
+        .map_err(|e| invalid(format_args!("invalid bytesValue: {}", e)))
+}
+
+fn lat_lng_from_json(json: &serde_json::Value) -> Result<LatLng, Error> {
+    let object = object_from_json(json, "geoPointValue")?;
+    let double = |key: &str| match object.get(key) {
+        None | Some(serde_json::Value::Null) => Ok(0_f64),
+        Some(json) => double_from_json(json),
+    };
+    Ok(LatLng {
+        latitude: double("latitude")?,
+        longitude: double("longitude")?,
+    })
+}
+
+fn values_from_json(json: Option<&serde_json::Value>) -> Result<Vec<Value>, Error> {
+    match json {
+        None | Some(serde_json::Value::Null) => Ok(Vec::new()),
+        Some(serde_json::Value::Array(values)) => values.iter().map(value_from_json).collect(),
+        Some(_) => Err(invalid("values must be an array")),
+    }
+}
+
+fn fields_from_json(json: Option<&serde_json::Value>) -> Result<Vec<(String, Value)>, Error> {
+    match json {
+        None | Some(serde_json::Value::Null) => Ok(Vec::new()),
+        Some(serde_json::Value::Object(fields)) => fields
+            .iter()
+            .map(|(k, v)| value_from_json(v).map(|v| (k.clone(), v)))
+            .collect(),
+        Some(_) => Err(invalid("fields must be an object")),
+    }
+}
@@ -0,0 +1,198 @@
+//! RFC 3339 formatting and parsing for `google.protobuf.Timestamp`.
+//!
+//! <https://protobuf.dev/reference/protobuf/google.protobuf/#timestamp>
+
+use crate::{Error, error::ErrorCode};
+
+// 0001-01-01T00:00:00Z
+const MIN_SECONDS: i64 = -62_135_596_800;
+// 9999-12-31T23:59:59Z
+const MAX_SECONDS: i64 = 253_402_300_799;
+
+/// Formats the timestamp as `YYYY-MM-DDTHH:MM:SS[.fff|.ffffff|.fffffffff]Z`.
+pub(crate) fn format(seconds: i64, nanos: i32) -> Result<String, Error> {
+    if !(MIN_SECONDS..=MAX_SECONDS).contains(&seconds) || !(0..1_000_000_000).contains(&nanos) {
+        return Err(Error::from(ErrorCode::TimestampOutOfRange));
+    }
+    let days = seconds.div_euclid(86_400);
+    let secs_of_day = seconds.rem_euclid(86_400);
+    let (year, month, day) = civil_from_days(days);
+    let mut s = format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        secs_of_day / 3_600,
+        secs_of_day % 3_600 / 60,
+        secs_of_day % 60
+    );
+    if nanos != 0 {
+        if nanos % 1_000_000 == 0 {
+            s.push_str(&format!(".{:03}", nanos / 1_000_000));
+        } else if nanos % 1_000 == 0 {
+            s.push_str(&format!(".{:06}", nanos / 1_000));
+        } else {
+            s.push_str(&format!(".{:09}", nanos));
+        }
+    }
+    s.push('Z');
+    Ok(s)
+}
+
+/// Parses `YYYY-MM-DDTHH:MM:SS[.f+](Z|+HH:MM|-HH:MM)` into `(seconds, nanos)`.
+pub(crate) fn parse(s: &str) -> Result<(i64, i32), Error> {
+    let invalid = || Error::from(ErrorCode::InvalidRfc3339(s.to_string()));
+    let b = s.as_bytes();
+    if b.len() < 20
+        || b[4] != b'-'
+        || b[7] != b'-'
+        || !matches!(b[10], b'T' | b't' | b' ')
+        || b[13] != b':'
+        || b[16] != b':'
+    {
+        return Err(invalid());
+    }
+    let digits = |range: std::ops::Range<usize>| -> Result<i64, Error> {
+        let part = &b[range];
+        if !part.iter().all(u8::is_ascii_digit) {
+            return Err(invalid());
+        }
+        Ok(part
+            .iter()
+            .fold(0_i64, |acc, d| acc * 10 + i64::from(d - b'0')))
+    };
+    let year = digits(0..4)?;
+    let month = digits(5..7)?;
+    let day = digits(8..10)?;
+    let hour = digits(11..13)?;
+    let minute = digits(14..16)?;
+    let second = digits(17..19)?;
+    if !(1..=12).contains(&month)
+        || day < 1
+        || day > days_in_month(year, month)
+        || hour > 23
+        || minute > 59
+        || second > 59
+    {
+        return Err(invalid());
+    }
+
+    let mut rest = &b[19..];
+    let mut nanos = 0_i64;
+    if let Some((b'.', fraction)) = rest.split_first() {
+        let len = fraction.iter().take_while(|c| c.is_ascii_digit()).count();
+        if len == 0 || len > 9 {
+            return Err(invalid());
+        }
+        nanos = fraction[..len]
+            .iter()
+            .fold(0_i64, |acc, d| acc * 10 + i64::from(d - b'0'))
+            * 10_i64.pow(9 - len as u32);
+        rest = &fraction[len..];
+    }
+
+    let offset = match rest {
+        [b'Z' | b'z'] => 0,
+        [sign @ (b'+' | b'-'), h1, h2, b':', m1, m2] => {
+            let hm = [*h1, *h2, *m1, *m2];
+            if !hm.iter().all(u8::is_ascii_digit) {
+                return Err(invalid());
+            }
+            let hours = i64::from((h1 - b'0') * 10 + (h2 - b'0'));
+            let minutes = i64::from((m1 - b'0') * 10 + (m2 - b'0'));
+            if hours > 23 || minutes > 59 {
+                return Err(invalid());
+            }
+            let offset = hours * 3_600 + minutes * 60;
+            if *sign == b'-' { -offset } else { offset }
+        }
+        _ => return Err(invalid()),
+    };
+
+    let seconds =
+        days_from_civil(year, month, day) * 86_400 + hour * 3_600 + minute * 60 + second - offset;
+    if !(MIN_SECONDS..=MAX_SECONDS).contains(&seconds) {
+        return Err(Error::from(ErrorCode::TimestampOutOfRange));
+    }
+    Ok((seconds, nanos as i32))
+}
+
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// <https://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+pub(crate) fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+// <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+pub(crate) fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format() -> anyhow::Result<()> {
+        assert_eq!(format(0, 0)?, "1970-01-01T00:00:00Z");
+        assert_eq!(format(1, 2)?, "1970-01-01T00:00:01.000000002Z");
+        assert_eq!(format(1, 2_000)?, "1970-01-01T00:00:01.000002Z");
+        assert_eq!(format(1, 2_000_000)?, "1970-01-01T00:00:01.002Z");
+        assert_eq!(format(-1, 0)?, "1969-12-31T23:59:59Z");
+        assert_eq!(format(951_782_400, 0)?, "2000-02-29T00:00:00Z");
+        assert_eq!(format(MIN_SECONDS, 0)?, "0001-01-01T00:00:00Z");
+        assert_eq!(
+            format(MAX_SECONDS, 999_999_999)?,
+            "9999-12-31T23:59:59.999999999Z"
+        );
+        assert!(format(MIN_SECONDS - 1, 0).is_err());
+        assert!(format(MAX_SECONDS + 1, 0).is_err());
+        assert!(format(0, -1).is_err());
+        assert!(format(0, 1_000_000_000).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse() -> anyhow::Result<()> {
+        assert_eq!(parse("1970-01-01T00:00:00Z")?, (0, 0));
+        assert_eq!(parse("1970-01-01T00:00:01.000000002Z")?, (1, 2));
+        assert_eq!(parse("1970-01-01T00:00:01.2Z")?, (1, 200_000_000));
+        assert_eq!(parse("1970-01-01T09:00:00+09:00")?, (0, 0));
+        assert_eq!(parse("1969-12-31T15:00:00-09:00")?, (0, 0));
+        assert_eq!(parse("2000-02-29T00:00:00z")?, (951_782_400, 0));
+        assert!(parse("2001-02-29T00:00:00Z").is_err());
+        assert!(parse("1970-01-01T00:00:00").is_err());
+        assert!(parse("1970-01-01T00:00:00.Z").is_err());
+        assert!(parse("1970-01-01T00:00:00.0000000001Z").is_err());
+        assert!(parse("1970-01-01T24:00:00Z").is_err());
+        assert!(parse("0001-01-01T00:00:00+00:01").is_err());
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 use crate::google::firestore::v1::Value;
 use crate::ser::firestore_variable_reference_value_serializer::FirestoreVariableReferenceValueSerializer;
-use crate::{FieldReference, VariableReference};
+use crate::{FieldReference, LatLng, RawValue, Timestamp, VariableReference};
 use crate::{
     Reference,
     error::ErrorCode,
@@ -21,14 +21,25 @@ use super::{
 /// A Serializer type which implements [`serde::Serializer`] for [`Value`].
 #[derive(Debug)]
 pub struct Serializer {
-    _private: (),
+    human_readable: bool,
 }
 
 impl Serializer {
     /// Creates a new `FirestoreValueSerializer`.
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { _private: () }
+        Self {
+            human_readable: true,
+        }
+    }
+
+    /// Creates a serializer for the inner value of a private newtype (e.g.
+    /// `Timestamp`). It is not human readable so that the inner value uses
+    /// the encoding for this crate instead of a natural encoding.
+    fn private() -> Self {
+        Self {
+            human_readable: false,
+        }
     }
 }
 
@@ -110,7 +121,8 @@ impl serde::Serializer for Serializer {
     }
 
     fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
-        // NOTE: unreachable. See: <https://serde.rs/impl-serialize.html#other-special-cases>
+        // NOTE: `Vec<u8>` does not reach here. Use `Bytes`, `with::bytes` or `serde_bytes`.
+        // See: <https://serde.rs/impl-serialize.html#other-special-cases>
         if v.len() > MAX_BYTE_LEN {
             return Err(Error::from(ErrorCode::MaximumByteLengthExceeded));
         }
@@ -159,6 +171,10 @@ impl serde::Serializer for Serializer {
             value.serialize(FirestoreFieldReferenceValueSerializer)
         } else if name == VariableReference::NAME {
             value.serialize(FirestoreVariableReferenceValueSerializer)
+        } else if name == Timestamp::NAME || name == LatLng::NAME {
+            value.serialize(Serializer::private())
+        } else if name == RawValue::NAME {
+            crate::typ::raw_value::from_marked_value(&value.serialize(Serializer::new())?)
         } else {
             value.serialize(Serializer::new())
         }
@@ -232,4 +248,8 @@ impl serde::Serializer for Serializer {
             Self::SerializeMap::new(Some(len)),
         ))
     }
+
+    fn is_human_readable(&self) -> bool {
+        self.human_readable
+    }
 }
@@ -0,0 +1,40 @@
+struct BytesRef<'a>(&'a [u8]);
+
+impl serde::Serialize for BytesRef<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_bytes(self.0)
+    }
+}
+
+pub(crate) fn serialize_bytes<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    T: AsRef<[u8]>,
+{
+    serializer.serialize_bytes(value.as_ref())
+}
+
+pub(crate) fn serialize_option_bytes<S, T>(
+    value: &Option<T>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    T: AsRef<[u8]>,
+{
+    serde::Serialize::serialize(
+        &value.as_ref().map(|value| BytesRef(value.as_ref())),
+        serializer,
+    )
+}
+
+pub(crate) fn serialize_vec_bytes<S, T>(value: &[T], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    T: AsRef<[u8]>,
+{
+    serializer.collect_seq(value.iter().map(|value| BytesRef(value.as_ref())))
+}
@@ -0,0 +1,8 @@
+use crate::google::firestore::v1::Value;
+
+pub(crate) fn serialize_raw_value<S>(value: &Value, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    crate::typ::raw_value::serialize_raw_value(value, serializer)
+}
@@ -0,0 +1,11 @@
+use crate::FieldReference;
+
+pub(crate) fn serialize_string_as_field_reference<S>(
+    value: &str,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serde::Serialize::serialize(&FieldReference::from(value.to_string()), serializer)
+}
@@ -0,0 +1,40 @@
+struct UuidAsBytes<'a>(&'a uuid::Uuid);
+
+impl serde::Serialize for UuidAsBytes<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_bytes(self.0.as_bytes())
+    }
+}
+
+pub(crate) fn serialize_uuid_as_bytes<S>(
+    value: &uuid::Uuid,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serde::Serialize::serialize(&UuidAsBytes(value), serializer)
+}
+
+pub(crate) fn serialize_option_uuid_as_bytes<S>(
+    value: &Option<uuid::Uuid>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serde::Serialize::serialize(&value.as_ref().map(UuidAsBytes), serializer)
+}
+
+pub(crate) fn serialize_vec_uuid_as_bytes<S>(
+    value: &[uuid::Uuid],
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.collect_seq(value.iter().map(UuidAsBytes))
+}
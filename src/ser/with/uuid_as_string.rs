@@ -0,0 +1,44 @@
+struct UuidAsString<'a>(&'a uuid::Uuid);
+
+impl serde::Serialize for UuidAsString<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(
+            self.0
+                .hyphenated()
+                .encode_lower(&mut uuid::Uuid::encode_buffer()),
+        )
+    }
+}
+
+pub(crate) fn serialize_uuid_as_string<S>(
+    value: &uuid::Uuid,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serde::Serialize::serialize(&UuidAsString(value), serializer)
+}
+
+pub(crate) fn serialize_option_uuid_as_string<S>(
+    value: &Option<uuid::Uuid>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serde::Serialize::serialize(&value.as_ref().map(UuidAsString), serializer)
+}
+
+pub(crate) fn serialize_vec_uuid_as_string<S>(
+    value: &[uuid::Uuid],
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.collect_seq(value.iter().map(UuidAsString))
+}
@@ -0,0 +1,304 @@
+//! Adapters for `#[serde_as(as = "...")]` of the `serde_with` crate.
+//!
+//! Each adapter implements [`SerializeAs`] and [`DeserializeAs`], so it
+//! composes with the containers supported by `serde_with` (`Option`, `Vec`,
+//! `HashMap`, ...) without a dedicated module in [`crate::with`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! # fn main() -> anyhow::Result<()> {
+//! use serde_firestore_value::serde_as::{AsReference, AsTimestamp};
+//! use serde_firestore_value::{from_value, to_value};
+//!
+//! #[serde_with::serde_as]
+//! #[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+//! struct S {
+//!     #[serde_as(as = "Vec<Option<AsTimestamp>>")]
+//!     timestamps: Vec<Option<prost_types::Timestamp>>,
+//!     #[serde_as(as = "std::collections::HashMap<_, AsReference>")]
+//!     references: std::collections::HashMap<String, String>,
+//! }
+//!
+//! let o = S {
+//!     timestamps: vec![Some(prost_types::Timestamp { seconds: 1, nanos: 2 }), None],
+//!     references: std::collections::HashMap::from([(
+//!         "k".to_string(),
+//!         "projects/p/databases/d/documents/c/1".to_string(),
+//!     )]),
+//! };
+//! let v = to_value(&o)?;
+//! assert_eq!(from_value::<'_, S>(&v)?, o);
+//! #     Ok(())
+//! # }
+//! ```
+
+use serde_with::{DeserializeAs, SerializeAs};
+
+use crate::google::r#type::LatLng as GoogleApiProtoLatLng;
+use crate::{FieldReference, LatLng, Reference, Timestamp};
+
+/// (De)serialize `String` or [`FieldReference`] as `fieldReferenceValue`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct AsFieldReference;
+
+impl SerializeAs<String> for AsFieldReference {
+    fn serialize_as<S>(source: &String, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::with::string_as_field_reference::serialize(source, serializer)
+    }
+}
+
+impl<'de> DeserializeAs<'de, String> for AsFieldReference {
+    fn deserialize_as<D>(deserializer: D) -> Result<String, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        crate::with::string_as_field_reference::deserialize(deserializer)
+    }
+}
+
+impl SerializeAs<FieldReference> for AsFieldReference {
+    fn serialize_as<S>(source: &FieldReference, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serde::Serialize::serialize(source, serializer)
+    }
+}
+
+impl<'de> DeserializeAs<'de, FieldReference> for AsFieldReference {
+    fn deserialize_as<D>(deserializer: D) -> Result<FieldReference, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        serde::Deserialize::deserialize(deserializer)
+    }
+}
+
+/// (De)serialize `google::type::LatLng`, [`LatLng`] or `geo_types::Point<f64>`
+/// (`geo-types` feature) as `geoPointValue`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct AsGeoPoint;
+
+impl SerializeAs<GoogleApiProtoLatLng> for AsGeoPoint {
+    fn serialize_as<S>(source: &GoogleApiProtoLatLng, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::with::lat_lng::serialize(source, serializer)
+    }
+}
+
+impl<'de> DeserializeAs<'de, GoogleApiProtoLatLng> for AsGeoPoint {
+    fn deserialize_as<D>(deserializer: D) -> Result<GoogleApiProtoLatLng, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        crate::with::lat_lng::deserialize(deserializer)
+    }
+}
+
+impl SerializeAs<LatLng> for AsGeoPoint {
+    fn serialize_as<S>(source: &LatLng, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serde::Serialize::serialize(source, serializer)
+    }
+}
+
+impl<'de> DeserializeAs<'de, LatLng> for AsGeoPoint {
+    fn deserialize_as<D>(deserializer: D) -> Result<LatLng, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        serde::Deserialize::deserialize(deserializer)
+    }
+}
+
+#[cfg(feature = "geo-types")]
+impl SerializeAs<geo_types::Point<f64>> for AsGeoPoint {
+    fn serialize_as<S>(source: &geo_types::Point<f64>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::with::geo_point::serialize(source, serializer)
+    }
+}
+
+#[cfg(feature = "geo-types")]
+impl<'de> DeserializeAs<'de, geo_types::Point<f64>> for AsGeoPoint {
+    fn deserialize_as<D>(deserializer: D) -> Result<geo_types::Point<f64>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        crate::with::geo_point::deserialize(deserializer)
+    }
+}
+
+/// (De)serialize `String` or [`Reference`] as `referenceValue`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct AsReference;
+
+impl SerializeAs<String> for AsReference {
+    fn serialize_as<S>(source: &String, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::with::string_as_reference::serialize(source, serializer)
+    }
+}
+
+impl<'de> DeserializeAs<'de, String> for AsReference {
+    fn deserialize_as<D>(deserializer: D) -> Result<String, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        crate::with::string_as_reference::deserialize(deserializer)
+    }
+}
+
+impl SerializeAs<Reference> for AsReference {
+    fn serialize_as<S>(source: &Reference, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serde::Serialize::serialize(source, serializer)
+    }
+}
+
+impl<'de> DeserializeAs<'de, Reference> for AsReference {
+    fn deserialize_as<D>(deserializer: D) -> Result<Reference, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        serde::Deserialize::deserialize(deserializer)
+    }
+}
+
+/// (De)serialize `prost_types::Timestamp`, [`Timestamp`],
+/// `std::time::SystemTime`, `chrono::DateTime<chrono::Utc>` (`chrono`
+/// feature), `jiff::Timestamp` (`jiff` feature) or `time::OffsetDateTime`
+/// (`time` feature) as `timestampValue`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct AsTimestamp;
+
+impl SerializeAs<prost_types::Timestamp> for AsTimestamp {
+    fn serialize_as<S>(source: &prost_types::Timestamp, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::with::timestamp::serialize(source, serializer)
+    }
+}
+
+impl<'de> DeserializeAs<'de, prost_types::Timestamp> for AsTimestamp {
+    fn deserialize_as<D>(deserializer: D) -> Result<prost_types::Timestamp, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        crate::with::timestamp::deserialize(deserializer)
+    }
+}
+
+impl SerializeAs<Timestamp> for AsTimestamp {
+    fn serialize_as<S>(source: &Timestamp, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serde::Serialize::serialize(source, serializer)
+    }
+}
+
+impl<'de> DeserializeAs<'de, Timestamp> for AsTimestamp {
+    fn deserialize_as<D>(deserializer: D) -> Result<Timestamp, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        serde::Deserialize::deserialize(deserializer)
+    }
+}
+
+impl SerializeAs<std::time::SystemTime> for AsTimestamp {
+    fn serialize_as<S>(source: &std::time::SystemTime, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::with::system_time_as_timestamp::serialize(source, serializer)
+    }
+}
+
+impl<'de> DeserializeAs<'de, std::time::SystemTime> for AsTimestamp {
+    fn deserialize_as<D>(deserializer: D) -> Result<std::time::SystemTime, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        crate::with::system_time_as_timestamp::deserialize(deserializer)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl SerializeAs<chrono::DateTime<chrono::Utc>> for AsTimestamp {
+    fn serialize_as<S>(
+        source: &chrono::DateTime<chrono::Utc>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::with::chrono_date_time_as_timestamp::serialize(source, serializer)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<'de> DeserializeAs<'de, chrono::DateTime<chrono::Utc>> for AsTimestamp {
+    fn deserialize_as<D>(deserializer: D) -> Result<chrono::DateTime<chrono::Utc>, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        crate::with::chrono_date_time_as_timestamp::deserialize(deserializer)
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl SerializeAs<jiff::Timestamp> for AsTimestamp {
+    fn serialize_as<S>(source: &jiff::Timestamp, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::with::jiff_timestamp_as_timestamp::serialize(source, serializer)
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl<'de> DeserializeAs<'de, jiff::Timestamp> for AsTimestamp {
+    fn deserialize_as<D>(deserializer: D) -> Result<jiff::Timestamp, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        crate::with::jiff_timestamp_as_timestamp::deserialize(deserializer)
+    }
+}
+
+#[cfg(feature = "time")]
+impl SerializeAs<time::OffsetDateTime> for AsTimestamp {
+    fn serialize_as<S>(source: &time::OffsetDateTime, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::with::time_offset_date_time_as_timestamp::serialize(source, serializer)
+    }
+}
+
+#[cfg(feature = "time")]
+impl<'de> DeserializeAs<'de, time::OffsetDateTime> for AsTimestamp {
+    fn deserialize_as<D>(deserializer: D) -> Result<time::OffsetDateTime, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        crate::with::time_offset_date_time_as_timestamp::deserialize(deserializer)
+    }
+}
@@ -1,3 +1,11 @@
+#[cfg(feature = "serde_json")]
+mod conversion;
+
+#[cfg(feature = "serde_json")]
+pub use self::conversion::{
+    BigNumberStrategy, JsonOptions, Representation, json_to_value, value_to_json,
+};
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
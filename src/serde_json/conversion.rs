@@ -0,0 +1,489 @@
+use base64::Engine as _;
+
+use crate::google::{
+    firestore::v1::{
+        ArrayValue, Function, MapValue, Pipeline, Value, pipeline::Stage, value::ValueType,
+    },
+    r#type::LatLng,
+};
+use crate::{Error, error::ErrorCode, rfc3339, value_ext::ValueExt};
+
+const BYTES_TAG: &str = "$bytes";
+const FIELD_REFERENCE_TAG: &str = "$fieldReference";
+const FUNCTION_TAG: &str = "$function";
+const GEO_POINT_TAG: &str = "$geoPoint";
+const MAP_TAG: &str = "$map";
+const PIPELINE_TAG: &str = "$pipeline";
+const REFERENCE_TAG: &str = "$reference";
+const TIMESTAMP_TAG: &str = "$timestamp";
+const VARIABLE_REFERENCE_TAG: &str = "$variableReference";
+
+/// How a Firestore-specific value is represented in JSON.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Representation {
+    /// A single-key object such as `{"$reference": "projects/..."}`.
+    ///
+    /// Tagged values are always recognized by [`json_to_value`].
+    #[default]
+    Tagged,
+    /// A plain JSON value.
+    ///
+    /// - `referenceValue`: the resource name string. Strings which look like
+    ///   `projects/{p}/databases/{d}/documents/{path}` are read back as references.
+    /// - `geoPointValue`: `{"latitude": f64, "longitude": f64}`. Objects with
+    ///   exactly these two number fields are read back as geo points.
+    /// - `bytesValue`: a base64 string. It is read back as a `stringValue`.
+    /// - `timestampValue`: an RFC 3339 string. See [`JsonOptions::rfc3339_strings_as_timestamps`].
+    Plain,
+}
+
+/// How JSON integers outside the `i64` range are converted.
+///
+/// JSON numbers larger than `u64::MAX` are parsed as `f64` by `serde_json`
+/// and always become `doubleValue`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum BigNumberStrategy {
+    /// Returns an error.
+    #[default]
+    Error,
+    /// Converts to `doubleValue`. This may lose precision.
+    Double,
+    /// Converts to `stringValue` of the decimal representation.
+    String,
+}
+
+/// Options for the conversion between [`serde_json::Value`] and [`Value`].
+///
+/// The default options round-trip every [`Value`] except non-finite doubles.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{JsonOptions, Representation};
+///
+/// let options = JsonOptions::new()
+///     .reference(Representation::Plain)
+///     .rfc3339_strings_as_timestamps(true);
+/// let value = options.json_to_value(&serde_json::json!("2001-02-03T04:05:06Z"))?;
+/// assert_eq!(
+///     value,
+///     Value {
+///         value_type: Some(ValueType::TimestampValue(prost_types::Timestamp {
+///             seconds: 981_173_106,
+///             nanos: 0,
+///         })),
+///     }
+/// );
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct JsonOptions {
+    big_number: BigNumberStrategy,
+    bytes: Representation,
+    geo_point: Representation,
+    reference: Representation,
+    rfc3339_strings_as_timestamps: bool,
+    timestamp: Representation,
+}
+
+impl JsonOptions {
+    /// Creates the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how JSON integers outside the `i64` range are converted.
+    pub fn big_number(mut self, strategy: BigNumberStrategy) -> Self {
+        self.big_number = strategy;
+        self
+    }
+
+    /// Sets how `bytesValue` is represented.
+    pub fn bytes(mut self, representation: Representation) -> Self {
+        self.bytes = representation;
+        self
+    }
+
+    /// Sets how `geoPointValue` is represented.
+    pub fn geo_point(mut self, representation: Representation) -> Self {
+        self.geo_point = representation;
+        self
+    }
+
+    /// Sets how `referenceValue` is represented.
+    pub fn reference(mut self, representation: Representation) -> Self {
+        self.reference = representation;
+        self
+    }
+
+    /// Sets whether JSON strings in RFC 3339 format become `timestampValue`.
+    pub fn rfc3339_strings_as_timestamps(mut self, enabled: bool) -> Self {
+        self.rfc3339_strings_as_timestamps = enabled;
+        self
+    }
+
+    /// Sets how `timestampValue` is represented.
+    pub fn timestamp(mut self, representation: Representation) -> Self {
+        self.timestamp = representation;
+        self
+    }
+
+    /// Converts a [`serde_json::Value`] to a [`Value`] using these options.
+    pub fn json_to_value(&self, json: &serde_json::Value) -> Result<Value, Error> {
+        Ok(match json {
+            serde_json::Value::Null => Value::null(),
+            serde_json::Value::Bool(b) => Value::from_bool(*b),
+            serde_json::Value::Number(n) => self.number_to_value(n)?,
+            serde_json::Value::String(s) => self.string_to_value(s),
+            serde_json::Value::Array(values) => Value::from_values(
+                values
+                    .iter()
+                    .map(|v| self.json_to_value(v))
+                    .collect::<Result<Vec<_>, Error>>()?,
+            ),
+            serde_json::Value::Object(object) => self.object_to_value(object)?,
+        })
+    }
+
+    /// Converts a [`Value`] to a [`serde_json::Value`] using these options.
+    pub fn value_to_json(&self, value: &Value) -> Result<serde_json::Value, Error> {
+        Ok(match value.value_type()? {
+            ValueType::NullValue(_) => serde_json::Value::Null,
+            ValueType::BooleanValue(b) => serde_json::Value::Bool(*b),
+            ValueType::IntegerValue(i) => serde_json::Value::from(*i),
+            ValueType::DoubleValue(d) => serde_json::Number::from_f64(*d)
+                .map(serde_json::Value::Number)
+                .ok_or_else(|| invalid(format_args!("{} can not be represented in JSON", d)))?,
+            ValueType::TimestampValue(t) => {
+                let s = serde_json::Value::String(rfc3339::format(t.seconds, t.nanos)?);
+                match self.timestamp {
+                    Representation::Tagged => tagged(TIMESTAMP_TAG, s),
+                    Representation::Plain => s,
+                }
+            }
+            ValueType::StringValue(s) => serde_json::Value::String(s.clone()),
+            ValueType::BytesValue(b) => {
+                let s =
+                    serde_json::Value::String(base64::engine::general_purpose::STANDARD.encode(b));
+                match self.bytes {
+                    Representation::Tagged => tagged(BYTES_TAG, s),
+                    Representation::Plain => s,
+                }
+            }
+            ValueType::ReferenceValue(r) => {
+                let s = serde_json::Value::String(r.clone());
+                match self.reference {
+                    Representation::Tagged => tagged(REFERENCE_TAG, s),
+                    Representation::Plain => s,
+                }
+            }
+            ValueType::GeoPointValue(LatLng {
+                latitude,
+                longitude,
+            }) => {
+                let object = serde_json::json!({
+                    "latitude": latitude,
+                    "longitude": longitude,
+                });
+                match self.geo_point {
+                    Representation::Tagged => tagged(GEO_POINT_TAG, object),
+                    Representation::Plain => object,
+                }
+            }
+            ValueType::ArrayValue(ArrayValue { values }) => self.values_to_json(values)?,
+            ValueType::MapValue(MapValue { fields }) => {
+                let object = self.fields_to_json(fields.iter())?;
+                // Escape maps which would be read back as a tagged value.
+                if self.is_ambiguous_object(&object) {
+                    tagged(MAP_TAG, object)
+                } else {
+                    object
+                }
+            }
+            ValueType::FieldReferenceValue(s) => {
+                tagged(FIELD_REFERENCE_TAG, serde_json::Value::String(s.clone()))
+            }
+            ValueType::VariableReferenceValue(s) => {
+                tagged(VARIABLE_REFERENCE_TAG, serde_json::Value::String(s.clone()))
+            }
+            ValueType::FunctionValue(Function {
+                name,
+                args,
+                options,
+            }) => tagged(FUNCTION_TAG, self.call_to_json(name, args, options.iter())?),
+            ValueType::PipelineValue(Pipeline { stages }) => tagged(
+                PIPELINE_TAG,
+                serde_json::json!({
+                    "stages": stages
+                        .iter()
+                        .map(|stage| self.call_to_json(&stage.name, &stage.args, stage.options.iter()))
+                        .collect::<Result<Vec<_>, Error>>()?,
+                }),
+            ),
+        })
+    }
+
+    fn number_to_value(&self, n: &serde_json::Number) -> Result<Value, Error> {
+        if let Some(i) = n.as_i64() {
+            return Ok(Value::from_i64(i));
+        }
+        if let Some(u) = n.as_u64() {
+            return match self.big_number {
+                BigNumberStrategy::Error => {
+                    Err(invalid(format_args!("{} is out of range of i64", u)))
+                }
+                BigNumberStrategy::Double => Ok(Value::from_f64(u as f64)),
+                BigNumberStrategy::String => Ok(Value::from_string(u.to_string())),
+            };
+        }
+        n.as_f64()
+            .map(Value::from_f64)
+            .ok_or_else(|| invalid(format_args!("invalid number {}", n)))
+    }
+
+    fn string_to_value(&self, s: &str) -> Value {
+        if self.rfc3339_strings_as_timestamps
+            && let Ok((seconds, nanos)) = rfc3339::parse(s)
+        {
+            return Value::from_timestamp(prost_types::Timestamp { seconds, nanos });
+        }
+        if self.reference == Representation::Plain && is_document_name(s) {
+            return Value::from_string_as_reference_value(s.to_string());
+        }
+        Value::from_string(s.to_string())
+    }
+
+    fn object_to_value(
+        &self,
+        object: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<Value, Error> {
+        if object.len() == 1 {
+            let (key, json) = object.iter().next().expect("object must have an entry");
+            match key.as_str() {
+                BYTES_TAG => {
+                    let s = tag_string(key, json)?;
+                    let bytes = base64::engine::general_purpose::STANDARD
+                        .decode(s)
+                        .map_err(|e| invalid(format_args!("invalid {}: {}", key, e)))?;
+                    return Ok(Value::from_bytes(bytes));
+                }
+                FIELD_REFERENCE_TAG => {
+                    return Ok(Value::from_string_as_field_reference_value(
+                        tag_string(key, json)?.to_string(),
+                    ));
+                }
+                FUNCTION_TAG => {
+                    let (name, args, options) = self.call_from_json(key, json)?;
+                    return Ok(Value::from_function(Function {
+                        name,
+                        args,
+                        options: options.into_iter().collect(),
+                    }));
+                }
+                GEO_POINT_TAG => {
+                    let object = json
+                        .as_object()
+                        .ok_or_else(|| invalid(format_args!("{} must be an object", key)))?;
+                    return lat_lng_from_json(object)
+                        .map(Value::from_lat_lng)
+                        .ok_or_else(|| invalid(format_args!("invalid {}", key)));
+                }
+                MAP_TAG => {
+                    let object = json
+                        .as_object()
+                        .ok_or_else(|| invalid(format_args!("{} must be an object", key)))?;
+                    return Ok(Value::from_fields(self.fields_from_json(object)?));
+                }
+                PIPELINE_TAG => {
+                    let stages = json
+                        .get("stages")
+                        .and_then(serde_json::Value::as_array)
+                        .ok_or_else(|| invalid(format_args!("{}.stages must be an array", key)))?
+                        .iter()
+                        .map(|stage| {
+                            self.call_from_json(key, stage)
+                                .map(|(name, args, options)| Stage {
+                                    name,
+                                    args,
+                                    options: options.into_iter().collect(),
+                                })
+                        })
+                        .collect::<Result<Vec<_>, Error>>()?;
+                    return Ok(Value::from_pipeline(Pipeline { stages }));
+                }
+                REFERENCE_TAG => {
+                    return Ok(Value::from_string_as_reference_value(
+                        tag_string(key, json)?.to_string(),
+                    ));
+                }
+                TIMESTAMP_TAG => {
+                    let (seconds, nanos) = rfc3339::parse(tag_string(key, json)?)?;
+                    return Ok(Value::from_timestamp(prost_types::Timestamp {
+                        seconds,
+                        nanos,
+                    }));
+                }
+                VARIABLE_REFERENCE_TAG => {
+                    return Ok(Value::from_string_as_variable_reference_value(
+                        tag_string(key, json)?.to_string(),
+                    ));
+                }
+                _ => {}
+            }
+        }
+        if self.geo_point == Representation::Plain
+            && let Some(lat_lng) = lat_lng_from_json(object)
+        {
+            return Ok(Value::from_lat_lng(lat_lng));
+        }
+        Ok(Value::from_fields(self.fields_from_json(object)?))
+    }
+
+    fn fields_from_json(
+        &self,
+        object: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<Vec<(String, Value)>, Error> {
+        object
+            .iter()
+            .map(|(k, v)| self.json_to_value(v).map(|v| (k.clone(), v)))
+            .collect()
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn call_from_json(
+        &self,
+        tag: &str,
+        json: &serde_json::Value,
+    ) -> Result<(String, Vec<Value>, Vec<(String, Value)>), Error> {
+        let object = json
+            .as_object()
+            .ok_or_else(|| invalid(format_args!("{} must be an object", tag)))?;
+        let name = object
+            .get("name")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| invalid(format_args!("{}.name must be a string", tag)))?
+            .to_string();
+        let args = match object.get("args") {
+            None => Vec::new(),
+            Some(serde_json::Value::Array(args)) => args
+                .iter()
+                .map(|v| self.json_to_value(v))
+                .collect::<Result<Vec<_>, Error>>()?,
+            Some(_) => return Err(invalid(format_args!("{}.args must be an array", tag))),
+        };
+        let options = match object.get("options") {
+            None => Vec::new(),
+            Some(serde_json::Value::Object(options)) => self.fields_from_json(options)?,
+            Some(_) => return Err(invalid(format_args!("{}.options must be an object", tag))),
+        };
+        Ok((name, args, options))
+    }
+
+    fn values_to_json(&self, values: &[Value]) -> Result<serde_json::Value, Error> {
+        Ok(serde_json::Value::Array(
+            values
+                .iter()
+                .map(|v| self.value_to_json(v))
+                .collect::<Result<Vec<_>, Error>>()?,
+        ))
+    }
+
+    fn fields_to_json<'a, I>(&self, fields: I) -> Result<serde_json::Value, Error>
+    where
+        I: Iterator<Item = (&'a String, &'a Value)>,
+    {
+        Ok(serde_json::Value::Object(
+            fields
+                .map(|(k, v)| self.value_to_json(v).map(|v| (k.clone(), v)))
+                .collect::<Result<serde_json::Map<_, _>, Error>>()?,
+        ))
+    }
+
+    fn call_to_json<'a, I>(
+        &self,
+        name: &str,
+        args: &[Value],
+        options: I,
+    ) -> Result<serde_json::Value, Error>
+    where
+        I: Iterator<Item = (&'a String, &'a Value)>,
+    {
+        Ok(serde_json::json!({
+            "name": name,
+            "args": self.values_to_json(args)?,
+            "options": self.fields_to_json(options)?,
+        }))
+    }
+
+    fn is_ambiguous_object(&self, json: &serde_json::Value) -> bool {
+        let Some(object) = json.as_object() else {
+            return false;
+        };
+        (object.len() == 1 && object.keys().all(|k| k.starts_with('$')))
+            || (self.geo_point == Representation::Plain && lat_lng_from_json(object).is_some())
+    }
+}
+
+/// Converts a [`serde_json::Value`] to a [`Value`] using the default [`JsonOptions`].
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::{json_to_value, value_to_json};
+///
+/// let json = serde_json::json!({
+///     "n": 1,
+///     "r": { "$reference": "projects/p/databases/d/documents/c/1" },
+///     "t": { "$timestamp": "1970-01-01T00:00:01Z" },
+/// });
+/// let value = json_to_value(&json)?;
+/// assert_eq!(value_to_json(&value)?, json);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn json_to_value(json: &serde_json::Value) -> Result<Value, Error> {
+    JsonOptions::default().json_to_value(json)
+}
+
+/// Converts a [`Value`] to a [`serde_json::Value`] using the default [`JsonOptions`].
+pub fn value_to_json(value: &Value) -> Result<serde_json::Value, Error> {
+    JsonOptions::default().value_to_json(value)
+}
+
+fn invalid<T: std::fmt::Display>(e: T) -> Error {
+    Error::from(ErrorCode::InvalidJson(e.to_string()))
+}
+
+fn tagged(tag: &str, json: serde_json::Value) -> serde_json::Value {
+    serde_json::Value::Object(serde_json::Map::from_iter([(tag.to_string(), json)]))
+}
+
+fn tag_string<'a>(tag: &str, json: &'a serde_json::Value) -> Result<&'a str, Error> {
+    json.as_str()
+        .ok_or_else(|| invalid(format_args!("{} must be a string", tag)))
+}
+
+fn lat_lng_from_json(object: &serde_json::Map<String, serde_json::Value>) -> Option<LatLng> {
+    if object.len() != 2 {
+        return None;
+    }
+    Some(LatLng {
+        latitude: object.get("latitude")?.as_f64()?,
+        longitude: object.get("longitude")?.as_f64()?,
+    })
+}
+
+/// `projects/{project_id}/databases/{database_id}/documents/{document_path}`
+fn is_document_name(s: &str) -> bool {
+    let segments = s.split('/').collect::<Vec<&str>>();
+    segments.len() >= 7
+        && segments.len() % 2 == 1
+        && segments[0] == "projects"
+        && segments[2] == "databases"
+        && segments[4] == "documents"
+        && segments.iter().all(|segment| !segment.is_empty())
+}
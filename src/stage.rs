@@ -0,0 +1,752 @@
+//! Typed pipeline stages.
+//!
+//! [`TypedStage`] is the typed form of a [`Stage`]. The builder methods on
+//! [`Pipeline`] append typed stages in the encoding Firestore expects, and
+//! [`Pipeline::typed_stages`] decodes an existing pipeline.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # fn main() -> anyhow::Result<()> {
+//! use serde_firestore_value::expr::{count_all, field, lit};
+//! use serde_firestore_value::stage::{Ordering, TypedStage};
+//! use serde_firestore_value::Pipeline;
+//!
+//! let pipeline = Pipeline::collection("users")
+//!     .where_(field("age").gte(lit(18)))
+//!     .select(["name", "age"])
+//!     .sort([Ordering::descending(field("age"))])
+//!     .limit(10);
+//! assert_eq!(pipeline.stages[0].name, "collection");
+//! assert_eq!(
+//!     pipeline.typed_stages()?[4],
+//!     TypedStage::Limit(10)
+//! );
+//!
+//! let pipeline = Pipeline::collection("users")
+//!     .aggregate([("total", count_all())], [("city", field("city"))]);
+//! assert_eq!(pipeline.stages[1].name, "aggregate");
+//! #     Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+
+use crate::expr::Expr;
+use crate::google::firestore::v1::{Value, value::ValueType};
+use crate::value_ext::ValueExt as _;
+use crate::{Error, FieldReference, Pipeline, Stage, error::ErrorCode};
+
+/// Names of the pipeline stages.
+pub mod names {
+    /// `add_fields(map)`
+    pub const ADD_FIELDS: &str = "add_fields";
+    /// `aggregate(accumulators, groups)`
+    pub const AGGREGATE: &str = "aggregate";
+    /// `collection(reference)`
+    pub const COLLECTION: &str = "collection";
+    /// `collection_group(reference, collection_id)`
+    pub const COLLECTION_GROUP: &str = "collection_group";
+    /// `database()`
+    pub const DATABASE: &str = "database";
+    /// `distinct(groups)`
+    pub const DISTINCT: &str = "distinct";
+    /// `documents(references...)`
+    pub const DOCUMENTS: &str = "documents";
+    /// `find_nearest(field, vector, distance_measure)`
+    pub const FIND_NEAREST: &str = "find_nearest";
+    /// `limit(n)`
+    pub const LIMIT: &str = "limit";
+    /// `offset(n)`
+    pub const OFFSET: &str = "offset";
+    /// `remove_fields(fields...)`
+    pub const REMOVE_FIELDS: &str = "remove_fields";
+    /// `select(map)`
+    pub const SELECT: &str = "select";
+    /// `sort(orderings...)`
+    pub const SORT: &str = "sort";
+    /// `where(condition)`
+    pub const WHERE: &str = "where";
+}
+
+/// A typed pipeline stage.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TypedStage {
+    /// `add_fields`. Aliases to expressions.
+    AddFields(BTreeMap<String, Expr>),
+    /// `aggregate`.
+    Aggregate {
+        /// Aliases to accumulator expressions (e.g. `count()`, `sum(x)`).
+        accumulators: BTreeMap<String, Expr>,
+        /// Aliases to grouping expressions.
+        groups: BTreeMap<String, Expr>,
+    },
+    /// `collection`. The collection path (e.g. `/users`).
+    Collection(String),
+    /// `collection_group`.
+    CollectionGroup {
+        /// The path of the parent document (e.g. `/users/alice`), or `""`
+        /// for the whole database.
+        parent: String,
+        /// The collection ID.
+        collection_id: String,
+    },
+    /// `database`.
+    Database,
+    /// `distinct`. Aliases to grouping expressions.
+    Distinct(BTreeMap<String, Expr>),
+    /// `documents`. The document paths (e.g. `/users/alice`).
+    Documents(Vec<String>),
+    /// `find_nearest`.
+    FindNearest(FindNearest),
+    /// `limit`.
+    Limit(i64),
+    /// `offset`.
+    Offset(i64),
+    /// A stage which has no typed form.
+    Other(Stage),
+    /// `remove_fields`.
+    RemoveFields(Vec<FieldReference>),
+    /// `select`. Aliases to expressions.
+    Select(BTreeMap<String, Expr>),
+    /// `sort`.
+    Sort(Vec<Ordering>),
+    /// `where`.
+    Where(Expr),
+}
+
+/// The parameters of a `find_nearest` stage.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FindNearest {
+    /// The vector field to search.
+    pub field: FieldReference,
+    /// The query vector.
+    pub vector: Vec<f64>,
+    /// The distance measure.
+    pub distance_measure: DistanceMeasure,
+    /// The `limit` option.
+    pub limit: Option<i64>,
+    /// The `distance_field` option.
+    pub distance_field: Option<FieldReference>,
+}
+
+/// The distance measure of a `find_nearest` stage.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DistanceMeasure {
+    /// `cosine`
+    Cosine,
+    /// `dot_product`
+    DotProduct,
+    /// `euclidean`
+    Euclidean,
+}
+
+impl DistanceMeasure {
+    /// Returns the encoded name.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DistanceMeasure::Cosine => "cosine",
+            DistanceMeasure::DotProduct => "dot_product",
+            DistanceMeasure::Euclidean => "euclidean",
+        }
+    }
+}
+
+impl std::str::FromStr for DistanceMeasure {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cosine" => Ok(DistanceMeasure::Cosine),
+            "dot_product" => Ok(DistanceMeasure::DotProduct),
+            "euclidean" => Ok(DistanceMeasure::Euclidean),
+            _ => Err(invalid_stage(format!("unknown distance measure `{s}`"))),
+        }
+    }
+}
+
+/// The direction of an [`Ordering`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Direction {
+    /// `ascending`
+    Ascending,
+    /// `descending`
+    Descending,
+}
+
+impl Direction {
+    /// Returns the encoded name.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Direction::Ascending => "ascending",
+            Direction::Descending => "descending",
+        }
+    }
+}
+
+/// An ordering of a `sort` stage.
+///
+/// Encoded as `{direction: "ascending" | "descending", expression: expr}`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ordering {
+    /// The expression to sort by.
+    pub expr: Expr,
+    /// The direction.
+    pub direction: Direction,
+}
+
+impl Ordering {
+    /// Creates an ascending ordering.
+    pub fn ascending<E: Into<Expr>>(expr: E) -> Self {
+        Self {
+            expr: expr.into(),
+            direction: Direction::Ascending,
+        }
+    }
+
+    /// Creates a descending ordering.
+    pub fn descending<E: Into<Expr>>(expr: E) -> Self {
+        Self {
+            expr: expr.into(),
+            direction: Direction::Descending,
+        }
+    }
+}
+
+/// An aliased expression of `select`, `add_fields`, `aggregate` and
+/// `distinct`.
+///
+/// A field path converts to a selection of that field aliased by its path.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Selection {
+    /// The output field name.
+    pub alias: String,
+    /// The expression.
+    pub expr: Expr,
+}
+
+impl From<&str> for Selection {
+    fn from(path: &str) -> Self {
+        Self::from(FieldReference(path.to_string()))
+    }
+}
+
+impl From<String> for Selection {
+    fn from(path: String) -> Self {
+        Self::from(FieldReference(path))
+    }
+}
+
+impl From<FieldReference> for Selection {
+    fn from(field_reference: FieldReference) -> Self {
+        Self {
+            alias: field_reference.0.clone(),
+            expr: Expr::Field(field_reference),
+        }
+    }
+}
+
+impl<S: Into<String>> From<(S, Expr)> for Selection {
+    fn from((alias, expr): (S, Expr)) -> Self {
+        Self {
+            alias: alias.into(),
+            expr,
+        }
+    }
+}
+
+impl Pipeline {
+    /// Creates a pipeline which starts with a `collection` stage.
+    ///
+    /// A leading `/` is added to `path` if missing.
+    pub fn collection<S: Into<String>>(path: S) -> Self {
+        let path = path.into();
+        let path = if path.starts_with('/') {
+            path
+        } else {
+            format!("/{path}")
+        };
+        Self::from_stage(TypedStage::Collection(path))
+    }
+
+    /// Creates a pipeline which starts with a `collection_group` stage over
+    /// the whole database.
+    pub fn collection_group<S: Into<String>>(collection_id: S) -> Self {
+        Self::collection_group_in("", collection_id)
+    }
+
+    /// Creates a pipeline which starts with a `collection_group` stage below
+    /// the document `parent` (e.g. `/users/alice`).
+    ///
+    /// A leading `/` is added to a non-empty `parent` if missing.
+    pub fn collection_group_in<P, S>(parent: P, collection_id: S) -> Self
+    where
+        P: Into<String>,
+        S: Into<String>,
+    {
+        let parent = parent.into();
+        let parent = if parent.is_empty() || parent.starts_with('/') {
+            parent
+        } else {
+            format!("/{parent}")
+        };
+        Self::from_stage(TypedStage::CollectionGroup {
+            parent,
+            collection_id: collection_id.into(),
+        })
+    }
+
+    /// Creates a pipeline which starts with a `database` stage.
+    pub fn database() -> Self {
+        Self::from_stage(TypedStage::Database)
+    }
+
+    /// Creates a pipeline which starts with a `documents` stage.
+    pub fn documents<I>(paths: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Self::from_stage(TypedStage::Documents(
+            paths.into_iter().map(Into::into).collect(),
+        ))
+    }
+
+    /// Appends an `add_fields` stage.
+    pub fn add_fields<I>(self, fields: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Selection>,
+    {
+        self.stage(TypedStage::AddFields(selections(fields)))
+    }
+
+    /// Appends an `aggregate` stage.
+    pub fn aggregate<A, G>(self, accumulators: A, groups: G) -> Self
+    where
+        A: IntoIterator,
+        A::Item: Into<Selection>,
+        G: IntoIterator,
+        G::Item: Into<Selection>,
+    {
+        self.stage(TypedStage::Aggregate {
+            accumulators: selections(accumulators),
+            groups: selections(groups),
+        })
+    }
+
+    /// Appends a `distinct` stage.
+    pub fn distinct<I>(self, groups: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Selection>,
+    {
+        self.stage(TypedStage::Distinct(selections(groups)))
+    }
+
+    /// Appends a `find_nearest` stage.
+    pub fn find_nearest(self, find_nearest: FindNearest) -> Self {
+        self.stage(TypedStage::FindNearest(find_nearest))
+    }
+
+    /// Appends a `limit` stage.
+    pub fn limit(self, limit: i64) -> Self {
+        self.stage(TypedStage::Limit(limit))
+    }
+
+    /// Appends an `offset` stage.
+    pub fn offset(self, offset: i64) -> Self {
+        self.stage(TypedStage::Offset(offset))
+    }
+
+    /// Appends a `remove_fields` stage.
+    pub fn remove_fields<I>(self, fields: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.stage(TypedStage::RemoveFields(
+            fields
+                .into_iter()
+                .map(|s| FieldReference(s.into()))
+                .collect(),
+        ))
+    }
+
+    /// Appends a `select` stage.
+    pub fn select<I>(self, fields: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Selection>,
+    {
+        self.stage(TypedStage::Select(selections(fields)))
+    }
+
+    /// Appends a `sort` stage.
+    pub fn sort<I>(self, orderings: I) -> Self
+    where
+        I: IntoIterator<Item = Ordering>,
+    {
+        self.stage(TypedStage::Sort(orderings.into_iter().collect()))
+    }
+
+    /// Appends a stage.
+    pub fn stage<S: Into<Stage>>(mut self, stage: S) -> Self {
+        self.stages.push(stage.into());
+        self
+    }
+
+    /// Appends a `where` stage.
+    pub fn where_<E: Into<Expr>>(self, condition: E) -> Self {
+        self.stage(TypedStage::Where(condition.into()))
+    }
+
+    /// Decodes the stages into [`TypedStage`]s.
+    ///
+    /// Stages with an unknown name become [`TypedStage::Other`]. Stages with
+    /// a known name but an unexpected encoding are an error.
+    pub fn typed_stages(&self) -> Result<Vec<TypedStage>, Error> {
+        self.stages
+            .iter()
+            .cloned()
+            .map(TypedStage::try_from)
+            .collect()
+    }
+
+    fn from_stage(stage: TypedStage) -> Self {
+        Self {
+            stages: vec![Stage::from(stage)],
+        }
+    }
+}
+
+impl From<TypedStage> for Stage {
+    fn from(stage: TypedStage) -> Self {
+        let (name, args, options): (&str, Vec<Value>, Vec<(&str, Value)>) = match stage {
+            TypedStage::AddFields(fields) => (names::ADD_FIELDS, vec![map_value(fields)], vec![]),
+            TypedStage::Aggregate {
+                accumulators,
+                groups,
+            } => (
+                names::AGGREGATE,
+                vec![map_value(accumulators), map_value(groups)],
+                vec![],
+            ),
+            TypedStage::Collection(path) => (
+                names::COLLECTION,
+                vec![Value::from_string_as_reference_value(path)],
+                vec![],
+            ),
+            TypedStage::CollectionGroup {
+                parent,
+                collection_id,
+            } => (
+                names::COLLECTION_GROUP,
+                vec![
+                    Value::from_string_as_reference_value(parent),
+                    Value::from_string(collection_id),
+                ],
+                vec![],
+            ),
+            TypedStage::Database => (names::DATABASE, vec![], vec![]),
+            TypedStage::Distinct(groups) => (names::DISTINCT, vec![map_value(groups)], vec![]),
+            TypedStage::Documents(paths) => (
+                names::DOCUMENTS,
+                paths
+                    .into_iter()
+                    .map(Value::from_string_as_reference_value)
+                    .collect(),
+                vec![],
+            ),
+            TypedStage::FindNearest(FindNearest {
+                field,
+                vector,
+                distance_measure,
+                limit,
+                distance_field,
+            }) => {
+                let mut options = vec![];
+                if let Some(limit) = limit {
+                    options.push(("limit", Value::from_i64(limit)));
+                }
+                if let Some(FieldReference(distance_field)) = distance_field {
+                    options.push((
+                        "distance_field",
+                        Value::from_string_as_field_reference_value(distance_field),
+                    ));
+                }
+                (
+                    names::FIND_NEAREST,
+                    vec![
+                        Value::from_string_as_field_reference_value(field.0),
+                        vector_value(vector),
+                        Value::from_string(distance_measure.as_str().to_string()),
+                    ],
+                    options,
+                )
+            }
+            TypedStage::Limit(limit) => (names::LIMIT, vec![Value::from_i64(limit)], vec![]),
+            TypedStage::Offset(offset) => (names::OFFSET, vec![Value::from_i64(offset)], vec![]),
+            TypedStage::Other(stage) => return stage,
+            TypedStage::RemoveFields(fields) => (
+                names::REMOVE_FIELDS,
+                fields
+                    .into_iter()
+                    .map(|FieldReference(s)| Value::from_string_as_field_reference_value(s))
+                    .collect(),
+                vec![],
+            ),
+            TypedStage::Select(fields) => (names::SELECT, vec![map_value(fields)], vec![]),
+            TypedStage::Sort(orderings) => (
+                names::SORT,
+                orderings
+                    .into_iter()
+                    .map(|Ordering { expr, direction }| {
+                        Value::from_fields([
+                            (
+                                "direction",
+                                Value::from_string(direction.as_str().to_string()),
+                            ),
+                            ("expression", Value::from(expr)),
+                        ])
+                    })
+                    .collect(),
+                vec![],
+            ),
+            TypedStage::Where(condition) => (names::WHERE, vec![Value::from(condition)], vec![]),
+        };
+        Stage {
+            name: name.to_string(),
+            args,
+            options: options
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+        }
+    }
+}
+
+impl std::convert::TryFrom<Stage> for TypedStage {
+    type Error = Error;
+
+    fn try_from(stage: Stage) -> Result<Self, Self::Error> {
+        let name = stage.name.as_str();
+        let arity = |n: usize| -> Result<(), Error> {
+            if stage.args.len() == n {
+                Ok(())
+            } else {
+                Err(invalid_stage(format!(
+                    "`{name}` expects {n} argument(s), got {}",
+                    stage.args.len()
+                )))
+            }
+        };
+        let no_options = || -> Result<(), Error> {
+            match stage.options.keys().min() {
+                None => Ok(()),
+                Some(key) => Err(invalid_stage(format!(
+                    "`{name}` has unknown option `{key}`"
+                ))),
+            }
+        };
+        let typed = match name {
+            names::ADD_FIELDS => {
+                arity(1)?;
+                no_options()?;
+                TypedStage::AddFields(expr_map(&stage.args[0])?)
+            }
+            names::AGGREGATE => {
+                arity(2)?;
+                no_options()?;
+                TypedStage::Aggregate {
+                    accumulators: expr_map(&stage.args[0])?,
+                    groups: expr_map(&stage.args[1])?,
+                }
+            }
+            names::COLLECTION => {
+                arity(1)?;
+                no_options()?;
+                TypedStage::Collection(stage.args[0].as_reference_value_as_string()?.clone())
+            }
+            names::COLLECTION_GROUP => {
+                arity(2)?;
+                no_options()?;
+                TypedStage::CollectionGroup {
+                    parent: stage.args[0].as_reference_value_as_string()?.clone(),
+                    collection_id: stage.args[1].as_string()?.clone(),
+                }
+            }
+            names::DATABASE => {
+                arity(0)?;
+                no_options()?;
+                TypedStage::Database
+            }
+            names::DISTINCT => {
+                arity(1)?;
+                no_options()?;
+                TypedStage::Distinct(expr_map(&stage.args[0])?)
+            }
+            names::DOCUMENTS => {
+                no_options()?;
+                TypedStage::Documents(
+                    stage
+                        .args
+                        .iter()
+                        .map(|arg| arg.as_reference_value_as_string().cloned())
+                        .collect::<Result<Vec<_>, Error>>()?,
+                )
+            }
+            names::FIND_NEAREST => {
+                arity(3)?;
+                let mut limit = None;
+                let mut distance_field = None;
+                for (key, value) in &stage.options {
+                    match key.as_str() {
+                        "limit" => limit = Some(value.as_integer()?),
+                        "distance_field" => {
+                            distance_field = Some(FieldReference(
+                                value.as_field_reference_value_as_string()?.clone(),
+                            ))
+                        }
+                        _ => {
+                            return Err(invalid_stage(format!(
+                                "`{name}` has unknown option `{key}`"
+                            )));
+                        }
+                    }
+                }
+                TypedStage::FindNearest(FindNearest {
+                    field: FieldReference(
+                        stage.args[0].as_field_reference_value_as_string()?.clone(),
+                    ),
+                    vector: vector(&stage.args[1])?,
+                    distance_measure: stage.args[2].as_string()?.parse()?,
+                    limit,
+                    distance_field,
+                })
+            }
+            names::LIMIT => {
+                arity(1)?;
+                no_options()?;
+                TypedStage::Limit(stage.args[0].as_integer()?)
+            }
+            names::OFFSET => {
+                arity(1)?;
+                no_options()?;
+                TypedStage::Offset(stage.args[0].as_integer()?)
+            }
+            names::REMOVE_FIELDS => {
+                no_options()?;
+                TypedStage::RemoveFields(
+                    stage
+                        .args
+                        .iter()
+                        .map(|arg| {
+                            arg.as_field_reference_value_as_string()
+                                .map(|s| FieldReference(s.clone()))
+                        })
+                        .collect::<Result<Vec<_>, Error>>()?,
+                )
+            }
+            names::SELECT => {
+                arity(1)?;
+                no_options()?;
+                TypedStage::Select(expr_map(&stage.args[0])?)
+            }
+            names::SORT => {
+                no_options()?;
+                TypedStage::Sort(
+                    stage
+                        .args
+                        .iter()
+                        .map(ordering)
+                        .collect::<Result<Vec<_>, Error>>()?,
+                )
+            }
+            names::WHERE => {
+                arity(1)?;
+                no_options()?;
+                TypedStage::Where(Expr::from(stage.args[0].clone()))
+            }
+            _ => TypedStage::Other(stage),
+        };
+        Ok(typed)
+    }
+}
+
+fn expr_map(value: &Value) -> Result<BTreeMap<String, Expr>, Error> {
+    Ok(value
+        .as_fields()?
+        .iter()
+        .map(|(k, v)| (k.clone(), Expr::from(v.clone())))
+        .collect())
+}
+
+fn invalid_stage(message: String) -> Error {
+    Error::from(ErrorCode::InvalidStage(message))
+}
+
+fn map_value(fields: BTreeMap<String, Expr>) -> Value {
+    Value::from_fields(fields.into_iter().map(|(k, v)| (k, Value::from(v))))
+}
+
+fn ordering(value: &Value) -> Result<Ordering, Error> {
+    let fields = value.as_fields()?;
+    let direction = match fields.get("direction").map(|v| v.as_string()).transpose()? {
+        Some(s) if s == "ascending" => Direction::Ascending,
+        Some(s) if s == "descending" => Direction::Descending,
+        Some(s) => return Err(invalid_stage(format!("unknown direction `{s}`"))),
+        None => return Err(invalid_stage("ordering has no direction".to_string())),
+    };
+    let expr = fields
+        .get("expression")
+        .cloned()
+        .map(Expr::from)
+        .ok_or_else(|| invalid_stage("ordering has no expression".to_string()))?;
+    Ok(Ordering { expr, direction })
+}
+
+fn selections<I>(items: I) -> BTreeMap<String, Expr>
+where
+    I: IntoIterator,
+    I::Item: Into<Selection>,
+{
+    items
+        .into_iter()
+        .map(|item| {
+            let Selection { alias, expr } = item.into();
+            (alias, expr)
+        })
+        .collect()
+}
+
+pub(crate) fn vector(value: &Value) -> Result<Vec<f64>, Error> {
+    let values = match value.value_type()? {
+        ValueType::ArrayValue(_) => value.as_values()?,
+        ValueType::MapValue(_) => {
+            let fields = value.as_fields()?;
+            match (fields.get("__type__"), fields.get("value")) {
+                (Some(t), Some(v)) if t.as_string()? == "__vector__" => v.as_values()?,
+                _ => return Err(invalid_stage("expected a vector".to_string())),
+            }
+        }
+        _ => return Err(invalid_stage("expected a vector".to_string())),
+    };
+    values
+        .iter()
+        .map(|v| match v.value_type()? {
+            ValueType::IntegerValue(i) => Ok(*i as f64),
+            _ => v.as_double(),
+        })
+        .collect()
+}
+
+pub(crate) fn vector_value(vector: Vec<f64>) -> Value {
+    Value::from_fields([
+        ("__type__", Value::from_string("__vector__".to_string())),
+        (
+            "value",
+            Value::from_values(vector.into_iter().map(Value::from_f64).collect()),
+        ),
+    ])
+}
@@ -0,0 +1,1070 @@
+//! Building [`StructuredQuery`]s and converting them to and from
+//! [`Pipeline`]s.
+//!
+//! [`Query`] builds a query with filter operands converted by
+//! [`to_value`](crate::to_value).
+//!
+//! [`to_pipeline`] converts a query to an equivalent pipeline and
+//! [`from_pipeline`] converts a pipeline back to a query. Anything which
+//! cannot be expressed on the other side is reported as an error instead of
+//! being dropped.
+//!
+//! The pipeline makes the implicit parts of a query explicit:
+//!
+//! - The `sort` stage has the effective ordering of the query: the explicit
+//!   `order_by`, then the inequality fields missing from it (by field path)
+//!   and `__name__`, in the direction of the last explicit order.
+//! - Ordering by a field only matches documents which have the field, so
+//!   an `exists(field)` condition is added for each sorted field which is
+//!   not already constrained by a top-level filter.
+//! - Cursors become comparisons of the sorted fields in the `where` stage.
+//!   Like the other comparisons, they only match values of the same type as
+//!   the cursor value.
+//!
+//! Converting back turns cursors into equivalent filters.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # fn main() -> anyhow::Result<()> {
+//! use serde_firestore_value::google::firestore::v1::{
+//!     StructuredQuery,
+//!     structured_query::{
+//!         CollectionSelector, FieldFilter, FieldReference, Filter, field_filter,
+//!         filter::FilterType,
+//!     },
+//! };
+//! use serde_firestore_value::structured_query::{from_pipeline, to_pipeline};
+//! use serde_firestore_value::to_value;
+//!
+//! let query = StructuredQuery {
+//!     from: vec![CollectionSelector {
+//!         collection_id: "posts".to_string(),
+//!         all_descendants: false,
+//!     }],
+//!     r#where: Some(Filter {
+//!         filter_type: Some(FilterType::FieldFilter(FieldFilter {
+//!             field: Some(FieldReference {
+//!                 field_path: "likes".to_string(),
+//!             }),
+//!             op: field_filter::Operator::GreaterThan as i32,
+//!             value: Some(to_value(&10)?),
+//!         })),
+//!     }),
+//!     limit: Some(20),
+//!     ..Default::default()
+//! };
+//! let pipeline = to_pipeline("projects/p/databases/(default)/documents/users/alice", &query)?;
+//! assert_eq!(pipeline.stages[0].name, "collection");
+//!
+//! let (parent, converted) = from_pipeline(&pipeline)?;
+//! assert_eq!(parent, "users/alice");
+//! assert_eq!(converted.limit, Some(20));
+//! #     Ok(())
+//! # }
+//! ```
+
+mod builder;
+
+use std::collections::BTreeSet;
+
+use crate::expr::{Expr, FunctionExpr, field, function, names};
+use crate::google::firestore::v1::{
+    Cursor, StructuredQuery, Value,
+    structured_query::{
+        self, CollectionSelector, CompositeFilter, FieldFilter, Filter, Order, Projection,
+        UnaryFilter, composite_filter, field_filter, filter::FilterType, find_nearest,
+        unary_filter,
+    },
+    value::ValueType,
+};
+use crate::stage::{self, Direction, DistanceMeasure, FindNearest, Ordering, TypedStage};
+use crate::value_ext::ValueExt as _;
+use crate::value_order::is_nan;
+use crate::{Error, Pipeline, Stage, error::ErrorCode};
+
+pub use self::builder::{Op, Query, UnaryOp, and, field_filter, or, unary_filter};
+
+/// Converts `query` to an equivalent pipeline.
+///
+/// `parent` is the parent resource name of the query (e.g.
+/// `projects/p/databases/d/documents` or
+/// `projects/p/databases/d/documents/users/alice`) or a document path
+/// relative to the database (e.g. `""` or `users/alice`).
+///
+/// `NOT_EQUAL`, `NOT_IN` and `IS_NOT_NAN` filters become `and(condition,
+/// not(is_null(field)))` and `LESS_THAN` and `LESS_THAN_OR_EQUAL` filters on
+/// a number become `or(condition, is_nan(field))`, since the filters and the
+/// functions disagree on null and NaN.
+///
+/// Fails if the query is invalid or uses a feature which pipelines cannot
+/// express.
+pub fn to_pipeline(parent: &str, query: &StructuredQuery) -> Result<Pipeline, Error> {
+    let parent = parent_path(parent);
+    let [selector] = query.from.as_slice() else {
+        return Err(unsupported(format!(
+            "`from` must have exactly one collection selector, got {}",
+            query.from.len()
+        )));
+    };
+    let mut pipeline = match (selector.all_descendants, parent) {
+        (false, "") => Pipeline::collection(selector.collection_id.as_str()),
+        (false, parent) => Pipeline::collection(format!("{parent}/{}", selector.collection_id)),
+        (true, parent) => Pipeline::collection_group_in(parent, selector.collection_id.as_str()),
+    };
+
+    let orders = order_by(query)?;
+    let mut conjuncts = match &query.r#where {
+        Some(filter) => split_and(filter_to_expr(filter)?),
+        None => vec![],
+    };
+    let implied = query
+        .r#where
+        .as_ref()
+        .map(existence_implied)
+        .unwrap_or_default();
+    for (path, _) in &orders {
+        if path != "__name__" && !implied.contains(path) {
+            conjuncts.push(field(path.as_str()).exists());
+        }
+    }
+    if let Some(cursor) = &query.start_at {
+        conjuncts.extend(cursor_to_expr(&orders, cursor, true)?);
+    }
+    if let Some(cursor) = &query.end_at {
+        conjuncts.extend(cursor_to_expr(&orders, cursor, false)?);
+    }
+    if let Some(condition) = join(names::AND, conjuncts) {
+        pipeline = pipeline.where_(condition);
+    }
+    pipeline = pipeline.sort(orders.into_iter().map(|(path, direction)| Ordering {
+        expr: field(path),
+        direction,
+    }));
+
+    if query.offset < 0 {
+        return Err(unsupported(format!("negative `offset` {}", query.offset)));
+    }
+    if query.offset > 0 {
+        pipeline = pipeline.offset(i64::from(query.offset));
+    }
+    match query.limit {
+        Some(limit) if limit < 0 => {
+            return Err(unsupported(format!("negative `limit` {limit}")));
+        }
+        Some(limit) => pipeline = pipeline.limit(i64::from(limit)),
+        None => {}
+    }
+
+    if let Some(find_nearest) = &query.find_nearest {
+        let (stage, threshold) = find_nearest_to_stage(find_nearest)?;
+        pipeline = pipeline.find_nearest(stage);
+        if let Some(threshold) = threshold {
+            pipeline = pipeline.where_(threshold);
+        }
+    }
+
+    if let Some(projection) = &query.select
+        && !projection.fields.is_empty()
+    {
+        pipeline = pipeline.select(
+            projection
+                .fields
+                .iter()
+                .map(|field| field.field_path.as_str()),
+        );
+    }
+    Ok(pipeline)
+}
+
+/// Converts `pipeline` back to a query. Returns the parent document path
+/// relative to the database (`""` for the root) and the query.
+///
+/// The pipeline must start with a `collection` or `collection_group` stage,
+/// followed by `where` and `sort` stages, then `offset` and `limit` stages,
+/// an optional `find_nearest` stage (optionally followed by a `where` stage
+/// on its distance field) and an optional `select` stage of plain fields.
+/// Conditions must compare a field with a constant using the functions a
+/// [`FieldFilter`] or an [`UnaryFilter`] supports, combined with `and` and
+/// `or`. `not_equal`, `not_equal_any` and `not(is_nan(field))` must be
+/// combined with `not(is_null(field))` with `and`, since the filters do not
+/// match null.
+///
+/// A sorted field must have an `exists(field)` condition or a top-level
+/// filter, since ordering a query by a field only matches documents which
+/// have it.
+pub fn from_pipeline(pipeline: &Pipeline) -> Result<(String, StructuredQuery), Error> {
+    let stages = pipeline.typed_stages()?;
+    let (source, stages) = stages
+        .split_first()
+        .ok_or_else(|| unsupported("pipeline has no stages".to_string()))?;
+    let mut query = StructuredQuery::default();
+    let parent = match source {
+        TypedStage::Collection(path) => {
+            let path = path.trim_matches('/');
+            let (parent, collection_id) = path.rsplit_once('/').unwrap_or(("", path));
+            query.from.push(CollectionSelector {
+                collection_id: collection_id.to_string(),
+                all_descendants: false,
+            });
+            parent.to_string()
+        }
+        TypedStage::CollectionGroup {
+            parent,
+            collection_id,
+        } => {
+            query.from.push(CollectionSelector {
+                collection_id: collection_id.clone(),
+                all_descendants: true,
+            });
+            parent.trim_matches('/').to_string()
+        }
+        stage => return Err(stage_error(0, stage, "is not a collection source")),
+    };
+
+    // Stages must appear in this order of phases.
+    const FILTER: u8 = 0;
+    const PAGE: u8 = 1;
+    const NEAREST: u8 = 2;
+    const THRESHOLD: u8 = 3;
+    const SELECT: u8 = 4;
+
+    let mut phase = FILTER;
+    let mut conjuncts = vec![];
+    let mut orderings: Option<&[Ordering]> = None;
+    let mut offset = 0_i64;
+    let mut limit = None::<i64>;
+    for (index, stage) in stages.iter().enumerate() {
+        let index = index + 1;
+        match stage {
+            TypedStage::Where(condition) if phase == FILTER => {
+                conjuncts.extend(split_and(condition.clone()).into_iter().map(|c| (index, c)));
+            }
+            TypedStage::Sort(sort) if phase == FILTER && orderings.is_none() => {
+                orderings = Some(sort);
+            }
+            TypedStage::Offset(n) if phase <= PAGE && *n >= 0 => {
+                phase = PAGE;
+                offset += n;
+                limit = limit.map(|limit| (limit - n).max(0));
+            }
+            TypedStage::Limit(n) if phase <= PAGE && *n >= 0 => {
+                phase = PAGE;
+                limit = Some(limit.map_or(*n, |limit| limit.min(*n)));
+            }
+            TypedStage::FindNearest(stage) if phase <= PAGE => {
+                phase = NEAREST;
+                query.find_nearest = Some(find_nearest_from_stage(index, stage)?);
+            }
+            TypedStage::Where(condition) if phase == NEAREST => {
+                phase = THRESHOLD;
+                let find_nearest = query.find_nearest.as_mut().expect("find_nearest");
+                find_nearest.distance_threshold =
+                    Some(threshold(find_nearest, condition).ok_or_else(|| {
+                        stage_error(
+                            index,
+                            stage,
+                            "is not a distance threshold of `find_nearest`",
+                        )
+                    })?);
+            }
+            TypedStage::Select(fields) if phase < SELECT => {
+                phase = SELECT;
+                let fields = fields
+                    .iter()
+                    .map(|(alias, expr)| match expr {
+                        Expr::Field(field) if field.0 == *alias => {
+                            Ok(structured_query::FieldReference {
+                                field_path: alias.clone(),
+                            })
+                        }
+                        _ => Err(stage_error(
+                            index,
+                            stage,
+                            "selects a computed or renamed field",
+                        )),
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+                query.select = Some(Projection { fields });
+            }
+            TypedStage::Sort(_) if phase == FILTER => {
+                return Err(stage_error(index, stage, "follows another `sort`"));
+            }
+            TypedStage::Offset(_) | TypedStage::Limit(_) if phase <= PAGE => {
+                return Err(stage_error(index, stage, "is negative"));
+            }
+            TypedStage::Where(_)
+            | TypedStage::Sort(_)
+            | TypedStage::Offset(_)
+            | TypedStage::Limit(_)
+            | TypedStage::FindNearest(_)
+            | TypedStage::Select(_) => {
+                return Err(stage_error(index, stage, "is out of order"));
+            }
+            _ => return Err(stage_error(index, stage, "has no equivalent")),
+        }
+    }
+
+    // The conditions which an ordering makes redundant.
+    let sorted = orderings
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|ordering| match &ordering.expr {
+            Expr::Field(field) => Some(field.0.as_str()),
+            _ => None,
+        })
+        .collect::<BTreeSet<_>>();
+    let mut filters = vec![];
+    let mut existing = BTreeSet::new();
+    for (index, condition) in conjuncts {
+        match exists_field(&condition) {
+            Some(path) if sorted.contains(path) => {
+                existing.insert(path.to_string());
+            }
+            _ => {
+                let filter = expr_to_filter(&condition)
+                    .map_err(|message| unsupported(format!("stages[{index}]: {message}")))?;
+                existing.extend(existence_implied(&filter));
+                filters.push(filter);
+            }
+        }
+    }
+    let mut filters = fold_not_null(filters).map_err(unsupported)?;
+    query.r#where = match filters.len() {
+        0 => None,
+        1 => filters.pop(),
+        _ => Some(composite(composite_filter::Operator::And, filters)),
+    };
+
+    if let Some(orderings) = orderings {
+        query.order_by = orderings_to_orders(orderings, &existing)?;
+    }
+    if offset > 0 {
+        query.offset = i32::try_from(offset)
+            .map_err(|_| unsupported(format!("`offset` {offset} out of range")))?;
+    }
+    query.limit = limit
+        .map(|limit| {
+            i32::try_from(limit).map_err(|_| unsupported(format!("`limit` {limit} out of range")))
+        })
+        .transpose()?;
+    Ok((parent, query))
+}
+
+/// Returns the query which the client SDKs run for `limit_to_last`.
+///
+/// The effective `order_by` (see [`to_pipeline`]) is reversed, the start
+/// and end cursors are swapped and `limit` is set. The returned query finds
+/// the last `limit` results of `query`, in reverse order.
+pub fn limit_to_last(query: &StructuredQuery, limit: i32) -> Result<StructuredQuery, Error> {
+    let flip = |cursor: &Cursor| Cursor {
+        values: cursor.values.clone(),
+        before: !cursor.before,
+    };
+    Ok(StructuredQuery {
+        order_by: order_by(query)?
+            .into_iter()
+            .map(|(path, direction)| {
+                order(
+                    path,
+                    match direction {
+                        Direction::Ascending => Direction::Descending,
+                        Direction::Descending => Direction::Ascending,
+                    },
+                )
+            })
+            .collect(),
+        start_at: query.end_at.as_ref().map(flip),
+        end_at: query.start_at.as_ref().map(flip),
+        limit: Some(limit),
+        ..query.clone()
+    })
+}
+
+/// Returns the effective `order_by` of `query` as field paths and
+/// directions.
+///
+/// Inequality fields missing from the explicit `order_by` are appended in
+/// field path order, then `__name__`, in the direction of the last explicit
+/// order.
+pub(crate) fn order_by(query: &StructuredQuery) -> Result<Vec<(String, Direction)>, Error> {
+    let mut orders = query
+        .order_by
+        .iter()
+        .map(|order| {
+            let path = field_path(order.field.as_ref(), "order_by")?;
+            let direction = match structured_query::Direction::try_from(order.direction) {
+                Ok(structured_query::Direction::Unspecified)
+                | Ok(structured_query::Direction::Ascending) => Direction::Ascending,
+                Ok(structured_query::Direction::Descending) => Direction::Descending,
+                Err(_) => {
+                    return Err(unsupported(format!(
+                        "unknown direction {}",
+                        order.direction
+                    )));
+                }
+            };
+            Ok((path.to_string(), direction))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    let direction = orders
+        .last()
+        .map_or(Direction::Ascending, |(_, direction)| *direction);
+    let mut inequalities = BTreeSet::new();
+    if let Some(filter) = &query.r#where {
+        inequality_fields(filter, &mut inequalities);
+    }
+    for path in inequalities {
+        if !orders.iter().any(|(p, _)| *p == path) {
+            orders.push((path, direction));
+        }
+    }
+    if !orders.iter().any(|(p, _)| p == "__name__") {
+        orders.push(("__name__".to_string(), direction));
+    }
+    Ok(orders)
+}
+
+/// Returns the document path of `parent` relative to the database.
+pub(crate) fn parent_path(parent: &str) -> &str {
+    if parent.starts_with("projects/") {
+        match parent.find("/documents") {
+            Some(index) => parent[index + "/documents".len()..].trim_matches('/'),
+            None => "",
+        }
+    } else {
+        parent.trim_matches('/')
+    }
+}
+
+fn unsupported(message: String) -> Error {
+    Error::from(ErrorCode::UnsupportedConversion(message))
+}
+
+fn stage_error(index: usize, stage: &TypedStage, reason: &str) -> Error {
+    let name = Stage::from(stage.clone()).name;
+    unsupported(format!("stages[{index}]: `{name}` stage {reason}"))
+}
+
+fn field_path<'a>(
+    field: Option<&'a structured_query::FieldReference>,
+    context: &str,
+) -> Result<&'a str, Error> {
+    field
+        .map(|field| field.field_path.as_str())
+        .ok_or_else(|| unsupported(format!("`{context}` has no field")))
+}
+
+fn filter_to_expr(filter: &Filter) -> Result<Expr, Error> {
+    match &filter.filter_type {
+        Some(FilterType::CompositeFilter(CompositeFilter { op, filters })) => {
+            let name = match composite_filter::Operator::try_from(*op) {
+                Ok(composite_filter::Operator::And) => names::AND,
+                Ok(composite_filter::Operator::Or) => names::OR,
+                _ => return Err(unsupported(format!("unknown composite operator {op}"))),
+            };
+            let args = filters
+                .iter()
+                .map(filter_to_expr)
+                .collect::<Result<Vec<_>, Error>>()?;
+            join(name, args).ok_or_else(|| unsupported("empty composite filter".to_string()))
+        }
+        Some(FilterType::FieldFilter(FieldFilter { field, op, value })) => {
+            let path = field_path(field.as_ref(), "field_filter")?;
+            let field = self::field(path);
+            let value = value
+                .as_ref()
+                .ok_or_else(|| unsupported("`field_filter` has no value".to_string()))?;
+            use field_filter::Operator;
+            let operator = Operator::try_from(*op);
+            let range = matches!(
+                operator,
+                Ok(Operator::LessThan
+                    | Operator::LessThanOrEqual
+                    | Operator::GreaterThan
+                    | Operator::GreaterThanOrEqual)
+            );
+            if range && is_nan(value) {
+                return Err(unsupported(format!("`{path}`: range filter on NaN")));
+            }
+            let number = matches!(
+                value.value_type,
+                Some(ValueType::IntegerValue(_) | ValueType::DoubleValue(_))
+            );
+            let value = Expr::from(value.clone());
+            Ok(match operator {
+                Ok(Operator::LessThan) => or_nan(path, number, field.lt(value)),
+                Ok(Operator::LessThanOrEqual) => or_nan(path, number, field.lte(value)),
+                Ok(Operator::GreaterThan) => field.gt(value),
+                Ok(Operator::GreaterThanOrEqual) => field.gte(value),
+                Ok(Operator::Equal) => field.eq(value),
+                Ok(Operator::NotEqual) => not_null(path, field.neq(value)),
+                Ok(Operator::ArrayContains) => field.array_contains(value),
+                Ok(Operator::In) => field.equal_any(value),
+                Ok(Operator::ArrayContainsAny) => field.array_contains_any(value),
+                Ok(Operator::NotIn) => not_null(path, field.not_equal_any(value)),
+                Ok(Operator::Unspecified) | Err(_) => {
+                    return Err(unsupported(format!("unknown field filter operator {op}")));
+                }
+            })
+        }
+        Some(FilterType::UnaryFilter(UnaryFilter { op, operand_type })) => {
+            let Some(unary_filter::OperandType::Field(field)) = operand_type else {
+                return Err(unsupported("`unary_filter` has no field".to_string()));
+            };
+            let path = field.field_path.as_str();
+            let field = self::field(path);
+            use unary_filter::Operator;
+            Ok(match Operator::try_from(*op) {
+                Ok(Operator::IsNan) => field.is_nan(),
+                Ok(Operator::IsNull) => field.is_null(),
+                Ok(Operator::IsNotNan) => not_null(path, field.is_nan().not()),
+                Ok(Operator::IsNotNull) => field.is_null().not(),
+                Ok(Operator::Unspecified) | Err(_) => {
+                    return Err(unsupported(format!("unknown unary filter operator {op}")));
+                }
+            })
+        }
+        None => Err(unsupported("filter has no type".to_string())),
+    }
+}
+
+fn expr_to_filter(expr: &Expr) -> Result<Filter, String> {
+    let Expr::Function(FunctionExpr {
+        name,
+        args,
+        options,
+    }) = expr
+    else {
+        return Err("condition is not a function".to_string());
+    };
+    if !options.is_empty() {
+        return Err(format!("`{name}` has options"));
+    }
+    let field_filter = |op: field_filter::Operator| -> Result<Filter, String> {
+        match args.as_slice() {
+            [Expr::Field(field), Expr::Constant(value)] => Ok(Filter {
+                filter_type: Some(FilterType::FieldFilter(FieldFilter {
+                    field: Some(structured_query::FieldReference {
+                        field_path: field.0.clone(),
+                    }),
+                    op: op as i32,
+                    value: Some(value.clone()),
+                })),
+            }),
+            _ => Err(format!("`{name}` must compare a field with a constant")),
+        }
+    };
+    let unary_filter = |op: unary_filter::Operator, args: &[Expr]| -> Result<Filter, String> {
+        match args {
+            [Expr::Field(field)] => Ok(Filter {
+                filter_type: Some(FilterType::UnaryFilter(UnaryFilter {
+                    op: op as i32,
+                    operand_type: Some(unary_filter::OperandType::Field(
+                        structured_query::FieldReference {
+                            field_path: field.0.clone(),
+                        },
+                    )),
+                })),
+            }),
+            _ => Err(format!("`{name}` must take a field")),
+        }
+    };
+    match name.as_str() {
+        names::AND => {
+            let filters = args
+                .iter()
+                .cloned()
+                .flat_map(split_and)
+                .map(|arg| expr_to_filter(&arg))
+                .collect::<Result<Vec<_>, String>>()?;
+            if filters.is_empty() {
+                return Err(format!("`{name}` has no arguments"));
+            }
+            Ok(single_or_composite(
+                composite_filter::Operator::And,
+                fold_not_null(filters)?,
+            ))
+        }
+        names::OR => {
+            let filters = args
+                .iter()
+                .map(|arg| {
+                    let filter = expr_to_filter(arg)?;
+                    match arg {
+                        // An `and` folds its own conjuncts.
+                        Expr::Function(FunctionExpr { name, .. }) if name == names::AND => {
+                            Ok(vec![filter])
+                        }
+                        _ => fold_not_null(vec![filter]),
+                    }
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+            if filters.is_empty() {
+                return Err(format!("`{name}` has no arguments"));
+            }
+            Ok(single_or_composite(
+                composite_filter::Operator::Or,
+                fold_is_nan(filters.into_iter().flatten().collect()),
+            ))
+        }
+        names::LESS_THAN => field_filter(field_filter::Operator::LessThan),
+        names::LESS_THAN_OR_EQUAL => field_filter(field_filter::Operator::LessThanOrEqual),
+        names::GREATER_THAN => field_filter(field_filter::Operator::GreaterThan),
+        names::GREATER_THAN_OR_EQUAL => field_filter(field_filter::Operator::GreaterThanOrEqual),
+        names::EQUAL => field_filter(field_filter::Operator::Equal),
+        names::NOT_EQUAL => field_filter(field_filter::Operator::NotEqual),
+        names::ARRAY_CONTAINS => field_filter(field_filter::Operator::ArrayContains),
+        names::EQUAL_ANY => field_filter(field_filter::Operator::In),
+        names::ARRAY_CONTAINS_ANY => field_filter(field_filter::Operator::ArrayContainsAny),
+        names::NOT_EQUAL_ANY => field_filter(field_filter::Operator::NotIn),
+        names::IS_NAN => unary_filter(unary_filter::Operator::IsNan, args),
+        names::IS_NULL => unary_filter(unary_filter::Operator::IsNull, args),
+        names::NOT => match args.as_slice() {
+            [Expr::Function(inner)] if inner.options.is_empty() => match inner.name.as_str() {
+                names::IS_NAN => unary_filter(unary_filter::Operator::IsNotNan, &inner.args),
+                names::IS_NULL => unary_filter(unary_filter::Operator::IsNotNull, &inner.args),
+                _ => Err(format!("`{name}` of `{}` has no equivalent", inner.name)),
+            },
+            _ => Err(format!("`{name}` has no equivalent")),
+        },
+        names::EXISTS => Err(format!("`{name}` is only supported on a sorted field")),
+        _ => Err(format!("`{name}` has no equivalent")),
+    }
+}
+
+fn composite(op: composite_filter::Operator, filters: Vec<Filter>) -> Filter {
+    Filter {
+        filter_type: Some(FilterType::CompositeFilter(CompositeFilter {
+            op: op as i32,
+            filters,
+        })),
+    }
+}
+
+/// Returns the only filter of `filters`, or `filters` combined with `op`.
+fn single_or_composite(op: composite_filter::Operator, mut filters: Vec<Filter>) -> Filter {
+    match filters.len() {
+        1 => filters.pop().expect("one filter"),
+        _ => composite(op, filters),
+    }
+}
+
+/// Returns `args` combined with `name` (`and` or `or`), or `None` if
+/// `args` is empty.
+fn join(name: &str, mut args: Vec<Expr>) -> Option<Expr> {
+    match args.len() {
+        0 => None,
+        1 => args.pop(),
+        _ => Some(function(name, args)),
+    }
+}
+
+/// Splits an `and` expression into its arguments.
+fn split_and(expr: Expr) -> Vec<Expr> {
+    match expr {
+        Expr::Function(FunctionExpr {
+            name,
+            args,
+            options,
+        }) if name == names::AND && options.is_empty() => {
+            args.into_iter().flat_map(split_and).collect()
+        }
+        expr => vec![expr],
+    }
+}
+
+/// Returns `condition` and `not(is_null(path))`. `NOT_EQUAL`, `NOT_IN` and
+/// `IS_NOT_NAN` do not match null, but their pipeline functions do.
+fn not_null(path: &str, condition: Expr) -> Expr {
+    condition.and(field(path).is_null().not())
+}
+
+/// Returns `condition` or `is_nan(path)` if the value is a `number`.
+/// `LESS_THAN` and `LESS_THAN_OR_EQUAL` match NaN, which sorts before any
+/// number, but their pipeline functions do not.
+fn or_nan(path: &str, number: bool, condition: Expr) -> Expr {
+    if number {
+        condition.or(field(path).is_nan())
+    } else {
+        condition
+    }
+}
+
+/// Folds the `IS_NAN` filters which [`or_nan`] adds back into the
+/// `LESS_THAN` and `LESS_THAN_OR_EQUAL` filters among `filters`, the
+/// disjuncts of an `or`.
+fn fold_is_nan(mut filters: Vec<Filter>) -> Vec<Filter> {
+    let guarded = filters
+        .iter()
+        .filter_map(nan_matching_field)
+        .map(str::to_string)
+        .collect::<BTreeSet<_>>();
+    filters.retain(|filter| match &filter.filter_type {
+        Some(FilterType::UnaryFilter(UnaryFilter {
+            op,
+            operand_type: Some(unary_filter::OperandType::Field(field)),
+        })) if *op == unary_filter::Operator::IsNan as i32 => !guarded.contains(&field.field_path),
+        _ => true,
+    });
+    filters
+}
+
+/// Returns the field path of a `LESS_THAN` or `LESS_THAN_OR_EQUAL` filter
+/// on a number.
+fn nan_matching_field(filter: &Filter) -> Option<&str> {
+    match &filter.filter_type {
+        Some(FilterType::FieldFilter(FieldFilter {
+            field: Some(field),
+            op,
+            value:
+                Some(Value {
+                    value_type: Some(ValueType::IntegerValue(_) | ValueType::DoubleValue(_)),
+                }),
+        })) if *op == field_filter::Operator::LessThan as i32
+            || *op == field_filter::Operator::LessThanOrEqual as i32 =>
+        {
+            Some(field.field_path.as_str())
+        }
+        _ => None,
+    }
+}
+
+/// Folds the `IS_NOT_NULL` filters which [`not_null`] adds back into the
+/// `NOT_EQUAL`, `NOT_IN` and `IS_NOT_NAN` filters among `filters`, the
+/// conjuncts of an `and`. Those filters have no equivalent without it.
+fn fold_not_null(mut filters: Vec<Filter>) -> Result<Vec<Filter>, String> {
+    let guarded = filters
+        .iter()
+        .filter_map(null_excluding_field)
+        .map(str::to_string)
+        .collect::<BTreeSet<_>>();
+    let guards = filters
+        .iter()
+        .filter_map(is_not_null_field)
+        .map(str::to_string)
+        .collect::<BTreeSet<_>>();
+    if let Some(path) = guarded.difference(&guards).next() {
+        return Err(format!(
+            "the condition on `{path}` must be combined with `not(is_null({path}))`"
+        ));
+    }
+    filters.retain(|filter| is_not_null_field(filter).is_none_or(|path| !guarded.contains(path)));
+    Ok(filters)
+}
+
+/// Returns the field path of a `NOT_EQUAL`, `NOT_IN` or `IS_NOT_NAN` filter.
+fn null_excluding_field(filter: &Filter) -> Option<&str> {
+    match &filter.filter_type {
+        Some(FilterType::FieldFilter(FieldFilter {
+            field: Some(field),
+            op,
+            ..
+        })) if *op == field_filter::Operator::NotEqual as i32
+            || *op == field_filter::Operator::NotIn as i32 =>
+        {
+            Some(field.field_path.as_str())
+        }
+        Some(FilterType::UnaryFilter(UnaryFilter {
+            op,
+            operand_type: Some(unary_filter::OperandType::Field(field)),
+        })) if *op == unary_filter::Operator::IsNotNan as i32 => Some(field.field_path.as_str()),
+        _ => None,
+    }
+}
+
+/// Returns the field path of an `IS_NOT_NULL` filter.
+fn is_not_null_field(filter: &Filter) -> Option<&str> {
+    match &filter.filter_type {
+        Some(FilterType::UnaryFilter(UnaryFilter {
+            op,
+            operand_type: Some(unary_filter::OperandType::Field(field)),
+        })) if *op == unary_filter::Operator::IsNotNull as i32 => Some(field.field_path.as_str()),
+        _ => None,
+    }
+}
+
+/// Returns the field path of an `exists(field)` expression.
+fn exists_field(expr: &Expr) -> Option<&str> {
+    match expr {
+        Expr::Function(FunctionExpr { name, args, .. }) if name == names::EXISTS => {
+            match args.as_slice() {
+                [Expr::Field(field)] => Some(field.0.as_str()),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns the fields which the top-level conjuncts of `filter` only match
+/// when present.
+fn existence_implied(filter: &Filter) -> BTreeSet<String> {
+    let mut fields = BTreeSet::new();
+    match &filter.filter_type {
+        Some(FilterType::CompositeFilter(CompositeFilter { op, filters }))
+            if *op == composite_filter::Operator::And as i32 =>
+        {
+            for filter in filters {
+                fields.extend(existence_implied(filter));
+            }
+        }
+        Some(FilterType::FieldFilter(FieldFilter {
+            field: Some(field),
+            op,
+            ..
+        })) if *op != field_filter::Operator::NotEqual as i32
+            && *op != field_filter::Operator::NotIn as i32 =>
+        {
+            fields.insert(field.field_path.clone());
+        }
+        Some(FilterType::UnaryFilter(UnaryFilter {
+            op,
+            operand_type: Some(unary_filter::OperandType::Field(field)),
+        })) if *op == unary_filter::Operator::IsNan as i32
+            || *op == unary_filter::Operator::IsNull as i32 =>
+        {
+            fields.insert(field.field_path.clone());
+        }
+        _ => {}
+    }
+    fields
+}
+
+/// Collects the fields of the inequality filters in `filter`.
+fn inequality_fields(filter: &Filter, fields: &mut BTreeSet<String>) {
+    match &filter.filter_type {
+        Some(FilterType::CompositeFilter(CompositeFilter { filters, .. })) => {
+            for filter in filters {
+                inequality_fields(filter, fields);
+            }
+        }
+        Some(FilterType::FieldFilter(FieldFilter {
+            field: Some(field),
+            op,
+            ..
+        })) => {
+            use field_filter::Operator;
+            if matches!(
+                Operator::try_from(*op),
+                Ok(Operator::LessThan
+                    | Operator::LessThanOrEqual
+                    | Operator::GreaterThan
+                    | Operator::GreaterThanOrEqual
+                    | Operator::NotEqual
+                    | Operator::NotIn)
+            ) {
+                fields.insert(field.field_path.clone());
+            }
+        }
+        Some(FilterType::UnaryFilter(UnaryFilter {
+            op,
+            operand_type: Some(unary_filter::OperandType::Field(field)),
+        })) => {
+            use unary_filter::Operator;
+            if matches!(
+                Operator::try_from(*op),
+                Ok(Operator::IsNotNan | Operator::IsNotNull)
+            ) {
+                fields.insert(field.field_path.clone());
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Converts a cursor to a condition on the sorted fields: for values
+/// `(v1, v2)` of a start cursor, `a > v1 OR (a == v1 AND b > v2)`.
+fn cursor_to_expr(
+    orders: &[(String, Direction)],
+    cursor: &Cursor,
+    start: bool,
+) -> Result<Option<Expr>, Error> {
+    if cursor.values.len() > orders.len() {
+        return Err(unsupported(format!(
+            "cursor has {} values but the query has {} orders",
+            cursor.values.len(),
+            orders.len()
+        )));
+    }
+    let mut terms = vec![];
+    for (i, value) in cursor.values.iter().enumerate() {
+        let mut conjuncts = orders
+            .iter()
+            .zip(&cursor.values)
+            .take(i)
+            .map(|((path, _), value)| field(path.as_str()).eq(Expr::from(value.clone())))
+            .collect::<Vec<_>>();
+        let (path, direction) = &orders[i];
+        let inclusive = i + 1 == cursor.values.len() && cursor.before == start;
+        let greater = start == (*direction == Direction::Ascending);
+        let (field, value) = (field(path.as_str()), Expr::from(value.clone()));
+        conjuncts.push(match (greater, inclusive) {
+            (true, false) => field.gt(value),
+            (true, true) => field.gte(value),
+            (false, false) => field.lt(value),
+            (false, true) => field.lte(value),
+        });
+        terms.extend(join(names::AND, conjuncts));
+    }
+    Ok(join(names::OR, terms))
+}
+
+fn orderings_to_orders(
+    orderings: &[Ordering],
+    existing: &BTreeSet<String>,
+) -> Result<Vec<Order>, Error> {
+    let mut orders = orderings
+        .iter()
+        .map(|ordering| {
+            let Expr::Field(field) = &ordering.expr else {
+                return Err(unsupported("sort by a computed value".to_string()));
+            };
+            if field.0 != "__name__" && !existing.contains(&field.0) {
+                return Err(unsupported(format!(
+                    "sort by `{}` also matches documents without it (add `exists`)",
+                    field.0
+                )));
+            }
+            Ok((field.0.clone(), ordering.direction))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    // `__name__` in the implicit direction is appended by default.
+    if let [.., (path, direction)] = orders.as_slice() {
+        let implicit = match orders.len() {
+            1 => Direction::Ascending,
+            n => orders[n - 2].1,
+        };
+        if path == "__name__" && *direction == implicit {
+            orders.pop();
+        }
+    }
+    Ok(orders
+        .into_iter()
+        .map(|(field_path, direction)| order(field_path, direction))
+        .collect())
+}
+
+fn order(field_path: String, direction: Direction) -> Order {
+    Order {
+        field: Some(structured_query::FieldReference { field_path }),
+        direction: match direction {
+            Direction::Ascending => structured_query::Direction::Ascending,
+            Direction::Descending => structured_query::Direction::Descending,
+        } as i32,
+    }
+}
+
+fn find_nearest_to_stage(
+    find_nearest: &structured_query::FindNearest,
+) -> Result<(FindNearest, Option<Expr>), Error> {
+    let vector_field = field_path(find_nearest.vector_field.as_ref(), "find_nearest")?;
+    let vector = find_nearest
+        .query_vector
+        .as_ref()
+        .ok_or_else(|| unsupported("`find_nearest` has no query vector".to_string()))
+        .and_then(|value| {
+            stage::vector(value)
+                .map_err(|_| unsupported("`find_nearest` query is not a vector".to_string()))
+        })?;
+    let distance_measure =
+        match find_nearest::DistanceMeasure::try_from(find_nearest.distance_measure) {
+            Ok(find_nearest::DistanceMeasure::Cosine) => DistanceMeasure::Cosine,
+            Ok(find_nearest::DistanceMeasure::DotProduct) => DistanceMeasure::DotProduct,
+            Ok(find_nearest::DistanceMeasure::Euclidean) => DistanceMeasure::Euclidean,
+            _ => {
+                return Err(unsupported(format!(
+                    "unknown distance measure {}",
+                    find_nearest.distance_measure
+                )));
+            }
+        };
+    let distance_field = (!find_nearest.distance_result_field.is_empty())
+        .then(|| crate::FieldReference(find_nearest.distance_result_field.clone()));
+    let threshold = match (find_nearest.distance_threshold, &distance_field) {
+        (None, _) => None,
+        (Some(threshold), Some(distance_field)) => {
+            let field = Expr::Field(distance_field.clone());
+            let threshold =
+                Expr::Constant(crate::google::firestore::v1::Value::from_f64(threshold));
+            Some(match distance_measure {
+                DistanceMeasure::DotProduct => field.gte(threshold),
+                DistanceMeasure::Cosine | DistanceMeasure::Euclidean => field.lte(threshold),
+            })
+        }
+        (Some(_), None) => {
+            return Err(unsupported(
+                "`find_nearest` distance threshold without a distance result field".to_string(),
+            ));
+        }
+    };
+    Ok((
+        FindNearest {
+            field: crate::FieldReference(vector_field.to_string()),
+            vector,
+            distance_measure,
+            limit: find_nearest.limit.map(i64::from),
+            distance_field,
+        },
+        threshold,
+    ))
+}
+
+fn find_nearest_from_stage(
+    index: usize,
+    stage: &FindNearest,
+) -> Result<structured_query::FindNearest, Error> {
+    let limit = stage
+        .limit
+        .map(|limit| {
+            i32::try_from(limit).map_err(|_| {
+                unsupported(format!(
+                    "stages[{index}]: `find_nearest` limit {limit} out of range"
+                ))
+            })
+        })
+        .transpose()?;
+    Ok(structured_query::FindNearest {
+        vector_field: Some(structured_query::FieldReference {
+            field_path: stage.field.0.clone(),
+        }),
+        query_vector: Some(stage::vector_value(stage.vector.clone())),
+        distance_measure: match stage.distance_measure {
+            DistanceMeasure::Cosine => find_nearest::DistanceMeasure::Cosine,
+            DistanceMeasure::DotProduct => find_nearest::DistanceMeasure::DotProduct,
+            DistanceMeasure::Euclidean => find_nearest::DistanceMeasure::Euclidean,
+        } as i32,
+        limit,
+        distance_result_field: stage
+            .distance_field
+            .as_ref()
+            .map(|field| field.0.clone())
+            .unwrap_or_default(),
+        distance_threshold: None,
+    })
+}
+
+/// Returns the distance threshold of a `where` stage following
+/// `find_nearest`.
+fn threshold(find_nearest: &structured_query::FindNearest, condition: &Expr) -> Option<f64> {
+    let Expr::Function(FunctionExpr { name, args, .. }) = condition else {
+        return None;
+    };
+    let expected =
+        if find_nearest.distance_measure == find_nearest::DistanceMeasure::DotProduct as i32 {
+            names::GREATER_THAN_OR_EQUAL
+        } else {
+            names::LESS_THAN_OR_EQUAL
+        };
+    match args.as_slice() {
+        [Expr::Field(field), Expr::Constant(value)]
+            if *name == expected && field.0 == find_nearest.distance_result_field =>
+        {
+            value
+                .as_double()
+                .or_else(|_| value.as_integer().map(|i| i as f64))
+                .ok()
+        }
+        _ => None,
+    }
+}
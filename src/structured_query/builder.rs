@@ -0,0 +1,354 @@
+use crate::google::firestore::v1::{
+    Cursor, StructuredQuery, Value,
+    structured_query::{
+        self, CollectionSelector, CompositeFilter, FieldFilter, Filter, Order, Projection,
+        UnaryFilter, composite_filter, field_filter, filter::FilterType, unary_filter,
+    },
+    value::ValueType,
+};
+use crate::stage::Direction;
+use crate::value_ext::ValueExt as _;
+use crate::value_order::is_nan;
+use crate::{Error, error::ErrorCode};
+
+/// The operator of a field filter.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Op {
+    /// `LESS_THAN`
+    Lt,
+    /// `LESS_THAN_OR_EQUAL`
+    Lte,
+    /// `GREATER_THAN`
+    Gt,
+    /// `GREATER_THAN_OR_EQUAL`
+    Gte,
+    /// `EQUAL`
+    Eq,
+    /// `NOT_EQUAL`
+    Neq,
+    /// `ARRAY_CONTAINS`
+    ArrayContains,
+    /// `IN`. The operand must be an array.
+    In,
+    /// `ARRAY_CONTAINS_ANY`. The operand must be an array.
+    ArrayContainsAny,
+    /// `NOT_IN`. The operand must be an array.
+    NotIn,
+}
+
+impl From<Op> for field_filter::Operator {
+    fn from(op: Op) -> Self {
+        match op {
+            Op::Lt => field_filter::Operator::LessThan,
+            Op::Lte => field_filter::Operator::LessThanOrEqual,
+            Op::Gt => field_filter::Operator::GreaterThan,
+            Op::Gte => field_filter::Operator::GreaterThanOrEqual,
+            Op::Eq => field_filter::Operator::Equal,
+            Op::Neq => field_filter::Operator::NotEqual,
+            Op::ArrayContains => field_filter::Operator::ArrayContains,
+            Op::In => field_filter::Operator::In,
+            Op::ArrayContainsAny => field_filter::Operator::ArrayContainsAny,
+            Op::NotIn => field_filter::Operator::NotIn,
+        }
+    }
+}
+
+/// The operator of a unary filter.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UnaryOp {
+    /// `IS_NAN`
+    IsNan,
+    /// `IS_NULL`
+    IsNull,
+    /// `IS_NOT_NAN`
+    IsNotNan,
+    /// `IS_NOT_NULL`
+    IsNotNull,
+}
+
+impl From<UnaryOp> for unary_filter::Operator {
+    fn from(op: UnaryOp) -> Self {
+        match op {
+            UnaryOp::IsNan => unary_filter::Operator::IsNan,
+            UnaryOp::IsNull => unary_filter::Operator::IsNull,
+            UnaryOp::IsNotNan => unary_filter::Operator::IsNotNan,
+            UnaryOp::IsNotNull => unary_filter::Operator::IsNotNull,
+        }
+    }
+}
+
+/// Creates a field filter. `value` is converted with
+/// [`to_value`](crate::to_value).
+///
+/// Like the client SDKs, `Eq` and `Neq` with null or NaN become the
+/// `IS_NULL`, `IS_NOT_NULL`, `IS_NAN` and `IS_NOT_NAN` unary filters.
+///
+/// Fails if `value` cannot be serialized, if null or NaN is used with
+/// another operator, or if `In`, `NotIn` or `ArrayContainsAny` is used with
+/// a value which is not a non-empty array.
+pub fn field_filter<S, T>(path: S, op: Op, value: &T) -> Result<Filter, Error>
+where
+    S: Into<String>,
+    T: serde::Serialize,
+{
+    let path = path.into();
+    let value = crate::to_value(value)?;
+    let is_null = matches!(value.value_type, Some(ValueType::NullValue(_)));
+    if is_null || is_nan(&value) {
+        return match (op, is_null) {
+            (Op::Eq, true) => Ok(unary_filter(path, UnaryOp::IsNull)),
+            (Op::Neq, true) => Ok(unary_filter(path, UnaryOp::IsNotNull)),
+            (Op::Eq, false) => Ok(unary_filter(path, UnaryOp::IsNan)),
+            (Op::Neq, false) => Ok(unary_filter(path, UnaryOp::IsNotNan)),
+            _ => Err(invalid_query(format!(
+                "`{path}`: null and NaN only support `Eq` and `Neq`"
+            ))),
+        };
+    }
+    if matches!(op, Op::In | Op::NotIn | Op::ArrayContainsAny)
+        && value.as_values().map_or(true, Vec::is_empty)
+    {
+        return Err(invalid_query(format!(
+            "`{path}`: `{op:?}` requires a non-empty array"
+        )));
+    }
+    Ok(Filter {
+        filter_type: Some(FilterType::FieldFilter(FieldFilter {
+            field: Some(field_reference(path)),
+            op: field_filter::Operator::from(op) as i32,
+            value: Some(value),
+        })),
+    })
+}
+
+/// Creates a unary filter.
+pub fn unary_filter<S: Into<String>>(path: S, op: UnaryOp) -> Filter {
+    Filter {
+        filter_type: Some(FilterType::UnaryFilter(UnaryFilter {
+            op: unary_filter::Operator::from(op) as i32,
+            operand_type: Some(unary_filter::OperandType::Field(field_reference(path))),
+        })),
+    }
+}
+
+/// Creates a composite filter which matches documents matching all of
+/// `filters`.
+pub fn and<I: IntoIterator<Item = Filter>>(filters: I) -> Filter {
+    composite(composite_filter::Operator::And, filters)
+}
+
+/// Creates a composite filter which matches documents matching any of
+/// `filters`.
+pub fn or<I: IntoIterator<Item = Filter>>(filters: I) -> Filter {
+    composite(composite_filter::Operator::Or, filters)
+}
+
+/// A builder of [`StructuredQuery`].
+///
+/// Errors (e.g. a filter operand which cannot be serialized) are reported
+/// by [`Query::build`].
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::stage::Direction;
+/// use serde_firestore_value::structured_query::{Op, Query, UnaryOp, field_filter, or};
+/// use serde_firestore_value::Reference;
+///
+/// let author = Reference("projects/p/databases/(default)/documents/users/alice".to_string());
+/// let query = Query::collection("posts")
+///     .where_field("author", Op::Eq, &author)
+///     .where_(or([
+///         field_filter("tags", Op::ArrayContainsAny, &["rust", "serde"])?,
+///         field_filter("likes", Op::Gte, &100)?,
+///     ]))
+///     .where_unary("deleted_at", UnaryOp::IsNull)
+///     .order_by("created", Direction::Descending)
+///     .limit(20)
+///     .build()?;
+/// assert_eq!(query.from[0].collection_id, "posts");
+/// assert_eq!(query.limit, Some(20));
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Query {
+    query: StructuredQuery,
+    error: Option<Error>,
+}
+
+impl Query {
+    /// Creates a query of the collection `collection_id` in the parent.
+    pub fn collection<S: Into<String>>(collection_id: S) -> Self {
+        Self::from_selector(collection_id.into(), false)
+    }
+
+    /// Creates a query of all collections `collection_id` below the parent.
+    pub fn collection_group<S: Into<String>>(collection_id: S) -> Self {
+        Self::from_selector(collection_id.into(), true)
+    }
+
+    /// Adds `filter`. Filters are combined with `AND`.
+    pub fn where_(mut self, filter: Filter) -> Self {
+        self.query.r#where = Some(match self.query.r#where.take() {
+            None => filter,
+            Some(Filter {
+                filter_type: Some(FilterType::CompositeFilter(mut composite)),
+            }) if composite.op == composite_filter::Operator::And as i32 => {
+                composite.filters.push(filter);
+                Filter {
+                    filter_type: Some(FilterType::CompositeFilter(composite)),
+                }
+            }
+            Some(existing) => and([existing, filter]),
+        });
+        self
+    }
+
+    /// Adds a field filter. See [`field_filter()`].
+    pub fn where_field<S, T>(self, path: S, op: Op, value: &T) -> Self
+    where
+        S: Into<String>,
+        T: serde::Serialize,
+    {
+        match field_filter(path, op, value) {
+            Ok(filter) => self.where_(filter),
+            Err(error) => self.fail(error),
+        }
+    }
+
+    /// Adds a unary filter.
+    pub fn where_unary<S: Into<String>>(self, path: S, op: UnaryOp) -> Self {
+        self.where_(unary_filter(path, op))
+    }
+
+    /// Appends an order.
+    pub fn order_by<S: Into<String>>(mut self, path: S, direction: Direction) -> Self {
+        self.query.order_by.push(Order {
+            field: Some(field_reference(path)),
+            direction: match direction {
+                Direction::Ascending => structured_query::Direction::Ascending,
+                Direction::Descending => structured_query::Direction::Descending,
+            } as i32,
+        });
+        self
+    }
+
+    /// Starts the results at the position of `values` (inclusive). `values`
+    /// (e.g. a tuple) is converted to the cursor values in `order_by`
+    /// order. A value which is not a sequence is a cursor with one value.
+    pub fn start_at<T: serde::Serialize>(self, values: &T) -> Self {
+        self.cursor(values, true, true)
+    }
+
+    /// Starts the results after the position of `values`. See
+    /// [`Query::start_at`].
+    pub fn start_after<T: serde::Serialize>(self, values: &T) -> Self {
+        self.cursor(values, true, false)
+    }
+
+    /// Ends the results at the position of `values` (inclusive). See
+    /// [`Query::start_at`].
+    pub fn end_at<T: serde::Serialize>(self, values: &T) -> Self {
+        self.cursor(values, false, false)
+    }
+
+    /// Ends the results before the position of `values`. See
+    /// [`Query::start_at`].
+    pub fn end_before<T: serde::Serialize>(self, values: &T) -> Self {
+        self.cursor(values, false, true)
+    }
+
+    /// Sets the number of results to skip.
+    pub fn offset(mut self, offset: i32) -> Self {
+        self.query.offset = offset;
+        self
+    }
+
+    /// Sets the maximum number of results.
+    pub fn limit(mut self, limit: i32) -> Self {
+        self.query.limit = Some(limit);
+        self
+    }
+
+    /// Returns only `fields` of the documents. `__name__` returns only the
+    /// document names.
+    pub fn select<I>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.query.select = Some(Projection {
+            fields: fields.into_iter().map(field_reference).collect(),
+        });
+        self
+    }
+
+    /// Returns the query, or the first error of the builder methods.
+    pub fn build(self) -> Result<StructuredQuery, Error> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.query),
+        }
+    }
+
+    fn from_selector(collection_id: String, all_descendants: bool) -> Self {
+        Self {
+            query: StructuredQuery {
+                from: vec![CollectionSelector {
+                    collection_id,
+                    all_descendants,
+                }],
+                ..Default::default()
+            },
+            error: None,
+        }
+    }
+
+    fn cursor<T: serde::Serialize>(mut self, values: &T, start: bool, before: bool) -> Self {
+        let values = match cursor_values(values) {
+            Ok(values) => values,
+            Err(error) => return self.fail(error),
+        };
+        let cursor = Some(Cursor { values, before });
+        if start {
+            self.query.start_at = cursor;
+        } else {
+            self.query.end_at = cursor;
+        }
+        self
+    }
+
+    fn fail(mut self, error: Error) -> Self {
+        self.error.get_or_insert(error);
+        self
+    }
+}
+
+fn cursor_values<T: serde::Serialize>(values: &T) -> Result<Vec<Value>, Error> {
+    let value = crate::to_value(values)?;
+    match value.value_type {
+        Some(ValueType::ArrayValue(_)) => value.into_values(),
+        _ => Ok(vec![value]),
+    }
+}
+
+fn composite<I: IntoIterator<Item = Filter>>(op: composite_filter::Operator, filters: I) -> Filter {
+    Filter {
+        filter_type: Some(FilterType::CompositeFilter(CompositeFilter {
+            op: op as i32,
+            filters: filters.into_iter().collect(),
+        })),
+    }
+}
+
+fn field_reference<S: Into<String>>(path: S) -> structured_query::FieldReference {
+    structured_query::FieldReference {
+        field_path: path.into(),
+    }
+}
+
+fn invalid_query(message: String) -> Error {
+    Error::from(ErrorCode::InvalidQuery(message))
+}
@@ -0,0 +1,852 @@
+//! A text syntax for pipelines, functions and values.
+//!
+//! ```text
+//! collection("users") |> where(greater_than(field("age"), 18)) |> limit(10)
+//! ```
+//!
+//! - A pipeline is a list of stages separated by `|>`.
+//! - A stage or a function is `name(args...)`. Named options follow the
+//!   arguments as `key: value`. A name which is not an identifier or which
+//!   is reserved is quoted with backticks (e.g. `` `field`(...) ``).
+//! - `null`, `true`, `false`, integers (`18`), doubles (`1.5`, `1e3`, `NaN`,
+//!   `Infinity`, `-Infinity`), strings (`"..."`), arrays (`[a, b]`) and maps
+//!   (`{"key": value}`).
+//! - `field("path")`, `var("name")`, `ref("path")`,
+//!   `timestamp("2001-02-03T04:05:06Z")`, `bytes("00ff")` (hex),
+//!   `geo(latitude, longitude)` and `pipeline(...)`.
+//!
+//! In the `collection`, `collection_group` and `documents` stages, string
+//! arguments are shorthand for the reference encoding Firestore expects,
+//! e.g. `collection("users")` is `collection(ref("/users"))` and
+//! `collection_group("users/alice", "posts")` is
+//! `collection_group(ref("/users/alice"), "posts")`. A quoted stage
+//! name (e.g. `` `collection`("users") ``) disables the shorthand.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # fn main() -> anyhow::Result<()> {
+//! use serde_firestore_value::text::{parse_pipeline, print_pipeline};
+//!
+//! let pipeline = parse_pipeline(
+//!     r#"collection("users") |> where(greater_than(field("age"), 18)) |> limit(10)"#,
+//! )?;
+//! assert_eq!(pipeline.stages[1].name, "where");
+//! assert_eq!(
+//!     print_pipeline(&pipeline),
+//!     r#"collection("/users") |> where(greater_than(field("age"), 18)) |> limit(10)"#
+//! );
+//!
+//! let error = parse_pipeline("collection(\"users\") |> limit(").unwrap_err();
+//! assert_eq!((error.line(), error.column()), (1, 30));
+//! #     Ok(())
+//! # }
+//! ```
+
+use std::fmt::Write as _;
+
+use crate::google::firestore::v1::{
+    ArrayValue, MapValue, Value, pipeline::Stage as GoogleStage, value::ValueType,
+};
+use crate::google::r#type::LatLng;
+use crate::value_ext::ValueExt as _;
+use crate::{Function, Pipeline, Stage};
+
+const RESERVED: &[&str] = &[
+    "Infinity",
+    "NaN",
+    "bytes",
+    "false",
+    "field",
+    "geo",
+    "null",
+    "pipeline",
+    "ref",
+    "timestamp",
+    "true",
+    "var",
+];
+
+const SHORTHAND_STAGES: &[&str] = &["collection", "collection_group", "documents"];
+
+/// An error which occurred while parsing the text syntax.
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+#[error("{line}:{column}: {message}")]
+pub struct ParseError {
+    offset: usize,
+    line: usize,
+    column: usize,
+    message: String,
+}
+
+impl ParseError {
+    /// Returns the 1-based column (in characters) of the error.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns the 1-based line of the error.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the error message without the position.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the byte offset of the error.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+/// Parses a pipeline.
+pub fn parse_pipeline(s: &str) -> Result<Pipeline, ParseError> {
+    let mut parser = Parser { src: s, pos: 0 };
+    let pipeline = parser.pipeline()?;
+    parser.end()?;
+    Ok(pipeline)
+}
+
+/// Parses a function.
+pub fn parse_function(s: &str) -> Result<Function, ParseError> {
+    let mut parser = Parser { src: s, pos: 0 };
+    parser.skip_whitespace();
+    let start = parser.pos;
+    let value = parser.value()?;
+    parser.end()?;
+    match value.value_type {
+        Some(ValueType::FunctionValue(function)) => Ok(Function::from(function)),
+        _ => Err(parser.error_at(start, "expected a function".to_string())),
+    }
+}
+
+/// Parses a value.
+///
+/// `field(...)` and `var(...)` produce `fieldReferenceValue` and
+/// `variableReferenceValue`, which can be deserialized as
+/// [`FieldReference`](crate::FieldReference) and
+/// [`VariableReference`](crate::VariableReference).
+pub fn parse_value(s: &str) -> Result<Value, ParseError> {
+    let mut parser = Parser { src: s, pos: 0 };
+    let value = parser.value()?;
+    parser.end()?;
+    Ok(value)
+}
+
+/// Prints a pipeline. The output can be parsed by [`parse_pipeline`].
+pub fn print_pipeline(pipeline: &Pipeline) -> String {
+    let mut s = String::new();
+    write_stages(&mut s, pipeline.stages.iter().map(StageRef::from));
+    s
+}
+
+/// Prints a function. The output can be parsed by [`parse_function`].
+pub fn print_function(function: &Function) -> String {
+    let mut s = String::new();
+    write_call(
+        &mut s,
+        &function.name,
+        &function.args,
+        function.options.iter(),
+    );
+    s
+}
+
+/// Prints a value. The output can be parsed by [`parse_value`].
+pub fn print_value(value: &Value) -> String {
+    let mut s = String::new();
+    write_value(&mut s, value);
+    s
+}
+
+struct StageRef<'a> {
+    name: &'a str,
+    args: &'a [Value],
+    options: Vec<(&'a String, &'a Value)>,
+}
+
+impl<'a> From<&'a Stage> for StageRef<'a> {
+    fn from(stage: &'a Stage) -> Self {
+        Self {
+            name: &stage.name,
+            args: &stage.args,
+            options: stage.options.iter().collect(),
+        }
+    }
+}
+
+impl<'a> From<&'a GoogleStage> for StageRef<'a> {
+    fn from(stage: &'a GoogleStage) -> Self {
+        Self {
+            name: &stage.name,
+            args: &stage.args,
+            options: stage.options.iter().collect(),
+        }
+    }
+}
+
+fn write_stages<'a, I: Iterator<Item = StageRef<'a>>>(s: &mut String, stages: I) {
+    for (i, stage) in stages.enumerate() {
+        if i > 0 {
+            s.push_str(" |> ");
+        }
+        // The parser adds a leading `/` to shorthand paths, so paths without
+        // one are printed as references.
+        let path = |arg: &'a Value| {
+            arg.as_reference_value_as_string()
+                .ok()
+                .filter(|path| path.starts_with('/'))
+                .map(String::as_str)
+        };
+        let shorthand = match (stage.name, stage.args) {
+            ("collection", [arg]) => path(arg).map(|path| vec![path]),
+            ("collection_group", [parent, id]) => {
+                match (parent.as_reference_value_as_string(), id.as_string()) {
+                    (Ok(parent), Ok(id)) if parent.is_empty() => Some(vec![id.as_str()]),
+                    (Ok(parent), Ok(id)) if parent.starts_with('/') => {
+                        Some(vec![parent.as_str(), id.as_str()])
+                    }
+                    _ => None,
+                }
+            }
+            ("documents", args) if !args.is_empty() => args.iter().map(path).collect(),
+            _ => None,
+        };
+        match shorthand {
+            Some(strings) if stage.options.is_empty() => {
+                write_name(s, stage.name);
+                s.push('(');
+                for (i, string) in strings.into_iter().enumerate() {
+                    if i > 0 {
+                        s.push_str(", ");
+                    }
+                    write_string(s, string);
+                }
+                s.push(')');
+            }
+            // Quote the name so that the arguments are not read as shorthand.
+            _ if SHORTHAND_STAGES.contains(&stage.name) => {
+                write_quoted_name(s, stage.name);
+                write_args(s, stage.args, stage.options.into_iter());
+            }
+            _ => write_call(s, stage.name, stage.args, stage.options.into_iter()),
+        }
+    }
+}
+
+fn write_call<'a, I>(s: &mut String, name: &str, args: &[Value], options: I)
+where
+    I: Iterator<Item = (&'a String, &'a Value)>,
+{
+    write_name(s, name);
+    write_args(s, args, options);
+}
+
+fn write_args<'a, I>(s: &mut String, args: &[Value], options: I)
+where
+    I: Iterator<Item = (&'a String, &'a Value)>,
+{
+    s.push('(');
+    let mut first = true;
+    for arg in args {
+        if !first {
+            s.push_str(", ");
+        }
+        first = false;
+        write_value(s, arg);
+    }
+    let mut options = options.collect::<Vec<_>>();
+    options.sort_by_key(|(k, _)| *k);
+    for (key, value) in options {
+        if !first {
+            s.push_str(", ");
+        }
+        first = false;
+        if is_identifier(key) {
+            s.push_str(key);
+        } else {
+            write_string(s, key);
+        }
+        s.push_str(": ");
+        write_value(s, value);
+    }
+    s.push(')');
+}
+
+fn write_name(s: &mut String, name: &str) {
+    if is_identifier(name) && !RESERVED.contains(&name) {
+        s.push_str(name);
+    } else {
+        write_quoted_name(s, name);
+    }
+}
+
+fn write_quoted_name(s: &mut String, name: &str) {
+    s.push('`');
+    for c in name.chars() {
+        if c == '`' || c == '\\' {
+            s.push('\\');
+        }
+        s.push(c);
+    }
+    s.push('`');
+}
+
+fn write_value(s: &mut String, value: &Value) {
+    match &value.value_type {
+        None | Some(ValueType::NullValue(_)) => s.push_str("null"),
+        Some(ValueType::BooleanValue(b)) => s.push_str(if *b { "true" } else { "false" }),
+        Some(ValueType::IntegerValue(i)) => write!(s, "{i}").expect("write to String"),
+        Some(ValueType::DoubleValue(d)) => write_double(s, *d),
+        Some(ValueType::TimestampValue(t)) => match crate::rfc3339::format(t.seconds, t.nanos) {
+            Ok(formatted) => {
+                s.push_str("timestamp(");
+                write_string(s, &formatted);
+                s.push(')');
+            }
+            Err(_) => write!(s, "timestamp({}, {})", t.seconds, t.nanos).expect("write to String"),
+        },
+        Some(ValueType::StringValue(string)) => write_string(s, string),
+        Some(ValueType::BytesValue(bytes)) => {
+            s.push_str("bytes(\"");
+            for b in AsRef::<[u8]>::as_ref(bytes) {
+                write!(s, "{b:02x}").expect("write to String");
+            }
+            s.push_str("\")");
+        }
+        Some(ValueType::ReferenceValue(r)) => {
+            s.push_str("ref(");
+            write_string(s, r);
+            s.push(')');
+        }
+        Some(ValueType::GeoPointValue(LatLng {
+            latitude,
+            longitude,
+        })) => {
+            s.push_str("geo(");
+            write_double(s, *latitude);
+            s.push_str(", ");
+            write_double(s, *longitude);
+            s.push(')');
+        }
+        Some(ValueType::ArrayValue(ArrayValue { values })) => {
+            s.push('[');
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    s.push_str(", ");
+                }
+                write_value(s, value);
+            }
+            s.push(']');
+        }
+        Some(ValueType::MapValue(MapValue { fields })) => {
+            let mut fields = fields.iter().collect::<Vec<_>>();
+            fields.sort_by_key(|(k, _)| *k);
+            s.push('{');
+            for (i, (key, value)) in fields.into_iter().enumerate() {
+                if i > 0 {
+                    s.push_str(", ");
+                }
+                write_string(s, key);
+                s.push_str(": ");
+                write_value(s, value);
+            }
+            s.push('}');
+        }
+        Some(ValueType::FieldReferenceValue(f)) => {
+            s.push_str("field(");
+            write_string(s, f);
+            s.push(')');
+        }
+        Some(ValueType::VariableReferenceValue(v)) => {
+            s.push_str("var(");
+            write_string(s, v);
+            s.push(')');
+        }
+        Some(ValueType::FunctionValue(function)) => {
+            write_call(s, &function.name, &function.args, function.options.iter())
+        }
+        Some(ValueType::PipelineValue(pipeline)) => {
+            s.push_str("pipeline(");
+            write_stages(s, pipeline.stages.iter().map(StageRef::from));
+            s.push(')');
+        }
+    }
+}
+
+fn write_double(s: &mut String, d: f64) {
+    if d.is_nan() {
+        s.push_str("NaN");
+    } else if d.is_infinite() {
+        s.push_str(if d > 0.0 { "Infinity" } else { "-Infinity" });
+    } else {
+        // `{:?}` always includes a `.` or an exponent.
+        write!(s, "{d:?}").expect("write to String");
+    }
+}
+
+fn write_string(s: &mut String, string: &str) {
+    s.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => s.push_str("\\\""),
+            '\\' => s.push_str("\\\\"),
+            '\n' => s.push_str("\\n"),
+            '\r' => s.push_str("\\r"),
+            '\t' => s.push_str("\\t"),
+            c if c.is_control() => write!(s, "\\u{{{:x}}}", c as u32).expect("write to String"),
+            c => s.push(c),
+        }
+    }
+    s.push('"');
+}
+
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error_at(&self, offset: usize, message: String) -> ParseError {
+        let before = &self.src[..offset];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let column = before[line_start..].chars().count() + 1;
+        ParseError {
+            offset,
+            line,
+            column,
+            message,
+        }
+    }
+
+    fn error(&self, message: String) -> ParseError {
+        self.error_at(self.pos, message)
+    }
+
+    fn unexpected(&self, expected: &str) -> ParseError {
+        match self.peek() {
+            Some(c) => self.error(format!("expected {expected}, found `{c}`")),
+            None => self.error(format!("expected {expected}, found end of input")),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.src[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        if self.src[self.pos..].starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), ParseError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("`{token}`")))
+        }
+    }
+
+    fn end(&mut self) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.unexpected("end of input")),
+        }
+    }
+
+    fn pipeline(&mut self) -> Result<Pipeline, ParseError> {
+        let mut stages = vec![self.stage()?];
+        while self.eat("|>") {
+            stages.push(self.stage()?);
+        }
+        Ok(Pipeline { stages })
+    }
+
+    fn stage(&mut self) -> Result<Stage, ParseError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        let quoted = self.peek() == Some('`');
+        let name = self.name()?;
+        let (mut args, options) = self.call_args()?;
+        if quoted {
+            return Ok(Stage {
+                name,
+                args,
+                options: options.into_iter().collect(),
+            });
+        }
+        let reference = |value: &mut Value, prefix: bool| {
+            if let Some(ValueType::StringValue(s)) = &value.value_type {
+                let s = if prefix && !s.starts_with('/') {
+                    format!("/{s}")
+                } else {
+                    s.clone()
+                };
+                *value = Value::from_string_as_reference_value(s);
+            }
+        };
+        match (name.as_str(), args.as_mut_slice()) {
+            ("collection", [arg]) => reference(arg, true),
+            ("collection_group", [arg]) => {
+                if arg.as_string().is_err() {
+                    return Err(self.error_at(start, "expected a collection ID".to_string()));
+                }
+                args.insert(0, Value::from_string_as_reference_value(String::new()));
+            }
+            ("collection_group", [parent, _]) => {
+                let prefix = parent.as_string().is_ok_and(|s| !s.is_empty());
+                reference(parent, prefix);
+            }
+            ("documents", args) => args.iter_mut().for_each(|arg| reference(arg, true)),
+            _ => {}
+        }
+        Ok(Stage {
+            name,
+            args,
+            options: options.into_iter().collect(),
+        })
+    }
+
+    fn name(&mut self) -> Result<String, ParseError> {
+        self.skip_whitespace();
+        if self.peek() == Some('`') {
+            self.pos += 1;
+            let mut name = String::new();
+            loop {
+                match self.peek() {
+                    None => return Err(self.unexpected("`` ` ``")),
+                    Some('`') => {
+                        self.pos += 1;
+                        return Ok(name);
+                    }
+                    Some('\\') => {
+                        self.pos += 1;
+                        match self.peek() {
+                            Some(c) => {
+                                self.pos += c.len_utf8();
+                                name.push(c);
+                            }
+                            None => return Err(self.unexpected("a character")),
+                        }
+                    }
+                    Some(c) => {
+                        self.pos += c.len_utf8();
+                        name.push(c);
+                    }
+                }
+            }
+        }
+        self.identifier()
+            .map(str::to_string)
+            .ok_or_else(|| self.unexpected("a name"))
+    }
+
+    fn identifier(&mut self) -> Option<&str> {
+        let rest = &self.src[self.pos..];
+        let len = rest
+            .char_indices()
+            .find(|(i, c)| {
+                !(c.is_ascii_alphabetic() || *c == '_' || (*i > 0 && c.is_ascii_digit()))
+            })
+            .map(|(i, _)| i)
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return None;
+        }
+        self.pos += len;
+        Some(&rest[..len])
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn call_args(&mut self) -> Result<(Vec<Value>, Vec<(String, Value)>), ParseError> {
+        self.expect("(")?;
+        let mut args = vec![];
+        let mut options: Vec<(String, Value)> = vec![];
+        if self.eat(")") {
+            return Ok((args, options));
+        }
+        loop {
+            self.skip_whitespace();
+            let start = self.pos;
+            match self.option_key()? {
+                Some(key) => {
+                    if options.iter().any(|(k, _)| *k == key) {
+                        return Err(self.error_at(start, format!("duplicate option `{key}`")));
+                    }
+                    let value = self.value()?;
+                    options.push((key, value));
+                }
+                None if !options.is_empty() => {
+                    return Err(self.error("arguments must precede options".to_string()));
+                }
+                None => args.push(self.value()?),
+            }
+            if self.eat(")") {
+                return Ok((args, options));
+            }
+            self.expect(",")?;
+        }
+    }
+
+    /// Parses `key:` if present.
+    fn option_key(&mut self) -> Result<Option<String>, ParseError> {
+        let start = self.pos;
+        let key = match self.peek() {
+            Some('"') => Some(self.string()?),
+            _ => self.identifier().map(str::to_string),
+        };
+        if key.is_some() && self.eat(":") {
+            return Ok(key);
+        }
+        self.pos = start;
+        Ok(None)
+    }
+
+    fn value(&mut self) -> Result<Value, ParseError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        match self.peek() {
+            Some('"') => return self.string().map(Value::from_string),
+            Some('[') => {
+                self.pos += 1;
+                let mut values = vec![];
+                if !self.eat("]") {
+                    loop {
+                        values.push(self.value()?);
+                        if self.eat("]") {
+                            break;
+                        }
+                        self.expect(",")?;
+                    }
+                }
+                return Ok(Value::from_values(values));
+            }
+            Some('{') => {
+                self.pos += 1;
+                let mut fields: Vec<(String, Value)> = vec![];
+                if !self.eat("}") {
+                    loop {
+                        self.skip_whitespace();
+                        let key_start = self.pos;
+                        if self.peek() != Some('"') {
+                            return Err(self.unexpected("a string key"));
+                        }
+                        let key = self.string()?;
+                        if fields.iter().any(|(k, _)| *k == key) {
+                            return Err(self.error_at(key_start, format!("duplicate key `{key}`")));
+                        }
+                        self.expect(":")?;
+                        fields.push((key, self.value()?));
+                        if self.eat("}") {
+                            break;
+                        }
+                        self.expect(",")?;
+                    }
+                }
+                return Ok(Value::from_fields(fields));
+            }
+            Some(c) if c == '-' || c.is_ascii_digit() => return self.number(),
+            Some('`') => {}
+            Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+            _ => return Err(self.unexpected("a value")),
+        }
+
+        let quoted = self.peek() == Some('`');
+        let name = self.name()?;
+        if !quoted {
+            match name.as_str() {
+                "null" => return Ok(Value::null()),
+                "true" => return Ok(Value::from_bool(true)),
+                "false" => return Ok(Value::from_bool(false)),
+                "NaN" => return Ok(Value::from_f64(f64::NAN)),
+                "Infinity" => return Ok(Value::from_f64(f64::INFINITY)),
+                "pipeline" => {
+                    self.expect("(")?;
+                    let pipeline = self.pipeline()?;
+                    self.expect(")")?;
+                    return Ok(Value::from_pipeline(pipeline.into()));
+                }
+                _ => {}
+            }
+        }
+        let (args, options) = self.call_args()?;
+        if quoted || !RESERVED.contains(&name.as_str()) {
+            return Ok(Value::from_function(
+                Function {
+                    name,
+                    args,
+                    options: options.into_iter().collect(),
+                }
+                .into(),
+            ));
+        }
+
+        if !options.is_empty() {
+            return Err(self.error_at(start, format!("`{name}` does not take options")));
+        }
+        let string_arg = |args: &[Value]| -> Option<String> {
+            match args {
+                [arg] => arg.as_string().ok().cloned(),
+                _ => None,
+            }
+        };
+        let invalid = |expected: &str| self.error_at(start, format!("`{name}` expects {expected}"));
+        match name.as_str() {
+            "field" => string_arg(&args)
+                .map(Value::from_string_as_field_reference_value)
+                .ok_or_else(|| invalid("a string")),
+            "var" => string_arg(&args)
+                .map(Value::from_string_as_variable_reference_value)
+                .ok_or_else(|| invalid("a string")),
+            "ref" => string_arg(&args)
+                .map(Value::from_string_as_reference_value)
+                .ok_or_else(|| invalid("a string")),
+            "bytes" => {
+                let hex = string_arg(&args).ok_or_else(|| invalid("a hex string"))?;
+                if hex.len() % 2 != 0 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+                    return Err(invalid("a hex string"));
+                }
+                (0..hex.len())
+                    .step_by(2)
+                    .map(|i| {
+                        hex.get(i..i + 2)
+                            .and_then(|b| u8::from_str_radix(b, 16).ok())
+                    })
+                    .collect::<Option<Vec<u8>>>()
+                    .map(Value::from_bytes)
+                    .ok_or_else(|| invalid("a hex string"))
+            }
+            "geo" => match args.as_slice() {
+                [latitude, longitude] => match (number(latitude), number(longitude)) {
+                    (Some(latitude), Some(longitude)) => Ok(Value::from_lat_lng(LatLng {
+                        latitude,
+                        longitude,
+                    })),
+                    _ => Err(invalid("two numbers")),
+                },
+                _ => Err(invalid("two numbers")),
+            },
+            _ => match args.as_slice() {
+                [arg] => {
+                    let s = arg.as_string().map_err(|_| invalid("a string"))?;
+                    let (seconds, nanos) =
+                        crate::rfc3339::parse(s).map_err(|_| invalid("an RFC 3339 timestamp"))?;
+                    Ok(Value::from_timestamp(prost_types::Timestamp {
+                        seconds,
+                        nanos,
+                    }))
+                }
+                [seconds, nanos] => match (seconds.as_integer(), nanos.as_integer()) {
+                    (Ok(seconds), Ok(nanos)) => Ok(Value::from_timestamp(prost_types::Timestamp {
+                        seconds,
+                        nanos: i32::try_from(nanos).map_err(|_| invalid("nanos in i32"))?,
+                    })),
+                    _ => Err(invalid("two integers")),
+                },
+                _ => Err(invalid("a string")),
+            },
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, ParseError> {
+        let start = self.pos;
+        if self.src[self.pos..].starts_with("-Infinity") {
+            self.pos += "-Infinity".len();
+            return Ok(Value::from_f64(f64::NEG_INFINITY));
+        }
+        let rest = &self.src[self.pos..];
+        let len = rest
+            .char_indices()
+            .find(|(i, c)| {
+                !(c.is_ascii_digit()
+                    || matches!(c, '.' | 'e' | 'E')
+                    || (matches!(c, '-' | '+')
+                        && (*i == 0 || matches!(rest.as_bytes()[i - 1], b'e' | b'E'))))
+            })
+            .map(|(i, _)| i)
+            .unwrap_or(rest.len());
+        let token = &rest[..len];
+        self.pos += len;
+        let value = if token.contains(['.', 'e', 'E']) {
+            token.parse::<f64>().ok().map(Value::from_f64)
+        } else {
+            token.parse::<i64>().ok().map(Value::from_i64)
+        };
+        value.ok_or_else(|| self.error_at(start, format!("invalid number `{token}`")))
+    }
+
+    fn string(&mut self) -> Result<String, ParseError> {
+        self.expect("\"")?;
+        let mut s = String::new();
+        loop {
+            let start = self.pos;
+            let Some(c) = self.peek() else {
+                return Err(self.unexpected("`\"`"));
+            };
+            self.pos += c.len_utf8();
+            match c {
+                '"' => return Ok(s),
+                '\\' => {
+                    let Some(escaped) = self.peek() else {
+                        return Err(self.unexpected("an escape sequence"));
+                    };
+                    self.pos += escaped.len_utf8();
+                    match escaped {
+                        '"' => s.push('"'),
+                        '\\' => s.push('\\'),
+                        'n' => s.push('\n'),
+                        'r' => s.push('\r'),
+                        't' => s.push('\t'),
+                        'u' => {
+                            self.expect("{")?;
+                            let end = self.src[self.pos..]
+                                .find('}')
+                                .ok_or_else(|| self.unexpected("`}`"))?;
+                            let hex = &self.src[self.pos..self.pos + end];
+                            let c = u32::from_str_radix(hex, 16)
+                                .ok()
+                                .and_then(char::from_u32)
+                                .ok_or_else(|| {
+                                    self.error_at(start, format!("invalid escape `\\u{{{hex}}}`"))
+                                })?;
+                            self.pos += end + 1;
+                            s.push(c);
+                        }
+                        c => return Err(self.error_at(start, format!("invalid escape `\\{c}`"))),
+                    }
+                }
+                c => s.push(c),
+            }
+        }
+    }
+}
+
+fn number(value: &Value) -> Option<f64> {
+    match value.value_type {
+        Some(ValueType::DoubleValue(d)) => Some(d),
+        Some(ValueType::IntegerValue(i)) => Some(i as f64),
+        _ => None,
+    }
+}
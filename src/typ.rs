@@ -1,16 +1,20 @@
+pub(crate) mod bytes;
 pub(crate) mod field_reference;
 pub(crate) mod function;
 pub(crate) mod lat_lng;
 pub(crate) mod pipeline;
 mod private;
+pub(crate) mod raw_value;
 pub(crate) mod reference;
 pub(crate) mod timestamp;
 mod variable_reference;
 
+pub use self::bytes::Bytes;
 pub use self::field_reference::FieldReference;
 pub use self::function::Function;
 pub use self::lat_lng::LatLng;
 pub use self::pipeline::{Pipeline, Stage};
+pub use self::raw_value::RawValue;
 pub use self::reference::Reference;
 pub use self::timestamp::Timestamp;
 pub use self::variable_reference::VariableReference;
@@ -0,0 +1,117 @@
+/// Bytes
+///
+/// `bytesValue` inner type.
+///
+/// A plain `Vec<u8>` is (de)serialized as `arrayValue` of `integerValue`.
+/// Use this type, [`crate::with::bytes`] or `serde_bytes` to (de)serialize
+/// it as `bytesValue`. The length is limited to 1,048,487 bytes.
+///
+/// With other formats, it is (de)serialized via `serialize_bytes` and
+/// `deserialize_byte_buf`, so it is compatible with `serde_bytes::ByteBuf`.
+///
+/// <https://firebase.google.com/docs/firestore/reference/rest/Shared.Types/ArrayValue#Value>
+///
+/// # Examples
+///
+/// ```rust
+/// # fn test_bytes() -> Result<(), serde_firestore_value::Error> {
+/// #     use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// #     use serde_firestore_value::{from_value, to_value, Bytes};
+/// let o = Bytes(vec![1_u8, 2_u8, 3_u8]);
+/// let v = Value {
+///     value_type: Some(ValueType::BytesValue(vec![1_u8, 2_u8, 3_u8])),
+/// };
+/// let s = to_value(&o)?;
+/// let d = from_value::<'_, Bytes>(&s)?;
+/// assert_eq!(s, v);
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Bytes(pub Vec<u8>);
+
+impl<'de> serde::Deserialize<'de> for Bytes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_byte_buf(BytesVisitor)
+    }
+}
+
+impl serde::Serialize for Bytes {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+impl AsRef<[u8]> for Bytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Bytes> for Vec<u8> {
+    fn from(Bytes(v): Bytes) -> Self {
+        v
+    }
+}
+
+impl From<Vec<u8>> for Bytes {
+    fn from(v: Vec<u8>) -> Self {
+        Self(v)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl From<Bytes> for prost::bytes::Bytes {
+    fn from(Bytes(v): Bytes) -> Self {
+        Self::from(v)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl From<prost::bytes::Bytes> for Bytes {
+    fn from(v: prost::bytes::Bytes) -> Self {
+        Self(Vec::from(v))
+    }
+}
+
+struct BytesVisitor;
+
+impl<'de> serde::de::Visitor<'de> for BytesVisitor {
+    type Value = Bytes;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("bytes")
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(Bytes(v.to_vec()))
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(Bytes(v))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let mut v = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4_096));
+        while let Some(b) = seq.next_element::<u8>()? {
+            v.push(b);
+        }
+        Ok(Bytes(v))
+    }
+}
@@ -1,7 +1,12 @@
+use super::private::{deserialize_string_newtype, serialize_string_newtype};
+
 /// FieldReference
 ///
 /// `fieldReferenceValue` inner type.
 ///
+/// With other human-readable formats (e.g. `serde_json`), it is (de)serialized
+/// as the field path string.
+///
 /// <https://firebase.google.com/docs/firestore/reference/rest/Shared.Types/ArrayValue#Value>
 ///
 /// # Examples
@@ -20,16 +25,31 @@
 /// #     Ok(())
 /// # }
 /// ```
-#[derive(
-    Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, serde::Deserialize, serde::Serialize,
-)]
-#[serde(rename = "$__serde-firestore-value_private_field_reference")]
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct FieldReference(pub String);
 
 impl FieldReference {
     pub(crate) const NAME: &'static str = "$__serde-firestore-value_private_field_reference";
 }
 
+impl<'de> serde::Deserialize<'de> for FieldReference {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserialize_string_newtype(Self::NAME, deserializer).map(Self)
+    }
+}
+
+impl serde::Serialize for FieldReference {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_string_newtype(Self::NAME, &self.0, serializer)
+    }
+}
+
 impl From<FieldReference> for String {
     fn from(FieldReference(s): FieldReference) -> Self {
         s
@@ -3,8 +3,11 @@ use std::collections::BTreeMap;
 #[cfg(feature = "hash-map")]
 use std::collections::HashMap;
 
-use super::private::{ValueMapSeed, ValueVecSeed, ValueWrapper};
-use crate::google::firestore::v1::Value;
+use super::private::{
+    ValueMapSeed, ValueVecSeed, ValueWrapper, deserialize_args, deserialize_options,
+    serialize_args, serialize_options,
+};
+use crate::google::firestore::v1::{Function as GoogleFirestoreFunction, Value};
 
 /// Function
 ///
@@ -28,6 +31,72 @@ pub struct Function {
 impl Function {
     pub(crate) const FIELDS: &'static [&'static str] = &["name", "args", "options"];
     pub(crate) const NAME: &'static str = "$__serde-firestore-value_private_function";
+
+    /// Creates a function from `args` serialized positionally (e.g. a tuple
+    /// `&(a, b)`).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use serde_firestore_value::{FieldReference, Function};
+    ///
+    /// let function = Function::from_args(
+    ///     "greater_than",
+    ///     &(FieldReference("age".to_string()), 18),
+    /// )?;
+    /// let (field, value) = function.args_as::<(FieldReference, i64)>()?;
+    /// assert_eq!(field, FieldReference("age".to_string()));
+    /// assert_eq!(value, 18);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_args<S, T>(name: S, args: &T) -> Result<Self, crate::Error>
+    where
+        S: Into<String>,
+        T: serde::Serialize,
+    {
+        Ok(Self {
+            name: name.into(),
+            args: serialize_args(args)?,
+            options: Default::default(),
+        })
+    }
+
+    /// Replaces the options with `options` serialized by name (e.g. a
+    /// struct).
+    pub fn with_options<T>(mut self, options: &T) -> Result<Self, crate::Error>
+    where
+        T: serde::Serialize,
+    {
+        self.options = serialize_options(options)?;
+        Ok(self)
+    }
+
+    /// Deserializes the arguments positionally as `T` (e.g. a tuple).
+    pub fn args_as<'a, T>(&'a self) -> Result<T, crate::Error>
+    where
+        T: serde::Deserialize<'a>,
+    {
+        deserialize_args(&self.args)
+    }
+
+    /// Deserializes the option `key` as `T`. Returns `None` if the option
+    /// is absent.
+    pub fn option_as<'a, T>(&'a self, key: &str) -> Result<Option<T>, crate::Error>
+    where
+        T: serde::Deserialize<'a>,
+    {
+        self.options.get(key).map(crate::from_value).transpose()
+    }
+
+    /// Deserializes the options by name as `T` (e.g. a struct).
+    pub fn options_as<'a, T>(&'a self) -> Result<T, crate::Error>
+    where
+        T: serde::Deserialize<'a>,
+    {
+        deserialize_options(self.options.iter())
+    }
 }
 
 impl<'de> serde::Deserialize<'de> for Function {
@@ -50,14 +119,14 @@ impl serde::Serialize for Function {
         state.serialize_field("name", &self.name)?;
         state.serialize_field(
             "args",
-            &self.args.iter().map(ValueWrapper).collect::<Vec<_>>(),
+            &self.args.iter().map(ValueWrapper::new).collect::<Vec<_>>(),
         )?;
         state.serialize_field(
             "options",
             &self
                 .options
                 .iter()
-                .map(|(k, v)| (k.as_str(), ValueWrapper(v)))
+                .map(|(k, v)| (k.as_str(), ValueWrapper::new(v)))
                 .collect::<std::collections::HashMap<_, _>>(),
         )?;
         state.end()
@@ -121,3 +190,35 @@ impl<'de> serde::de::Visitor<'de> for FunctionVisitor {
         })
     }
 }
+
+impl From<GoogleFirestoreFunction> for Function {
+    fn from(
+        GoogleFirestoreFunction {
+            name,
+            args,
+            options,
+        }: GoogleFirestoreFunction,
+    ) -> Self {
+        Self {
+            name,
+            args,
+            options,
+        }
+    }
+}
+
+impl From<Function> for GoogleFirestoreFunction {
+    fn from(
+        Function {
+            name,
+            args,
+            options,
+        }: Function,
+    ) -> Self {
+        Self {
+            name,
+            args,
+            options,
+        }
+    }
+}
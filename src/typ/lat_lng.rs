@@ -1,9 +1,13 @@
+use super::private::{Encodings, serialize_with_handshake};
 use crate::google::r#type::LatLng as GoogleApiProtoLatLng;
 
 /// LatLng
 ///
 /// `geoPointValue` inner type.
 ///
+/// With other human-readable formats (e.g. `serde_json`), it is (de)serialized
+/// as `{"lat": f64, "lng": f64}`.
+///
 /// <https://firebase.google.com/docs/firestore/reference/rest/Shared.Types/LatLng>
 /// <https://firebase.google.com/docs/firestore/reference/rest/Shared.Types/ArrayValue#Value>
 ///
@@ -31,8 +35,7 @@ use crate::google::r#type::LatLng as GoogleApiProtoLatLng;
 /// assert_eq!(d, o);
 /// #     Ok(())
 /// # }
-#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
-#[serde(rename = "$__serde-firestore-value_private_lat_lng")]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct LatLng {
     /// latitude
     pub latitude: f64,
@@ -41,7 +44,125 @@ pub struct LatLng {
 }
 
 impl LatLng {
+    pub(crate) const FIELDS: &'static [&'static str] = &["latitude", "longitude"];
     pub(crate) const NAME: &'static str = "$__serde-firestore-value_private_lat_lng";
+    const NATURAL_FIELDS: &'static [&'static str] = &["lat", "lng"];
+    const NATURAL_NAME: &'static str = "LatLng";
+}
+
+impl<'de> serde::Deserialize<'de> for LatLng {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(Self::NAME, LatLngVisitor)
+    }
+}
+
+impl serde::Serialize for LatLng {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_with_handshake(Self::NAME, self, serializer)
+    }
+}
+
+impl Encodings for LatLng {
+    fn serialize_private<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct(Self::NAME, Self::FIELDS.len())?;
+        state.serialize_field("latitude", &self.latitude)?;
+        state.serialize_field("longitude", &self.longitude)?;
+        state.end()
+    }
+
+    fn serialize_natural<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut state =
+            serializer.serialize_struct(Self::NATURAL_NAME, Self::NATURAL_FIELDS.len())?;
+        state.serialize_field("lat", &self.latitude)?;
+        state.serialize_field("lng", &self.longitude)?;
+        state.end()
+    }
+}
+
+struct LatLngVisitor;
+
+impl<'de> serde::de::Visitor<'de> for LatLngVisitor {
+    type Value = LatLng;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a LatLng struct")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_struct(LatLng::NATURAL_NAME, LatLng::NATURAL_FIELDS, self)
+        } else {
+            deserializer.deserialize_struct(LatLng::NAME, LatLng::FIELDS, self)
+        }
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let latitude = seq
+            .next_element()?
+            .ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        let longitude = seq
+            .next_element()?
+            .ok_or_else(|| serde::de::Error::invalid_length(1, &self))?;
+        Ok(LatLng {
+            latitude,
+            longitude,
+        })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        let mut latitude: Option<f64> = None;
+        let mut longitude: Option<f64> = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "latitude" | "lat" => {
+                    if latitude.is_some() {
+                        return Err(serde::de::Error::duplicate_field("latitude"));
+                    }
+                    latitude = Some(map.next_value()?);
+                }
+                "longitude" | "lng" => {
+                    if longitude.is_some() {
+                        return Err(serde::de::Error::duplicate_field("longitude"));
+                    }
+                    longitude = Some(map.next_value()?);
+                }
+                _ => {
+                    let _: serde::de::IgnoredAny = map.next_value()?;
+                }
+            }
+        }
+        let latitude = latitude.ok_or_else(|| serde::de::Error::missing_field("latitude"))?;
+        let longitude = longitude.ok_or_else(|| serde::de::Error::missing_field("longitude"))?;
+        Ok(LatLng {
+            latitude,
+            longitude,
+        })
+    }
 }
 
 impl From<GoogleApiProtoLatLng> for LatLng {
@@ -71,3 +192,64 @@ impl From<LatLng> for GoogleApiProtoLatLng {
         }
     }
 }
+
+#[cfg(feature = "geo-types")]
+impl From<LatLng> for geo_types::Coord<f64> {
+    /// Converts to `Coord { x: longitude, y: latitude }`.
+    fn from(
+        LatLng {
+            latitude,
+            longitude,
+        }: LatLng,
+    ) -> Self {
+        Self {
+            x: longitude,
+            y: latitude,
+        }
+    }
+}
+
+#[cfg(feature = "geo-types")]
+impl From<LatLng> for geo_types::Point<f64> {
+    /// Converts to `Point(Coord { x: longitude, y: latitude })`.
+    fn from(lat_lng: LatLng) -> Self {
+        Self(geo_types::Coord::from(lat_lng))
+    }
+}
+
+#[cfg(feature = "geo-types")]
+impl std::convert::TryFrom<geo_types::Coord<f64>> for LatLng {
+    type Error = crate::Error;
+
+    /// Converts from `Coord { x: longitude, y: latitude }`.
+    ///
+    /// Fails if the latitude is not in `[-90, 90]` or the longitude is not in
+    /// `[-180, 180]`.
+    fn try_from(geo_types::Coord { x, y }: geo_types::Coord<f64>) -> Result<Self, Self::Error> {
+        if !(-90_f64..=90_f64).contains(&y) || !(-180_f64..=180_f64).contains(&x) {
+            return Err(crate::Error::from(
+                crate::error::ErrorCode::LatLngOutOfRange(format!(
+                    "latitude = {}, longitude = {}",
+                    y, x
+                )),
+            ));
+        }
+        Ok(Self {
+            latitude: y,
+            longitude: x,
+        })
+    }
+}
+
+#[cfg(feature = "geo-types")]
+impl std::convert::TryFrom<geo_types::Point<f64>> for LatLng {
+    type Error = crate::Error;
+
+    /// Converts from `Point(Coord { x: longitude, y: latitude })`.
+    ///
+    /// Fails if the latitude is not in `[-90, 90]` or the longitude is not in
+    /// `[-180, 180]`.
+    fn try_from(point: geo_types::Point<f64>) -> Result<Self, Self::Error> {
+        Self::try_from(point.0)
+    }
+}
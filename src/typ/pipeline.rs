@@ -3,7 +3,10 @@ use std::collections::BTreeMap;
 #[cfg(feature = "hash-map")]
 use std::collections::HashMap;
 
-use crate::google::firestore::v1::Value;
+use super::private::{deserialize_args, deserialize_options, serialize_args, serialize_options};
+use crate::google::firestore::v1::{
+    Pipeline as GoogleFirestorePipeline, Value, pipeline::Stage as GoogleFirestoreStage,
+};
 
 /// Pipeline
 ///
@@ -65,6 +68,73 @@ pub struct Stage {
 impl Stage {
     pub(crate) const FIELDS: &'static [&'static str] = &["name", "args", "options"];
     pub(crate) const NAME: &'static str = "$__serde-firestore-value_private_pipeline_stage";
+
+    /// Creates a stage from `args` serialized positionally (e.g. a tuple
+    /// `&(a, b)`).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # fn main() -> anyhow::Result<()> {
+    /// use serde_firestore_value::Stage;
+    ///
+    /// #[derive(serde::Serialize, serde::Deserialize)]
+    /// struct Options {
+    ///     limit: i64,
+    /// }
+    ///
+    /// let stage = Stage::from_args("sample", &())?.with_options(&Options { limit: 3 })?;
+    /// assert_eq!(stage.option_as::<i64>("limit")?, Some(3));
+    /// assert_eq!(stage.options_as::<Options>()?.limit, 3);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn from_args<S, T>(name: S, args: &T) -> Result<Self, crate::Error>
+    where
+        S: Into<String>,
+        T: serde::Serialize,
+    {
+        Ok(Self {
+            name: name.into(),
+            args: serialize_args(args)?,
+            options: Default::default(),
+        })
+    }
+
+    /// Replaces the options with `options` serialized by name (e.g. a
+    /// struct).
+    pub fn with_options<T>(mut self, options: &T) -> Result<Self, crate::Error>
+    where
+        T: serde::Serialize,
+    {
+        self.options = serialize_options(options)?;
+        Ok(self)
+    }
+
+    /// Deserializes the arguments positionally as `T` (e.g. a tuple).
+    pub fn args_as<'a, T>(&'a self) -> Result<T, crate::Error>
+    where
+        T: serde::Deserialize<'a>,
+    {
+        deserialize_args(&self.args)
+    }
+
+    /// Deserializes the option `key` as `T`. Returns `None` if the option
+    /// is absent.
+    pub fn option_as<'a, T>(&'a self, key: &str) -> Result<Option<T>, crate::Error>
+    where
+        T: serde::Deserialize<'a>,
+    {
+        self.options.get(key).map(crate::from_value).transpose()
+    }
+
+    /// Deserializes the options by name as `T` (e.g. a struct).
+    pub fn options_as<'a, T>(&'a self) -> Result<T, crate::Error>
+    where
+        T: serde::Deserialize<'a>,
+    {
+        deserialize_options(self.options.iter())
+    }
 }
 
 impl serde::Serialize for Stage {
@@ -81,7 +151,7 @@ impl serde::Serialize for Stage {
             &self
                 .args
                 .iter()
-                .map(crate::typ::private::ValueWrapper)
+                .map(crate::typ::private::ValueWrapper::new)
                 .collect::<Vec<_>>(),
         )?;
         state.serialize_field(
@@ -89,7 +159,7 @@ impl serde::Serialize for Stage {
             &self
                 .options
                 .iter()
-                .map(|(k, v)| (k.as_str(), crate::typ::private::ValueWrapper(v)))
+                .map(|(k, v)| (k.as_str(), crate::typ::private::ValueWrapper::new(v)))
                 .collect::<std::collections::HashMap<_, _>>(),
         )?;
         state.end()
@@ -244,3 +314,51 @@ impl<'de> serde::de::DeserializeSeed<'de> for StageSeed {
         serde::Deserialize::deserialize(deserializer)
     }
 }
+
+impl From<GoogleFirestorePipeline> for Pipeline {
+    fn from(GoogleFirestorePipeline { stages }: GoogleFirestorePipeline) -> Self {
+        Self {
+            stages: stages.into_iter().map(Stage::from).collect(),
+        }
+    }
+}
+
+impl From<Pipeline> for GoogleFirestorePipeline {
+    fn from(Pipeline { stages }: Pipeline) -> Self {
+        Self {
+            stages: stages.into_iter().map(GoogleFirestoreStage::from).collect(),
+        }
+    }
+}
+
+impl From<GoogleFirestoreStage> for Stage {
+    fn from(
+        GoogleFirestoreStage {
+            name,
+            args,
+            options,
+        }: GoogleFirestoreStage,
+    ) -> Self {
+        Self {
+            name,
+            args,
+            options,
+        }
+    }
+}
+
+impl From<Stage> for GoogleFirestoreStage {
+    fn from(
+        Stage {
+            name,
+            args,
+            options,
+        }: Stage,
+    ) -> Self {
+        Self {
+            name,
+            args,
+            options,
+        }
+    }
+}
@@ -5,6 +5,227 @@ use std::collections::HashMap;
 
 use crate::{VariableReference, google::firestore::v1::Value};
 
+/// The encodings of a type which is serialized with [`serialize_with_handshake`].
+pub(super) trait Encodings {
+    /// Serializes in the encoding for this crate (e.g. a struct with a
+    /// private name).
+    fn serialize_private<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer;
+
+    /// Serializes in a natural encoding (e.g. an RFC 3339 string).
+    fn serialize_natural<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer;
+}
+
+/// Serializes `value` as the newtype `name`.
+///
+/// [`Serializer`](crate::Serializer) recognizes `name` and serializes the
+/// inner value with a serializer which is not human readable. Other formats
+/// see a plain newtype, so the inner value uses the natural encoding if the
+/// format is human readable (e.g. `serde_json`).
+pub(super) fn serialize_with_handshake<T, S>(
+    name: &'static str,
+    value: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    T: Encodings,
+    S: serde::Serializer,
+{
+    serializer.serialize_newtype_struct(name, &Handshake(value))
+}
+
+struct Handshake<'a, T>(&'a T);
+
+impl<T: Encodings> serde::Serialize for Handshake<'_, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if serializer.is_human_readable() {
+            self.0.serialize_natural(serializer)
+        } else {
+            self.0.serialize_private(serializer)
+        }
+    }
+}
+
+/// Serializes a `String` newtype as the newtype `name`.
+///
+/// Formats with transparent newtypes (e.g. `serde_json`) see a plain string.
+pub(super) fn serialize_string_newtype<S>(
+    name: &'static str,
+    value: &str,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_newtype_struct(name, value)
+}
+
+/// Deserializes a `String` newtype from the newtype `name` or from a plain
+/// string.
+pub(super) fn deserialize_string_newtype<'de, D>(
+    name: &'static str,
+    deserializer: D,
+) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    deserializer.deserialize_newtype_struct(name, StringNewtypeVisitor)
+}
+
+struct StringNewtypeVisitor;
+
+impl<'de> serde::de::Visitor<'de> for StringNewtypeVisitor {
+    type Value = String;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a newtype struct of String")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(v.to_owned())
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(v)
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        <String as serde::Deserialize>::deserialize(deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        seq.next_element()?
+            .ok_or_else(|| serde::de::Error::invalid_length(0, &self))
+    }
+}
+
+/// Deserializes `args` positionally as `T` (e.g. a tuple).
+pub(super) fn deserialize_args<'a, T>(args: &'a [Value]) -> Result<T, crate::Error>
+where
+    T: serde::Deserialize<'a>,
+{
+    T::deserialize(ArgsDeserializer(args))
+}
+
+/// Deserializes a list of arguments as a sequence. No arguments also
+/// deserialize as `()`.
+struct ArgsDeserializer<'a>(&'a [Value]);
+
+impl<'de> serde::Deserializer<'de> for ArgsDeserializer<'de> {
+    type Error = crate::Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        serde::de::value::SeqDeserializer::new(self.0.iter().map(crate::Deserializer::new))
+            .deserialize_any(visitor)
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        if self.0.is_empty() {
+            visitor.visit_unit()
+        } else {
+            self.deserialize_any(visitor)
+        }
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+/// Deserializes `options` by name as `T` (e.g. a struct).
+pub(super) fn deserialize_options<'a, I, T>(options: I) -> Result<T, crate::Error>
+where
+    I: Iterator<Item = (&'a String, &'a Value)>,
+    T: serde::Deserialize<'a>,
+{
+    T::deserialize(serde::de::value::MapDeserializer::new(options.map(
+        |(k, v)| {
+            (
+                serde::de::value::BorrowedStrDeserializer::new(k.as_str()),
+                crate::Deserializer::new(v),
+            )
+        },
+    )))
+}
+
+/// Serializes `args` (e.g. a tuple) as a list of arguments. `()` serializes as
+/// no arguments.
+pub(super) fn serialize_args<T>(args: &T) -> Result<Vec<Value>, crate::Error>
+where
+    T: serde::Serialize,
+{
+    use crate::value_ext::ValueExt as _;
+
+    let value = crate::to_value(args)?;
+    if matches!(
+        value.value_type,
+        Some(crate::google::firestore::v1::value::ValueType::NullValue(_))
+    ) {
+        // `()` has no arguments.
+        return Ok(Vec::new());
+    }
+    value.into_values()
+}
+
+/// Serializes `options` (e.g. a struct) as named arguments.
+#[cfg(feature = "btree-map")]
+pub(super) fn serialize_options<T>(options: &T) -> Result<BTreeMap<String, Value>, crate::Error>
+where
+    T: serde::Serialize,
+{
+    use crate::value_ext::ValueExt as _;
+
+    crate::to_value(options)?.into_fields()
+}
+
+/// Serializes `options` (e.g. a struct) as named arguments.
+#[cfg(feature = "hash-map")]
+pub(super) fn serialize_options<T>(options: &T) -> Result<HashMap<String, Value>, crate::Error>
+where
+    T: serde::Serialize,
+{
+    use crate::value_ext::ValueExt as _;
+
+    crate::to_value(options)?.into_fields()
+}
+
 pub(super) struct ValueVecSeed;
 
 impl<'de> serde::de::DeserializeSeed<'de> for ValueVecSeed {
@@ -537,91 +758,220 @@ impl<'de> serde::de::Visitor<'de> for ValueVisitor {
     }
 }
 
-pub(super) struct ValueWrapper<'a>(pub &'a Value);
+/// The prefix of the private names of this crate.
+const PRIVATE_PREFIX: &str = "$__serde-firestore-value_private_";
+
+/// Serializes a [`Value`].
+///
+/// By default, Firestore-specific values use the private names which
+/// [`Serializer`](crate::Serializer) recognizes. With [`ValueWrapper::marked`],
+/// they are maps with private marker keys instead, which `ValueSeed` reads
+/// back from any self-describing format. The marked encoding rejects values
+/// which it cannot round-trip: a missing value type and map keys which
+/// start with the private prefix.
+#[derive(Clone, Copy)]
+pub(super) struct ValueWrapper<'a> {
+    value: &'a Value,
+    marked: bool,
+}
+
+impl<'a> ValueWrapper<'a> {
+    pub(super) fn new(value: &'a Value) -> Self {
+        Self {
+            value,
+            marked: false,
+        }
+    }
+
+    pub(super) fn marked(value: &'a Value) -> Self {
+        Self {
+            value,
+            marked: true,
+        }
+    }
+
+    fn child<'b>(&self, value: &'b Value) -> ValueWrapper<'b> {
+        ValueWrapper {
+            value,
+            marked: self.marked,
+        }
+    }
+
+    fn values<'b>(&self, values: &'b [Value]) -> Vec<ValueWrapper<'b>> {
+        values.iter().map(|value| self.child(value)).collect()
+    }
+
+    fn reference<S>(&self, serializer: S, name: &'static str, v: &String) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if self.marked {
+            use serde::ser::SerializeMap;
+            let mut map = serializer.serialize_map(Some(1))?;
+            map.serialize_entry(name, v)?;
+            map.end()
+        } else {
+            serializer.serialize_newtype_struct(name, v)
+        }
+    }
+
+    #[cfg(feature = "btree-map")]
+    fn fields<'b>(&self, fields: &'b BTreeMap<String, Value>) -> FieldsWrapper<'b> {
+        FieldsWrapper {
+            fields,
+            marked: self.marked,
+        }
+    }
+
+    #[cfg(feature = "hash-map")]
+    fn fields<'b>(&self, fields: &'b HashMap<String, Value>) -> FieldsWrapper<'b> {
+        FieldsWrapper {
+            fields,
+            marked: self.marked,
+        }
+    }
+}
+
+/// Serializes a struct with the private name `$name`, or a map with the
+/// marker key `$name` if `$marked`.
+macro_rules! object {
+    ($serializer:expr, $marked:expr, $name:expr, { $($key:literal => $value:expr),* $(,)? }) => {{
+        let len = [$($key),*].len();
+        if $marked {
+            use serde::ser::SerializeMap;
+            let mut map = $serializer.serialize_map(Some(len + 1))?;
+            map.serialize_entry($name, &())?;
+            $(map.serialize_entry($key, $value)?;)*
+            map.end()
+        } else {
+            use serde::ser::SerializeStruct;
+            let mut state = $serializer.serialize_struct($name, len)?;
+            $(state.serialize_field($key, $value)?;)*
+            state.end()
+        }
+    }};
+}
 
 impl serde::Serialize for ValueWrapper<'_> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        use serde::ser::{SerializeMap, SerializeSeq, SerializeStruct};
+        use serde::ser::SerializeSeq;
 
         use crate::google::firestore::v1::value::ValueType;
-        use crate::typ::{FieldReference, Function, LatLng, Pipeline, Reference, Stage, Timestamp};
+        use crate::typ::{FieldReference, Function, LatLng, Pipeline, Reference, Timestamp};
 
-        match &self.0.value_type {
+        match &self.value.value_type {
+            None if self.marked => Err(serde::ser::Error::custom("value type must be some")),
             None => serializer.serialize_none(),
+            Some(ValueType::NullValue(_)) if self.marked => serializer.serialize_unit(),
             Some(ValueType::NullValue(_)) => serializer.serialize_none(),
             Some(ValueType::BooleanValue(v)) => serializer.serialize_bool(*v),
             Some(ValueType::IntegerValue(v)) => serializer.serialize_i64(*v),
             Some(ValueType::DoubleValue(v)) => serializer.serialize_f64(*v),
             Some(ValueType::TimestampValue(v)) => {
-                let mut state = serializer.serialize_struct(Timestamp::NAME, 2)?;
-                state.serialize_field("seconds", &v.seconds)?;
-                state.serialize_field("nanos", &v.nanos)?;
-                state.end()
+                object!(serializer, self.marked, Timestamp::NAME, {
+                    "seconds" => &v.seconds,
+                    "nanos" => &v.nanos,
+                })
             }
             Some(ValueType::StringValue(v)) => serializer.serialize_str(v),
             Some(ValueType::BytesValue(v)) => serializer.serialize_bytes(v),
-            Some(ValueType::ReferenceValue(v)) => {
-                serializer.serialize_newtype_struct(Reference::NAME, v)
-            }
-            Some(ValueType::GeoPointValue(v)) => {
-                let mut state = serializer.serialize_struct(LatLng::NAME, 2)?;
-                state.serialize_field("latitude", &v.latitude)?;
-                state.serialize_field("longitude", &v.longitude)?;
-                state.end()
-            }
+            Some(ValueType::ReferenceValue(v)) => self.reference(serializer, Reference::NAME, v),
+            Some(ValueType::GeoPointValue(v)) => object!(serializer, self.marked, LatLng::NAME, {
+                "latitude" => &v.latitude,
+                "longitude" => &v.longitude,
+            }),
             Some(ValueType::ArrayValue(v)) => {
                 let mut seq = serializer.serialize_seq(Some(v.values.len()))?;
                 for value in &v.values {
-                    seq.serialize_element(&ValueWrapper(value))?;
+                    seq.serialize_element(&self.child(value))?;
                 }
                 seq.end()
             }
-            Some(ValueType::MapValue(v)) => {
-                let mut map = serializer.serialize_map(Some(v.fields.len()))?;
-                for (k, value) in &v.fields {
-                    map.serialize_entry(k, &ValueWrapper(value))?;
-                }
-                map.end()
-            }
+            Some(ValueType::MapValue(v)) => self.fields(&v.fields).serialize(serializer),
             Some(ValueType::FieldReferenceValue(v)) => {
-                serializer.serialize_newtype_struct(FieldReference::NAME, v)
+                self.reference(serializer, FieldReference::NAME, v)
             }
             Some(ValueType::VariableReferenceValue(v)) => {
-                serializer.serialize_newtype_struct(VariableReference::NAME, v)
-            }
-            Some(ValueType::FunctionValue(v)) => {
-                let mut state = serializer.serialize_struct(Function::NAME, 3)?;
-                state.serialize_field("name", &v.name)?;
-                state.serialize_field(
-                    "args",
-                    &v.args.iter().map(ValueWrapper).collect::<Vec<_>>(),
-                )?;
-                state.serialize_field(
-                    "options",
-                    &v.options
-                        .iter()
-                        .map(|(k, v)| (k.as_str(), ValueWrapper(v)))
-                        .collect::<std::collections::HashMap<_, _>>(),
-                )?;
-                state.end()
+                self.reference(serializer, VariableReference::NAME, v)
             }
-            Some(ValueType::PipelineValue(v)) => {
-                let mut state = serializer.serialize_struct(Pipeline::NAME, 1)?;
-                let stages: Vec<Stage> = v
+            Some(ValueType::FunctionValue(v)) => object!(serializer, self.marked, Function::NAME, {
+                "name" => &v.name,
+                "args" => &self.values(&v.args),
+                "options" => &self.fields(&v.options),
+            }),
+            Some(ValueType::PipelineValue(v)) => object!(serializer, self.marked, Pipeline::NAME, {
+                "stages" => &v
                     .stages
                     .iter()
-                    .map(|s| Stage {
-                        name: s.name.clone(),
-                        args: s.args.clone(),
-                        options: s.options.clone(),
+                    .map(|stage| StageWrapper {
+                        stage,
+                        marked: self.marked,
                     })
-                    .collect();
-                state.serialize_field("stages", &stages)?;
-                state.end()
-            }
+                    .collect::<Vec<_>>(),
+            }),
+        }
+    }
+}
+
+/// Serializes the fields of a map or the options of a function or a stage.
+struct FieldsWrapper<'a> {
+    #[cfg(feature = "btree-map")]
+    fields: &'a BTreeMap<String, Value>,
+    #[cfg(feature = "hash-map")]
+    fields: &'a HashMap<String, Value>,
+    marked: bool,
+}
+
+impl serde::Serialize for FieldsWrapper<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if self.marked
+            && let Some(key) = self.fields.keys().find(|k| k.starts_with(PRIVATE_PREFIX))
+        {
+            return Err(serde::ser::Error::custom(format!(
+                "map key {key:?} is reserved"
+            )));
         }
+        let marked = self.marked;
+        serializer.collect_map(
+            self.fields
+                .iter()
+                .map(|(k, value)| (k, ValueWrapper { value, marked })),
+        )
+    }
+}
+
+/// Serializes a stage of a `pipelineValue`.
+struct StageWrapper<'a> {
+    stage: &'a crate::google::firestore::v1::pipeline::Stage,
+    marked: bool,
+}
+
+impl serde::Serialize for StageWrapper<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let marked = self.marked;
+        let args = self
+            .stage
+            .args
+            .iter()
+            .map(|value| ValueWrapper { value, marked })
+            .collect::<Vec<_>>();
+        let options = FieldsWrapper {
+            fields: &self.stage.options,
+            marked,
+        };
+        object!(serializer, self.marked, super::pipeline::Stage::NAME, {
+            "name" => &self.stage.name,
+            "args" => &args,
+            "options" => &options,
+        })
     }
 }
@@ -0,0 +1,99 @@
+use super::private::{ValueSeed, ValueWrapper};
+use crate::google::firestore::v1::Value;
+
+/// RawValue
+///
+/// A [`Value`] which is (de)serialized as is.
+///
+/// `to_value` and `from_value` round-trip any [`Value`] exactly. With other
+/// self-describing formats (e.g. `serde_json`), Firestore-specific values are
+/// encoded as maps with private marker keys so that they can be deserialized
+/// back. `bytesValue` is encoded with `serialize_bytes`, so it is read back
+/// as `bytesValue` only if the format supports bytes.
+///
+/// Serializing fails if the [`Value`] (or a nested one) has no value type,
+/// or if it contains a map key which starts with
+/// `$__serde-firestore-value_private_`, because neither can be read back.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn test_raw_value() -> anyhow::Result<()> {
+/// #     use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// #     use serde_firestore_value::{from_value, to_value, RawValue};
+/// #[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+/// struct S {
+///     payload: RawValue,
+/// }
+///
+/// let o = S {
+///     payload: RawValue(Value {
+///         value_type: Some(ValueType::ReferenceValue(
+///             "projects/p/databases/d/documents/c/1".to_string(),
+///         )),
+///     }),
+/// };
+/// let s = to_value(&o)?;
+/// let d = from_value::<'_, S>(&s)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RawValue(pub Value);
+
+impl RawValue {
+    pub(crate) const NAME: &'static str = "$__serde-firestore-value_private_raw_value";
+}
+
+impl<'de> serde::Deserialize<'de> for RawValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserialize_raw_value(deserializer).map(Self)
+    }
+}
+
+impl serde::Serialize for RawValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_raw_value(&self.0, serializer)
+    }
+}
+
+impl From<RawValue> for Value {
+    fn from(RawValue(value): RawValue) -> Self {
+        value
+    }
+}
+
+impl From<Value> for RawValue {
+    fn from(value: Value) -> Self {
+        Self(value)
+    }
+}
+
+pub(crate) fn deserialize_raw_value<'de, D>(deserializer: D) -> Result<Value, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    serde::de::DeserializeSeed::deserialize(ValueSeed, deserializer)
+}
+
+pub(crate) fn serialize_raw_value<S>(value: &Value, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    // `Serializer` recognizes the name and reads the markers back with
+    // `from_marked_value`. Other formats see a plain newtype.
+    serializer.serialize_newtype_struct(RawValue::NAME, &ValueWrapper::marked(value))
+}
+
+/// Converts a [`Value`] serialized from `ValueWrapper::marked` back to the
+/// original [`Value`].
+pub(crate) fn from_marked_value(value: &Value) -> Result<Value, crate::Error> {
+    serde::de::DeserializeSeed::deserialize(ValueSeed, crate::Deserializer::new(value))
+}
@@ -1,7 +1,12 @@
+use super::private::{deserialize_string_newtype, serialize_string_newtype};
+
 /// Reference
 ///
 /// `referenceValue` inner type.
 ///
+/// With other human-readable formats (e.g. `serde_json`), it is (de)serialized
+/// as the path string.
+///
 /// <https://firebase.google.com/docs/firestore/reference/rest/Shared.Types/ArrayValue#Value>
 ///
 /// # Examples
@@ -22,16 +27,31 @@
 /// #     Ok(())
 /// # }
 /// ```
-#[derive(
-    Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, serde::Deserialize, serde::Serialize,
-)]
-#[serde(rename = "$__serde-firestore-value_private_reference")]
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Reference(pub String);
 
 impl Reference {
     pub(crate) const NAME: &'static str = "$__serde-firestore-value_private_reference";
 }
 
+impl<'de> serde::Deserialize<'de> for Reference {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserialize_string_newtype(Self::NAME, deserializer).map(Self)
+    }
+}
+
+impl serde::Serialize for Reference {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_string_newtype(Self::NAME, &self.0, serializer)
+    }
+}
+
 impl From<Reference> for String {
     fn from(Reference(s): Reference) -> Self {
         s
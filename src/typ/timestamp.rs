@@ -1,7 +1,12 @@
+use super::private::{Encodings, serialize_with_handshake};
+
 /// Timestamp
 ///
 /// `timestampValue` inner type.
 ///
+/// With other human-readable formats (e.g. `serde_json`), it is (de)serialized
+/// as an RFC 3339 string such as `"1970-01-01T00:00:01.000000002Z"`.
+///
 /// <https://protobuf.dev/reference/protobuf/google.protobuf/#timestamp>
 /// <https://firebase.google.com/docs/firestore/reference/rest/Shared.Types/ArrayValue#Value>
 ///
@@ -28,10 +33,7 @@
 /// #     Ok(())
 /// # }
 /// ```
-#[derive(
-    Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd, serde::Deserialize, serde::Serialize,
-)]
-#[serde(rename = "$__serde-firestore-value_private_timestamp")]
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub struct Timestamp {
     /// seconds
     pub seconds: i64,
@@ -40,9 +42,123 @@ pub struct Timestamp {
 }
 
 impl Timestamp {
+    pub(crate) const FIELDS: &'static [&'static str] = &["seconds", "nanos"];
     pub(crate) const NAME: &'static str = "$__serde-firestore-value_private_timestamp";
 }
 
+impl<'de> serde::Deserialize<'de> for Timestamp {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(Self::NAME, TimestampVisitor)
+    }
+}
+
+impl serde::Serialize for Timestamp {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_with_handshake(Self::NAME, self, serializer)
+    }
+}
+
+impl Encodings for Timestamp {
+    fn serialize_private<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct(Self::NAME, Self::FIELDS.len())?;
+        state.serialize_field("seconds", &self.seconds)?;
+        state.serialize_field("nanos", &self.nanos)?;
+        state.end()
+    }
+
+    fn serialize_natural<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let s =
+            crate::rfc3339::format(self.seconds, self.nanos).map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(&s)
+    }
+}
+
+struct TimestampVisitor;
+
+impl<'de> serde::de::Visitor<'de> for TimestampVisitor {
+    type Value = Timestamp;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a Timestamp struct or an RFC 3339 string")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(self)
+        } else {
+            deserializer.deserialize_struct(Timestamp::NAME, Timestamp::FIELDS, self)
+        }
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        let (seconds, nanos) = crate::rfc3339::parse(v).map_err(E::custom)?;
+        Ok(Timestamp { seconds, nanos })
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let seconds = seq
+            .next_element()?
+            .ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        let nanos = seq
+            .next_element()?
+            .ok_or_else(|| serde::de::Error::invalid_length(1, &self))?;
+        Ok(Timestamp { seconds, nanos })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        let mut seconds: Option<i64> = None;
+        let mut nanos: Option<i32> = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "seconds" => {
+                    if seconds.is_some() {
+                        return Err(serde::de::Error::duplicate_field("seconds"));
+                    }
+                    seconds = Some(map.next_value()?);
+                }
+                "nanos" => {
+                    if nanos.is_some() {
+                        return Err(serde::de::Error::duplicate_field("nanos"));
+                    }
+                    nanos = Some(map.next_value()?);
+                }
+                _ => {
+                    let _: serde::de::IgnoredAny = map.next_value()?;
+                }
+            }
+        }
+        let seconds = seconds.ok_or_else(|| serde::de::Error::missing_field("seconds"))?;
+        let nanos = nanos.ok_or_else(|| serde::de::Error::missing_field("nanos"))?;
+        Ok(Timestamp { seconds, nanos })
+    }
+}
+
 impl From<Timestamp> for prost_types::Timestamp {
     fn from(Timestamp { seconds, nanos }: Timestamp) -> Self {
         Self { seconds, nanos }
@@ -75,6 +191,38 @@ impl std::convert::TryFrom<Timestamp> for chrono::DateTime<chrono::Utc> {
     }
 }
 
+#[cfg(feature = "jiff")]
+impl std::convert::TryFrom<Timestamp> for jiff::Timestamp {
+    type Error = crate::Error;
+
+    fn try_from(Timestamp { seconds, nanos }: Timestamp) -> Result<Self, Self::Error> {
+        if !(0..1_000_000_000).contains(&nanos) {
+            return Err(crate::Error::from(crate::error::ErrorCode::Custom(
+                format!(
+                    "jiff::Timestamp::try_from(Timestamp) / nanos out of range ({})",
+                    nanos
+                ),
+            )));
+        }
+        Self::new(seconds, nanos).map_err(|e| {
+            crate::Error::from(crate::error::ErrorCode::Custom(format!(
+                "jiff::Timestamp::try_from(Timestamp) / jiff::Timestamp::new({}, {}) : {}",
+                seconds, nanos, e
+            )))
+        })
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl std::convert::TryFrom<Timestamp> for jiff::Zoned {
+    type Error = crate::Error;
+
+    fn try_from(timestamp: Timestamp) -> Result<Self, Self::Error> {
+        jiff::Timestamp::try_from(timestamp)
+            .map(|timestamp| timestamp.to_zoned(jiff::tz::TimeZone::UTC))
+    }
+}
+
 #[cfg(feature = "time")]
 impl std::convert::TryFrom<Timestamp> for time::OffsetDateTime {
     type Error = crate::Error;
@@ -124,3 +272,100 @@ impl std::convert::TryFrom<time::OffsetDateTime> for Timestamp {
         Ok(Self { seconds, nanos })
     }
 }
+
+#[cfg(feature = "jiff")]
+impl std::convert::TryFrom<jiff::Timestamp> for Timestamp {
+    type Error = crate::Error;
+
+    fn try_from(timestamp: jiff::Timestamp) -> Result<Self, Self::Error> {
+        let timestamp_nanos = timestamp.as_nanosecond();
+        let seconds = timestamp_nanos.div_euclid(1_000_000_000_i128);
+        let nanos = timestamp_nanos.rem_euclid(1_000_000_000_i128);
+        let seconds = i64::try_from(seconds).map_err(|_| {
+            crate::Error::from(crate::error::ErrorCode::Custom(format!(
+                "Timestamp::try_from(jiff::Timestamp) / i64::try_from({})",
+                seconds
+            )))
+        })?;
+        let nanos = i32::try_from(nanos).map_err(|_| {
+            crate::Error::from(crate::error::ErrorCode::Custom(format!(
+                "Timestamp::try_from(jiff::Timestamp) / i32::try_from({})",
+                nanos
+            )))
+        })?;
+        Ok(Self { seconds, nanos })
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl std::convert::TryFrom<jiff::Zoned> for Timestamp {
+    type Error = crate::Error;
+
+    fn try_from(zoned: jiff::Zoned) -> Result<Self, Self::Error> {
+        Self::try_from(zoned.timestamp())
+    }
+}
+
+impl std::convert::TryFrom<std::time::SystemTime> for Timestamp {
+    type Error = crate::Error;
+
+    fn try_from(system_time: std::time::SystemTime) -> Result<Self, Self::Error> {
+        let out_of_range = || {
+            crate::Error::from(crate::error::ErrorCode::Custom(format!(
+                "Timestamp::try_from(std::time::SystemTime) / out of range ({:?})",
+                system_time
+            )))
+        };
+        match system_time.duration_since(std::time::UNIX_EPOCH) {
+            Ok(duration) => Ok(Self {
+                seconds: i64::try_from(duration.as_secs()).map_err(|_| out_of_range())?,
+                nanos: i32::try_from(duration.subsec_nanos()).map_err(|_| out_of_range())?,
+            }),
+            Err(e) => {
+                let duration = e.duration();
+                let seconds = i64::try_from(duration.as_secs()).map_err(|_| out_of_range())?;
+                let nanos = i32::try_from(duration.subsec_nanos()).map_err(|_| out_of_range())?;
+                if nanos == 0 {
+                    Ok(Self {
+                        seconds: -seconds,
+                        nanos: 0,
+                    })
+                } else {
+                    Ok(Self {
+                        seconds: (-seconds).checked_sub(1).ok_or_else(out_of_range)?,
+                        nanos: 1_000_000_000 - nanos,
+                    })
+                }
+            }
+        }
+    }
+}
+
+impl std::convert::TryFrom<Timestamp> for std::time::SystemTime {
+    type Error = crate::Error;
+
+    fn try_from(Timestamp { seconds, nanos }: Timestamp) -> Result<Self, Self::Error> {
+        let out_of_range = || {
+            crate::Error::from(crate::error::ErrorCode::Custom(format!(
+                "std::time::SystemTime::try_from(Timestamp) / out of range ({}, {})",
+                seconds, nanos
+            )))
+        };
+        let nanos = u32::try_from(nanos)
+            .ok()
+            .filter(|nanos| *nanos < 1_000_000_000)
+            .ok_or_else(out_of_range)?;
+        let system_time = if seconds >= 0 {
+            std::time::UNIX_EPOCH
+                .checked_add(std::time::Duration::from_secs(seconds.unsigned_abs()))
+        } else {
+            std::time::UNIX_EPOCH
+                .checked_sub(std::time::Duration::from_secs(seconds.unsigned_abs()))
+        };
+        system_time
+            .and_then(|system_time| {
+                system_time.checked_add(std::time::Duration::from_nanos(u64::from(nanos)))
+            })
+            .ok_or_else(out_of_range)
+    }
+}
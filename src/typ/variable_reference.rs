@@ -1,7 +1,12 @@
+use super::private::{deserialize_string_newtype, serialize_string_newtype};
+
 /// VariableReference
 ///
 /// `variableReferenceValue` inner type.
 ///
+/// With other human-readable formats (e.g. `serde_json`), it is (de)serialized
+/// as the variable name string.
+///
 /// <https://firebase.google.com/docs/firestore/reference/rest/Shared.Types/ArrayValue#Value>
 ///
 /// # Examples
@@ -20,16 +25,31 @@
 /// #     Ok(())
 /// # }
 /// ```
-#[derive(
-    Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, serde::Deserialize, serde::Serialize,
-)]
-#[serde(rename = "$__serde-firestore-value_private_variable_reference")]
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct VariableReference(pub String);
 
 impl VariableReference {
     pub(crate) const NAME: &'static str = "$__serde-firestore-value_private_variable_reference";
 }
 
+impl<'de> serde::Deserialize<'de> for VariableReference {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserialize_string_newtype(Self::NAME, deserializer).map(Self)
+    }
+}
+
+impl serde::Serialize for VariableReference {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serialize_string_newtype(Self::NAME, &self.0, serializer)
+    }
+}
+
 impl From<VariableReference> for String {
     fn from(VariableReference(s): VariableReference) -> Self {
         s
@@ -0,0 +1,698 @@
+//! Structural validation of pipelines, stages and functions.
+//!
+//! A [`Catalog`] describes the known stages and functions by
+//! [`Signature`]. [`Catalog::default`] knows the stages of
+//! [`stage::names`](crate::stage::names) and the functions of
+//! [`expr::names`](crate::expr::names); use [`Catalog::insert_function`] and
+//! [`Catalog::insert_stage`] to add more.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # fn main() -> anyhow::Result<()> {
+//! use serde_firestore_value::text::parse_pipeline;
+//! use serde_firestore_value::validate::Catalog;
+//!
+//! let pipeline = parse_pipeline(r#"collection("users") |> limt(10) |> limit("10")"#)?;
+//! let problems = Catalog::default().validate_pipeline(&pipeline);
+//! assert_eq!(
+//!     problems.iter().map(ToString::to_string).collect::<Vec<_>>(),
+//!     [
+//!         "stages[1]: unknown stage `limt` (did you mean `limit`?)",
+//!         "stages[2].args[0]: expected an integer, found a string value",
+//!     ]
+//! );
+//! #     Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+
+use crate::expr::names as function_names;
+use crate::google::firestore::v1::{
+    ArrayValue, MapValue, Value, pipeline::Stage as GoogleStage, value::ValueType,
+};
+use crate::stage::names as stage_names;
+use crate::value_order::{VECTOR_TYPE_ORDER, type_order};
+use crate::value_type_ext::ValueTypeExt as _;
+use crate::{Function, Pipeline, Stage};
+
+/// The kind of an argument or an option.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ArgKind {
+    /// A map of aliases to accumulator functions (`aggregate`).
+    AccumulatorMap,
+    /// Any expression: a constant, a field reference, a function or (where
+    /// allowed) a variable reference.
+    Expr,
+    /// A map of aliases to expressions.
+    ExprMap,
+    /// A `fieldReferenceValue`.
+    FieldReference,
+    /// An `integerValue`.
+    Integer,
+    /// A sort ordering `{direction: "ascending" | "descending", expression}`.
+    Ordering,
+    /// A `pipelineValue`.
+    Pipeline,
+    /// A `referenceValue`.
+    Reference,
+    /// A `stringValue`.
+    String,
+    /// A `stringValue` which is one of the given strings.
+    StringOneOf(Vec<String>),
+    /// An array of numbers, or a vector (`{__type__: "__vector__", value}`).
+    Vector,
+}
+
+/// The signature of a stage or a function.
+///
+/// # Examples
+///
+/// ```rust
+/// use serde_firestore_value::validate::{ArgKind, Signature};
+///
+/// // `my_function(expr, expr?, ...)` with an optional `mode` string option.
+/// let signature = Signature::new([ArgKind::Expr])
+///     .optional(ArgKind::Expr)
+///     .option("mode", ArgKind::String);
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Signature {
+    required: Vec<ArgKind>,
+    optional: Vec<ArgKind>,
+    rest: Option<ArgKind>,
+    options: BTreeMap<String, ArgKind>,
+    accumulator: bool,
+    allow_variables: bool,
+}
+
+impl Signature {
+    /// Creates a signature with required arguments.
+    pub fn new<I: IntoIterator<Item = ArgKind>>(required: I) -> Self {
+        Self {
+            required: required.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Marks the function as an accumulator. Accumulators are allowed only
+    /// in [`ArgKind::AccumulatorMap`].
+    pub fn accumulator(mut self) -> Self {
+        self.accumulator = true;
+        self
+    }
+
+    /// Allows variable references in the arguments and options.
+    pub fn allow_variables(mut self) -> Self {
+        self.allow_variables = true;
+        self
+    }
+
+    /// Adds a named option.
+    pub fn option<S: Into<String>>(mut self, key: S, kind: ArgKind) -> Self {
+        self.options.insert(key.into(), kind);
+        self
+    }
+
+    /// Adds an optional argument after the required ones.
+    pub fn optional(mut self, kind: ArgKind) -> Self {
+        self.optional.push(kind);
+        self
+    }
+
+    /// Allows any number of further arguments of `kind`.
+    pub fn rest(mut self, kind: ArgKind) -> Self {
+        self.rest = Some(kind);
+        self
+    }
+
+    fn arg_kind(&self, index: usize) -> Option<&ArgKind> {
+        self.required
+            .get(index)
+            .or_else(|| self.optional.get(index - self.required.len()))
+            .or(self.rest.as_ref())
+    }
+
+    fn arity(&self) -> String {
+        let min = self.required.len();
+        let max = min + self.optional.len();
+        match (self.rest.is_some(), min == max) {
+            (true, _) => format!("at least {min} argument(s)"),
+            (false, true) => format!("{min} argument(s)"),
+            (false, false) => format!("{min} to {max} arguments"),
+        }
+    }
+}
+
+/// A problem found by validation.
+#[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
+#[error("{path}: {message}")]
+pub struct Problem {
+    /// The path to the problem, e.g. `stages[1].args[0].args[1]`.
+    pub path: String,
+    /// The description of the problem.
+    pub message: String,
+}
+
+/// A catalog of known stages and functions.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Catalog {
+    functions: BTreeMap<String, Signature>,
+    stages: BTreeMap<String, Signature>,
+}
+
+impl Default for Catalog {
+    /// Returns the catalog of the built-in stages and functions.
+    fn default() -> Self {
+        use ArgKind::{
+            AccumulatorMap, Expr, ExprMap, FieldReference, Integer, Ordering, Reference, String,
+            StringOneOf, Vector,
+        };
+
+        let mut catalog = Self::empty();
+        let stages = [
+            (stage_names::ADD_FIELDS, Signature::new([ExprMap])),
+            (
+                stage_names::AGGREGATE,
+                Signature::new([AccumulatorMap, ExprMap]),
+            ),
+            (stage_names::COLLECTION, Signature::new([Reference])),
+            (
+                stage_names::COLLECTION_GROUP,
+                Signature::new([Reference, String]),
+            ),
+            (stage_names::DATABASE, Signature::new([])),
+            (stage_names::DISTINCT, Signature::new([ExprMap])),
+            (
+                stage_names::DOCUMENTS,
+                Signature::new([Reference]).rest(Reference),
+            ),
+            (
+                stage_names::FIND_NEAREST,
+                Signature::new([
+                    FieldReference,
+                    Vector,
+                    StringOneOf(
+                        ["cosine", "dot_product", "euclidean"]
+                            .map(ToString::to_string)
+                            .to_vec(),
+                    ),
+                ])
+                .option("distance_field", FieldReference)
+                .option("limit", Integer),
+            ),
+            (stage_names::LIMIT, Signature::new([Integer])),
+            (stage_names::OFFSET, Signature::new([Integer])),
+            (
+                stage_names::REMOVE_FIELDS,
+                Signature::new([FieldReference]).rest(FieldReference),
+            ),
+            (stage_names::SELECT, Signature::new([ExprMap])),
+            (stage_names::SORT, Signature::new([Ordering]).rest(Ordering)),
+            (stage_names::WHERE, Signature::new([Expr])),
+        ];
+        for (name, signature) in stages {
+            catalog.insert_stage(name, signature);
+        }
+
+        let unary = [
+            function_names::ARRAY_LENGTH,
+            function_names::ARRAY_REVERSE,
+            function_names::BYTE_LENGTH,
+            function_names::CHAR_LENGTH,
+            function_names::EXISTS,
+            function_names::IS_NAN,
+            function_names::IS_NULL,
+            function_names::NOT,
+            function_names::TO_LOWER,
+            function_names::TO_UPPER,
+            function_names::TRIM,
+        ];
+        let binary = [
+            function_names::ADD,
+            function_names::ARRAY_CONTAINS,
+            function_names::ARRAY_CONTAINS_ALL,
+            function_names::ARRAY_CONTAINS_ANY,
+            function_names::DIVIDE,
+            function_names::ENDS_WITH,
+            function_names::EQUAL,
+            function_names::EQUAL_ANY,
+            function_names::GREATER_THAN,
+            function_names::GREATER_THAN_OR_EQUAL,
+            function_names::LESS_THAN,
+            function_names::LESS_THAN_OR_EQUAL,
+            function_names::MAP_GET,
+            function_names::MOD,
+            function_names::MULTIPLY,
+            function_names::NOT_EQUAL,
+            function_names::NOT_EQUAL_ANY,
+            function_names::STARTS_WITH,
+            function_names::STRING_CONTAINS,
+            function_names::SUBTRACT,
+        ];
+        let variadic = [
+            function_names::AND,
+            function_names::ARRAY_CONCAT,
+            function_names::OR,
+            function_names::STRING_CONCAT,
+            function_names::XOR,
+        ];
+        let accumulators = [
+            function_names::AVERAGE,
+            function_names::COUNT_DISTINCT,
+            function_names::MAXIMUM,
+            function_names::MINIMUM,
+            function_names::SUM,
+        ];
+        for name in unary {
+            catalog.insert_function(name, Signature::new([Expr]));
+        }
+        for name in binary {
+            catalog.insert_function(name, Signature::new([Expr, Expr]));
+        }
+        for name in variadic {
+            catalog.insert_function(name, Signature::new([Expr, Expr]).rest(Expr));
+        }
+        for name in accumulators {
+            catalog.insert_function(name, Signature::new([Expr]).accumulator());
+        }
+        catalog.insert_function(
+            function_names::COUNT,
+            Signature::new([]).optional(Expr).accumulator(),
+        );
+        catalog.insert_function(
+            function_names::CONDITIONAL,
+            Signature::new([Expr, Expr, Expr]),
+        );
+        catalog
+    }
+}
+
+impl Catalog {
+    /// Creates a catalog without any stages or functions.
+    pub fn empty() -> Self {
+        Self {
+            functions: BTreeMap::new(),
+            stages: BTreeMap::new(),
+        }
+    }
+
+    /// Adds or replaces a function.
+    pub fn insert_function<S: Into<String>>(&mut self, name: S, signature: Signature) {
+        self.functions.insert(name.into(), signature);
+    }
+
+    /// Adds or replaces a stage.
+    pub fn insert_stage<S: Into<String>>(&mut self, name: S, signature: Signature) {
+        self.stages.insert(name.into(), signature);
+    }
+
+    /// Returns the signature of a function.
+    pub fn function(&self, name: &str) -> Option<&Signature> {
+        self.functions.get(name)
+    }
+
+    /// Returns the signature of a stage.
+    pub fn stage(&self, name: &str) -> Option<&Signature> {
+        self.stages.get(name)
+    }
+
+    /// Validates a function. Returns an empty `Vec` if there are no problems.
+    pub fn validate_function(&self, function: &Function) -> Vec<Problem> {
+        let mut validator = Validator {
+            catalog: self,
+            problems: vec![],
+        };
+        validator.call(
+            "",
+            Call {
+                name: &function.name,
+                args: &function.args,
+                options: function.options.iter().collect(),
+            },
+            false,
+            Context::default(),
+        );
+        validator.problems
+    }
+
+    /// Validates a pipeline. Returns an empty `Vec` if there are no problems.
+    pub fn validate_pipeline(&self, pipeline: &Pipeline) -> Vec<Problem> {
+        let mut validator = Validator {
+            catalog: self,
+            problems: vec![],
+        };
+        validator.stages(
+            "",
+            pipeline.stages.iter().map(Call::from),
+            Context::default(),
+        );
+        validator.problems
+    }
+
+    /// Validates a stage. Returns an empty `Vec` if there are no problems.
+    pub fn validate_stage(&self, stage: &Stage) -> Vec<Problem> {
+        let mut validator = Validator {
+            catalog: self,
+            problems: vec![],
+        };
+        validator.call("", Call::from(stage), true, Context::default());
+        validator.problems
+    }
+}
+
+struct Call<'a> {
+    name: &'a str,
+    args: &'a [Value],
+    options: Vec<(&'a String, &'a Value)>,
+}
+
+impl<'a> From<&'a Stage> for Call<'a> {
+    fn from(stage: &'a Stage) -> Self {
+        Self {
+            name: &stage.name,
+            args: &stage.args,
+            options: stage.options.iter().collect(),
+        }
+    }
+}
+
+impl<'a> From<&'a GoogleStage> for Call<'a> {
+    fn from(stage: &'a GoogleStage) -> Self {
+        Self {
+            name: &stage.name,
+            args: &stage.args,
+            options: stage.options.iter().collect(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct Context {
+    accumulator: bool,
+    variables: bool,
+}
+
+struct Validator<'a> {
+    catalog: &'a Catalog,
+    problems: Vec<Problem>,
+}
+
+impl Validator<'_> {
+    fn problem(&mut self, path: &str, message: String) {
+        self.problems.push(Problem {
+            path: path.to_string(),
+            message,
+        });
+    }
+
+    fn stages<'b, I: Iterator<Item = Call<'b>>>(
+        &mut self,
+        path: &str,
+        stages: I,
+        context: Context,
+    ) {
+        for (i, stage) in stages.enumerate() {
+            self.call(&format!("{path}stages[{i}]"), stage, true, context);
+        }
+    }
+
+    fn call(&mut self, path: &str, call: Call<'_>, is_stage: bool, context: Context) {
+        let (kind, known) = if is_stage {
+            ("stage", &self.catalog.stages)
+        } else {
+            ("function", &self.catalog.functions)
+        };
+        let Some(signature) = known.get(call.name) else {
+            let message = match suggest(call.name, known.keys()) {
+                Some(suggestion) => format!(
+                    "unknown {kind} `{}` (did you mean `{suggestion}`?)",
+                    call.name
+                ),
+                None => format!("unknown {kind} `{}`", call.name),
+            };
+            self.problem(path, message);
+            return;
+        };
+        if signature.accumulator && !context.accumulator {
+            self.problem(
+                path,
+                format!("accumulator `{}` is only allowed in `aggregate`", call.name),
+            );
+        }
+        let context = Context {
+            // Accumulators may not be nested.
+            accumulator: false,
+            variables: context.variables || signature.allow_variables,
+        };
+
+        let min = signature.required.len();
+        let max = min + signature.optional.len();
+        let len = call.args.len();
+        if len < min || (signature.rest.is_none() && len > max) {
+            self.problem(
+                path,
+                format!("`{}` expects {}, got {len}", call.name, signature.arity()),
+            );
+        }
+        for (i, arg) in call.args.iter().enumerate() {
+            if let Some(arg_kind) = signature.arg_kind(i) {
+                self.value(&member(path, &format!("args[{i}]")), arg, arg_kind, context);
+            }
+        }
+
+        let mut options = call.options;
+        options.sort_by_key(|(k, _)| *k);
+        for (key, value) in options {
+            let path = member(path, &format!("options.{key}"));
+            match signature.options.get(key) {
+                Some(arg_kind) => self.value(&path, value, arg_kind, context),
+                None => self.problem(&path, format!("unknown option `{key}` of `{}`", call.name)),
+            }
+        }
+    }
+
+    fn value(&mut self, path: &str, value: &Value, kind: &ArgKind, context: Context) {
+        let expected = match kind {
+            ArgKind::AccumulatorMap | ArgKind::ExprMap => {
+                let Some(ValueType::MapValue(MapValue { fields })) = &value.value_type else {
+                    return self.mismatch(path, "a map", value);
+                };
+                let context = Context {
+                    accumulator: *kind == ArgKind::AccumulatorMap,
+                    ..context
+                };
+                let mut fields = fields.iter().collect::<Vec<_>>();
+                fields.sort_by_key(|(k, _)| *k);
+                for (key, value) in fields {
+                    let path = format!("{path}[{key:?}]");
+                    if context.accumulator
+                        && !matches!(value.value_type, Some(ValueType::FunctionValue(_)))
+                    {
+                        self.mismatch(&path, "an accumulator function", value);
+                    } else {
+                        self.expr(&path, value, context);
+                    }
+                }
+                return;
+            }
+            ArgKind::Expr => return self.expr(path, value, context),
+            ArgKind::FieldReference => {
+                if matches!(value.value_type, Some(ValueType::FieldReferenceValue(_))) {
+                    return;
+                }
+                "a field reference"
+            }
+            ArgKind::Integer => {
+                if matches!(value.value_type, Some(ValueType::IntegerValue(_))) {
+                    return;
+                }
+                "an integer"
+            }
+            ArgKind::Ordering => {
+                let Some(ValueType::MapValue(MapValue { fields })) = &value.value_type else {
+                    return self.mismatch(path, "an ordering", value);
+                };
+                match fields.get("direction").map(|v| &v.value_type) {
+                    Some(Some(ValueType::StringValue(s)))
+                        if s == "ascending" || s == "descending" => {}
+                    _ => self.problem(
+                        &format!("{path}[\"direction\"]"),
+                        "expected \"ascending\" or \"descending\"".to_string(),
+                    ),
+                }
+                match fields.get("expression") {
+                    Some(expression) => {
+                        self.expr(&format!("{path}[\"expression\"]"), expression, context)
+                    }
+                    None => self.problem(path, "ordering has no expression".to_string()),
+                }
+                for key in fields.keys() {
+                    if key != "direction" && key != "expression" {
+                        self.problem(
+                            &format!("{path}[{key:?}]"),
+                            format!("unknown ordering key `{key}`"),
+                        );
+                    }
+                }
+                return;
+            }
+            ArgKind::Pipeline => {
+                let Some(ValueType::PipelineValue(pipeline)) = &value.value_type else {
+                    return self.mismatch(path, "a pipeline", value);
+                };
+                return self.pipeline(path, pipeline);
+            }
+            ArgKind::Reference => {
+                if matches!(value.value_type, Some(ValueType::ReferenceValue(_))) {
+                    return;
+                }
+                "a reference"
+            }
+            ArgKind::String => {
+                if matches!(value.value_type, Some(ValueType::StringValue(_))) {
+                    return;
+                }
+                "a string"
+            }
+            ArgKind::StringOneOf(strings) => {
+                if let Some(ValueType::StringValue(s)) = &value.value_type {
+                    if !strings.contains(s) {
+                        self.problem(path, format!("expected one of {strings:?}, found {s:?}"));
+                    }
+                    return;
+                }
+                "a string"
+            }
+            ArgKind::Vector => {
+                let values = match &value.value_type {
+                    Some(ValueType::ArrayValue(ArrayValue { values })) => Some(values),
+                    Some(ValueType::MapValue(MapValue { fields })) => {
+                        match (
+                            fields.get("__type__").map(|v| &v.value_type),
+                            fields.get("value").map(|v| &v.value_type),
+                        ) {
+                            (
+                                Some(Some(ValueType::StringValue(t))),
+                                Some(Some(ValueType::ArrayValue(ArrayValue { values }))),
+                            ) if t == "__vector__" => Some(values),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                };
+                if values.is_some_and(|values| {
+                    values.iter().all(|v| {
+                        matches!(
+                            v.value_type,
+                            Some(ValueType::DoubleValue(_) | ValueType::IntegerValue(_))
+                        )
+                    })
+                }) {
+                    return;
+                }
+                "a vector"
+            }
+        };
+        self.mismatch(path, expected, value);
+    }
+
+    fn mismatch(&mut self, path: &str, expected: &str, value: &Value) {
+        let found = match &value.value_type {
+            None => "no value".to_string(),
+            Some(_) if type_order(value) == VECTOR_TYPE_ORDER => "a vector value".to_string(),
+            Some(value_type) => {
+                let name = value_type.name().as_str();
+                if name.starts_with(['a', 'e', 'i', 'o', 'u']) {
+                    format!("an {name}")
+                } else {
+                    format!("a {name}")
+                }
+            }
+        };
+        self.problem(path, format!("expected {expected}, found {found}"));
+    }
+
+    fn expr(&mut self, path: &str, value: &Value, context: Context) {
+        match &value.value_type {
+            Some(ValueType::FunctionValue(function)) => self.call(
+                path,
+                Call {
+                    name: &function.name,
+                    args: &function.args,
+                    options: function.options.iter().collect(),
+                },
+                false,
+                context,
+            ),
+            Some(ValueType::VariableReferenceValue(name)) if !context.variables => {
+                self.problem(
+                    path,
+                    format!("variable reference `{name}` is not allowed here"),
+                );
+            }
+            Some(ValueType::ArrayValue(ArrayValue { values })) => {
+                for (i, value) in values.iter().enumerate() {
+                    self.expr(&format!("{path}[{i}]"), value, context);
+                }
+            }
+            Some(ValueType::MapValue(MapValue { fields })) => {
+                let mut fields = fields.iter().collect::<Vec<_>>();
+                fields.sort_by_key(|(k, _)| *k);
+                for (key, value) in fields {
+                    self.expr(&format!("{path}[{key:?}]"), value, context);
+                }
+            }
+            Some(ValueType::PipelineValue(pipeline)) => self.pipeline(path, pipeline),
+            _ => {}
+        }
+    }
+
+    /// Validates a nested pipeline. Variable references are allowed in
+    /// nested pipelines, where they refer to the enclosing pipeline.
+    fn pipeline(&mut self, path: &str, pipeline: &crate::google::firestore::v1::Pipeline) {
+        self.stages(
+            &format!("{path}."),
+            pipeline.stages.iter().map(Call::from),
+            Context {
+                accumulator: false,
+                variables: true,
+            },
+        );
+    }
+}
+
+/// Returns the closest name within an edit distance of a third of the
+/// length of `name` (at least 1).
+fn suggest<'a, I: Iterator<Item = &'a String>>(name: &str, names: I) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(1);
+    names
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.as_str())
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Returns the path of `name` in `path`. The root path is `""`.
+fn member(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{path}.{name}")
+    }
+}
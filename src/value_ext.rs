@@ -55,6 +55,21 @@ pub(crate) trait ValueExt {
     fn as_variable_reference_value_as_string(&self) -> Result<&String, Error>;
     fn as_variant_value(&self) -> Result<(&String, &Value), Error>;
     fn value_type(&self) -> Result<&ValueType, Error>;
+
+    fn into_bytes(self) -> Result<Vec<u8>, Error>;
+    fn into_field_reference_value_as_string(self) -> Result<String, Error>;
+    #[cfg(feature = "btree-map")]
+    fn into_fields(self) -> Result<BTreeMap<String, Value>, Error>;
+    #[cfg(feature = "hash-map")]
+    fn into_fields(self) -> Result<HashMap<String, Value>, Error>;
+    fn into_function(self) -> Result<GoogleFirestoreFunction, Error>;
+    fn into_pipeline(self) -> Result<GoogleFirestorePipeline, Error>;
+    fn into_reference_value_as_string(self) -> Result<String, Error>;
+    fn into_string(self) -> Result<String, Error>;
+    fn into_value_type(self) -> Result<ValueType, Error>;
+    fn into_values(self) -> Result<Vec<Value>, Error>;
+    fn into_variable_reference_value_as_string(self) -> Result<String, Error>;
+    fn into_variant_value(self) -> Result<(String, Value), Error>;
 }
 
 impl ValueExt for Value {
@@ -339,4 +354,122 @@ impl ValueExt for Value {
             .as_ref()
             .ok_or_else(|| Error::from(ErrorCode::ValueTypeMustBeSome))
     }
+
+    #[cfg(feature = "bytes")]
+    fn into_bytes(self) -> Result<Vec<u8>, Error> {
+        match self.into_value_type()? {
+            ValueType::BytesValue(value) => Ok(Vec::from(value)),
+            value_type => Err(Error::invalid_value_type(&value_type, ValueTypeName::Bytes)),
+        }
+    }
+
+    #[cfg(feature = "vec-u8")]
+    fn into_bytes(self) -> Result<Vec<u8>, Error> {
+        match self.into_value_type()? {
+            ValueType::BytesValue(value) => Ok(value),
+            value_type => Err(Error::invalid_value_type(&value_type, ValueTypeName::Bytes)),
+        }
+    }
+
+    fn into_field_reference_value_as_string(self) -> Result<String, Error> {
+        match self.into_value_type()? {
+            ValueType::FieldReferenceValue(value) => Ok(value),
+            value_type => Err(Error::invalid_value_type(
+                &value_type,
+                ValueTypeName::FieldReference,
+            )),
+        }
+    }
+
+    #[cfg(feature = "btree-map")]
+    fn into_fields(self) -> Result<BTreeMap<String, Value>, Error> {
+        match self.into_value_type()? {
+            ValueType::MapValue(MapValue { fields }) => Ok(fields),
+            value_type => Err(Error::invalid_value_type(&value_type, ValueTypeName::Map)),
+        }
+    }
+
+    #[cfg(feature = "hash-map")]
+    fn into_fields(self) -> Result<HashMap<String, Value>, Error> {
+        match self.into_value_type()? {
+            ValueType::MapValue(MapValue { fields }) => Ok(fields),
+            value_type => Err(Error::invalid_value_type(&value_type, ValueTypeName::Map)),
+        }
+    }
+
+    fn into_function(self) -> Result<GoogleFirestoreFunction, Error> {
+        match self.into_value_type()? {
+            ValueType::FunctionValue(value) => Ok(value),
+            value_type => Err(Error::invalid_value_type(
+                &value_type,
+                ValueTypeName::Function,
+            )),
+        }
+    }
+
+    fn into_pipeline(self) -> Result<GoogleFirestorePipeline, Error> {
+        match self.into_value_type()? {
+            ValueType::PipelineValue(value) => Ok(value),
+            value_type => Err(Error::invalid_value_type(
+                &value_type,
+                ValueTypeName::Pipeline,
+            )),
+        }
+    }
+
+    fn into_reference_value_as_string(self) -> Result<String, Error> {
+        match self.into_value_type()? {
+            ValueType::ReferenceValue(value) => Ok(value),
+            value_type => Err(Error::invalid_value_type(
+                &value_type,
+                ValueTypeName::Reference,
+            )),
+        }
+    }
+
+    fn into_string(self) -> Result<String, Error> {
+        match self.into_value_type()? {
+            ValueType::StringValue(value) => Ok(value),
+            value_type => Err(Error::invalid_value_type(
+                &value_type,
+                ValueTypeName::String,
+            )),
+        }
+    }
+
+    fn into_value_type(self) -> Result<ValueType, Error> {
+        self.value_type
+            .ok_or_else(|| Error::from(ErrorCode::ValueTypeMustBeSome))
+    }
+
+    fn into_values(self) -> Result<Vec<Value>, Error> {
+        match self.into_value_type()? {
+            ValueType::ArrayValue(ArrayValue { values }) => Ok(values),
+            value_type => Err(Error::invalid_value_type(&value_type, ValueTypeName::Array)),
+        }
+    }
+
+    fn into_variable_reference_value_as_string(self) -> Result<String, Error> {
+        match self.into_value_type()? {
+            ValueType::VariableReferenceValue(value) => Ok(value),
+            value_type => Err(Error::invalid_value_type(
+                &value_type,
+                ValueTypeName::VariableReference,
+            )),
+        }
+    }
+
+    fn into_variant_value(self) -> Result<(String, Value), Error> {
+        let fields = self.into_fields()?;
+        if fields.len() != 1 {
+            return Err(<Error as serde::de::Error>::invalid_length(
+                fields.len(),
+                &"1",
+            ));
+        }
+        Ok(fields
+            .into_iter()
+            .next()
+            .expect("fields must have an entry"))
+    }
 }
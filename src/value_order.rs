@@ -0,0 +1,226 @@
+use std::cmp::Ordering;
+
+use crate::google::firestore::v1::{ArrayValue, MapValue, Value, value::ValueType};
+
+/// The [`type_order`] of a vector.
+pub(crate) const VECTOR_TYPE_ORDER: u8 = 9;
+
+/// Returns the type order of `value`.
+///
+/// null < boolean < number (NaN first) < timestamp < string < bytes <
+/// reference < geo point < array < vector < map
+pub(crate) fn type_order(value: &Value) -> u8 {
+    match &value.value_type {
+        None | Some(ValueType::NullValue(_)) => 0,
+        Some(ValueType::BooleanValue(_)) => 1,
+        Some(ValueType::IntegerValue(_)) | Some(ValueType::DoubleValue(_)) => 2,
+        Some(ValueType::TimestampValue(_)) => 3,
+        Some(ValueType::StringValue(_)) => 4,
+        Some(ValueType::BytesValue(_)) => 5,
+        Some(ValueType::ReferenceValue(_)) => 6,
+        Some(ValueType::GeoPointValue(_)) => 7,
+        Some(ValueType::ArrayValue(_)) => 8,
+        Some(ValueType::MapValue(map)) if is_vector(map) => VECTOR_TYPE_ORDER,
+        Some(ValueType::MapValue(_)) => 10,
+        Some(ValueType::FieldReferenceValue(_)) => 11,
+        Some(ValueType::VariableReferenceValue(_)) => 12,
+        Some(ValueType::FunctionValue(_)) => 13,
+        Some(ValueType::PipelineValue(_)) => 14,
+    }
+}
+
+/// Compares two values in Firestore's total value ordering.
+///
+/// NaN is equal to NaN and less than any other number. Integers and doubles
+/// are compared numerically.
+pub(crate) fn compare(a: &Value, b: &Value) -> Ordering {
+    let (ta, tb) = (type_order(a), type_order(b));
+    if ta != tb {
+        return ta.cmp(&tb);
+    }
+    match (&a.value_type, &b.value_type) {
+        (Some(ValueType::BooleanValue(a)), Some(ValueType::BooleanValue(b))) => a.cmp(b),
+        (Some(ValueType::IntegerValue(a)), Some(ValueType::IntegerValue(b))) => a.cmp(b),
+        (Some(ValueType::IntegerValue(a)), Some(ValueType::DoubleValue(b))) => {
+            compare_i64_f64(*a, *b)
+        }
+        (Some(ValueType::DoubleValue(a)), Some(ValueType::IntegerValue(b))) => {
+            compare_i64_f64(*b, *a).reverse()
+        }
+        (Some(ValueType::DoubleValue(a)), Some(ValueType::DoubleValue(b))) => compare_f64(*a, *b),
+        (Some(ValueType::TimestampValue(a)), Some(ValueType::TimestampValue(b))) => {
+            (a.seconds, a.nanos).cmp(&(b.seconds, b.nanos))
+        }
+        (Some(ValueType::StringValue(a)), Some(ValueType::StringValue(b))) => a.cmp(b),
+        (Some(ValueType::BytesValue(a)), Some(ValueType::BytesValue(b))) => {
+            AsRef::<[u8]>::as_ref(a).cmp(AsRef::<[u8]>::as_ref(b))
+        }
+        (Some(ValueType::ReferenceValue(a)), Some(ValueType::ReferenceValue(b))) => {
+            compare_references(a, b)
+        }
+        (Some(ValueType::GeoPointValue(a)), Some(ValueType::GeoPointValue(b))) => {
+            compare_f64(a.latitude, b.latitude).then(compare_f64(a.longitude, b.longitude))
+        }
+        (
+            Some(ValueType::ArrayValue(ArrayValue { values: a })),
+            Some(ValueType::ArrayValue(ArrayValue { values: b })),
+        ) => compare_slices(a, b),
+        (Some(ValueType::MapValue(a)), Some(ValueType::MapValue(b))) if is_vector(a) => {
+            let (a, b) = (vector_values(a), vector_values(b));
+            a.len().cmp(&b.len()).then_with(|| compare_slices(a, b))
+        }
+        (Some(ValueType::MapValue(a)), Some(ValueType::MapValue(b))) => {
+            let mut a = a.fields.iter().collect::<Vec<_>>();
+            let mut b = b.fields.iter().collect::<Vec<_>>();
+            a.sort_by_key(|(x, _)| *x);
+            b.sort_by_key(|(x, _)| *x);
+            for ((ka, va), (kb, vb)) in a.iter().zip(b.iter()) {
+                let ordering = ka.cmp(kb).then_with(|| compare(va, vb));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            a.len().cmp(&b.len())
+        }
+        (Some(ValueType::FieldReferenceValue(a)), Some(ValueType::FieldReferenceValue(b)))
+        | (
+            Some(ValueType::VariableReferenceValue(a)),
+            Some(ValueType::VariableReferenceValue(b)),
+        ) => a.cmp(b),
+        _ => Ordering::Equal,
+    }
+}
+
+/// Returns `true` if the two values are equal in Firestore's equality.
+///
+/// Unlike [`compare`], NaN is not equal to anything (including NaN). `-0.0`
+/// equals `0.0` and integers equal doubles with the same numeric value.
+pub(crate) fn equals(a: &Value, b: &Value) -> bool {
+    match (&a.value_type, &b.value_type) {
+        (Some(ValueType::DoubleValue(a)), _) if a.is_nan() => false,
+        (_, Some(ValueType::DoubleValue(b))) if b.is_nan() => false,
+        (
+            Some(ValueType::ArrayValue(ArrayValue { values: a })),
+            Some(ValueType::ArrayValue(ArrayValue { values: b })),
+        ) => a.len() == b.len() && a.iter().zip(b).all(|(a, b)| equals(a, b)),
+        (
+            Some(ValueType::MapValue(MapValue { fields: a })),
+            Some(ValueType::MapValue(MapValue { fields: b })),
+        ) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(k, va)| b.get(k).is_some_and(|vb| equals(va, vb)))
+        }
+        _ => type_order(a) == type_order(b) && compare(a, b) == Ordering::Equal,
+    }
+}
+
+/// Returns `true` if `value` is a number which is NaN.
+pub(crate) fn is_nan(value: &Value) -> bool {
+    matches!(value.value_type, Some(ValueType::DoubleValue(d)) if d.is_nan())
+}
+
+fn compare_f64(a: f64, b: f64) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        (false, false) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+    }
+}
+
+fn compare_i64_f64(a: i64, b: f64) -> Ordering {
+    if b.is_nan() {
+        return Ordering::Greater;
+    }
+    // 2^63 is exactly representable as f64.
+    if b >= 9_223_372_036_854_775_808.0 {
+        return Ordering::Less;
+    }
+    if b < -9_223_372_036_854_775_808.0 {
+        return Ordering::Greater;
+    }
+    let truncated = b.trunc();
+    // `truncated` is in the i64 range here.
+    a.cmp(&(truncated as i64)).then_with(|| {
+        if b > truncated {
+            Ordering::Less
+        } else if b < truncated {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    })
+}
+
+fn compare_references(a: &str, b: &str) -> Ordering {
+    let mut a = a.split('/');
+    let mut b = b.split('/');
+    loop {
+        match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => match x.cmp(y) {
+                Ordering::Equal => {}
+                ordering => return ordering,
+            },
+        }
+    }
+}
+
+fn compare_slices(a: &[Value], b: &[Value]) -> Ordering {
+    for (a, b) in a.iter().zip(b) {
+        let ordering = compare(a, b);
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+fn is_vector(map: &MapValue) -> bool {
+    matches!(
+        map.fields.get("__type__").and_then(|v| v.value_type.as_ref()),
+        Some(ValueType::StringValue(s)) if s == "__vector__"
+    )
+}
+
+fn vector_values(map: &MapValue) -> &[Value] {
+    match map.fields.get("value").and_then(|v| v.value_type.as_ref()) {
+        Some(ValueType::ArrayValue(ArrayValue { values })) => values,
+        _ => &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn int(i: i64) -> Value {
+        Value {
+            value_type: Some(ValueType::IntegerValue(i)),
+        }
+    }
+
+    fn double(d: f64) -> Value {
+        Value {
+            value_type: Some(ValueType::DoubleValue(d)),
+        }
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(compare(&int(1), &double(1.0)), Ordering::Equal);
+        assert_eq!(compare(&int(1), &double(1.5)), Ordering::Less);
+        assert_eq!(compare(&int(-1), &double(-1.5)), Ordering::Greater);
+        assert_eq!(compare(&double(f64::NAN), &int(i64::MIN)), Ordering::Less);
+        assert_eq!(
+            compare(&double(f64::NAN), &double(f64::NAN)),
+            Ordering::Equal
+        );
+        assert_eq!(compare(&int(i64::MAX), &double(9.3e18)), Ordering::Less);
+        assert!(equals(&double(-0.0), &int(0)));
+        assert!(!equals(&double(f64::NAN), &double(f64::NAN)));
+    }
+}
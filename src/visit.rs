@@ -0,0 +1,318 @@
+//! Read-only traversal of Firestore Values.
+//!
+//! Implement [`ValueVisitor`] and override only the methods for the value
+//! types you are interested in. The default methods recurse into arrays,
+//! maps, functions, and pipelines.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # fn main() -> anyhow::Result<()> {
+//! use serde_firestore_value::google::firestore::v1::Value;
+//! use serde_firestore_value::visit::{ValuePath, ValueVisitor};
+//! use serde_firestore_value::{to_value, Timestamp};
+//!
+//! #[derive(serde::Serialize)]
+//! struct S {
+//!     created_at: Timestamp,
+//!     items: Vec<Timestamp>,
+//! }
+//!
+//! #[derive(Default)]
+//! struct CollectTimestamps(Vec<String>);
+//!
+//! impl<'v> ValueVisitor<'v> for CollectTimestamps {
+//!     fn visit_timestamp(&mut self, path: &ValuePath<'v>, _: &'v prost_types::Timestamp) {
+//!         self.0.push(path.to_string());
+//!     }
+//! }
+//!
+//! let value = to_value(&S {
+//!     created_at: Timestamp { seconds: 1, nanos: 0 },
+//!     items: vec![Timestamp { seconds: 2, nanos: 0 }],
+//! })?;
+//! let mut visitor = CollectTimestamps::default();
+//! visitor.visit_value(&mut ValuePath::new(), &value);
+//! visitor.0.sort();
+//! assert_eq!(visitor.0, vec!["created_at", "items[0]"]);
+//! #     Ok(())
+//! # }
+//! ```
+
+use crate::google::{
+    firestore::v1::{
+        ArrayValue, Function, MapValue, Pipeline, Value, pipeline::Stage, value::ValueType,
+    },
+    r#type::LatLng,
+};
+
+/// A segment of a [`ValuePath`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PathSegment<'v> {
+    /// A key of a `mapValue`.
+    Field(&'v str),
+    /// An index of an `arrayValue`.
+    Index(usize),
+    /// An index of `Function.args`.
+    FunctionArg(usize),
+    /// A key of `Function.options`.
+    FunctionOption(&'v str),
+    /// An index of `Pipeline.stages`.
+    Stage(usize),
+    /// An index of `Stage.args`.
+    StageArg(usize),
+    /// A key of `Stage.options`.
+    StageOption(&'v str),
+}
+
+/// The location of a value within the root value being visited.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ValuePath<'v> {
+    segments: Vec<PathSegment<'v>>,
+}
+
+impl<'v> ValuePath<'v> {
+    /// Creates an empty (root) path.
+    pub fn new() -> Self {
+        Self {
+            segments: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if this path points to the root value.
+    pub fn is_root(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Returns the last segment of this path.
+    pub fn last(&self) -> Option<&PathSegment<'v>> {
+        self.segments.last()
+    }
+
+    /// Returns the segments of this path.
+    pub fn segments(&self) -> &[PathSegment<'v>] {
+        &self.segments
+    }
+
+    fn with<F>(&mut self, segment: PathSegment<'v>, f: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        self.segments.push(segment);
+        f(self);
+        self.segments.pop();
+    }
+}
+
+impl std::fmt::Display for ValuePath<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, segment) in self.segments.iter().enumerate() {
+            match segment {
+                PathSegment::Field(key) => {
+                    if i != 0 {
+                        f.write_str(".")?;
+                    }
+                    f.write_str(key)?;
+                }
+                PathSegment::Index(index) => write!(f, "[{}]", index)?,
+                PathSegment::FunctionArg(index) => write!(f, "(args[{}])", index)?,
+                PathSegment::FunctionOption(key) => write!(f, "(options.{})", key)?,
+                PathSegment::Stage(index) => write!(f, "(stages[{}])", index)?,
+                PathSegment::StageArg(index) => write!(f, "(args[{}])", index)?,
+                PathSegment::StageOption(key) => write!(f, "(options.{})", key)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A read-only visitor over a [`Value`] tree.
+///
+/// All methods have default implementations. `visit_value`, `visit_array`,
+/// `visit_map`, `visit_function`, `visit_pipeline` and `visit_stage` recurse
+/// via the `walk_*` functions in this module. The leaf methods do nothing.
+pub trait ValueVisitor<'v> {
+    /// Visits any value. Dispatches to the method for its value type.
+    fn visit_value(&mut self, path: &mut ValuePath<'v>, value: &'v Value) {
+        walk_value(self, path, value)
+    }
+
+    /// Visits a `nullValue`.
+    fn visit_null(&mut self, path: &ValuePath<'v>) {
+        let _ = path;
+    }
+
+    /// Visits a `booleanValue`.
+    fn visit_boolean(&mut self, path: &ValuePath<'v>, value: bool) {
+        let _ = (path, value);
+    }
+
+    /// Visits an `integerValue`.
+    fn visit_integer(&mut self, path: &ValuePath<'v>, value: i64) {
+        let _ = (path, value);
+    }
+
+    /// Visits a `doubleValue`.
+    fn visit_double(&mut self, path: &ValuePath<'v>, value: f64) {
+        let _ = (path, value);
+    }
+
+    /// Visits a `timestampValue`.
+    fn visit_timestamp(&mut self, path: &ValuePath<'v>, value: &'v prost_types::Timestamp) {
+        let _ = (path, value);
+    }
+
+    /// Visits a `stringValue`.
+    fn visit_string(&mut self, path: &ValuePath<'v>, value: &'v str) {
+        let _ = (path, value);
+    }
+
+    /// Visits a `bytesValue`.
+    fn visit_bytes(&mut self, path: &ValuePath<'v>, value: &'v [u8]) {
+        let _ = (path, value);
+    }
+
+    /// Visits a `referenceValue`.
+    fn visit_reference(&mut self, path: &ValuePath<'v>, value: &'v str) {
+        let _ = (path, value);
+    }
+
+    /// Visits a `geoPointValue`.
+    fn visit_geo_point(&mut self, path: &ValuePath<'v>, value: &'v LatLng) {
+        let _ = (path, value);
+    }
+
+    /// Visits an `arrayValue`.
+    fn visit_array(&mut self, path: &mut ValuePath<'v>, value: &'v ArrayValue) {
+        walk_array(self, path, value)
+    }
+
+    /// Visits a `mapValue`.
+    fn visit_map(&mut self, path: &mut ValuePath<'v>, value: &'v MapValue) {
+        walk_map(self, path, value)
+    }
+
+    /// Visits a `fieldReferenceValue`.
+    fn visit_field_reference(&mut self, path: &ValuePath<'v>, value: &'v str) {
+        let _ = (path, value);
+    }
+
+    /// Visits a `variableReferenceValue`.
+    fn visit_variable_reference(&mut self, path: &ValuePath<'v>, value: &'v str) {
+        let _ = (path, value);
+    }
+
+    /// Visits a `functionValue`.
+    fn visit_function(&mut self, path: &mut ValuePath<'v>, value: &'v Function) {
+        walk_function(self, path, value)
+    }
+
+    /// Visits a `pipelineValue`.
+    fn visit_pipeline(&mut self, path: &mut ValuePath<'v>, value: &'v Pipeline) {
+        walk_pipeline(self, path, value)
+    }
+
+    /// Visits a stage of a `pipelineValue`.
+    fn visit_stage(&mut self, path: &mut ValuePath<'v>, value: &'v Stage) {
+        walk_stage(self, path, value)
+    }
+}
+
+/// Dispatches `value` to the [`ValueVisitor`] method for its value type.
+///
+/// A value without a value type is skipped.
+pub fn walk_value<'v, V>(visitor: &mut V, path: &mut ValuePath<'v>, value: &'v Value)
+where
+    V: ValueVisitor<'v> + ?Sized,
+{
+    let Some(value_type) = value.value_type.as_ref() else {
+        return;
+    };
+    match value_type {
+        ValueType::NullValue(_) => visitor.visit_null(path),
+        ValueType::BooleanValue(v) => visitor.visit_boolean(path, *v),
+        ValueType::IntegerValue(v) => visitor.visit_integer(path, *v),
+        ValueType::DoubleValue(v) => visitor.visit_double(path, *v),
+        ValueType::TimestampValue(v) => visitor.visit_timestamp(path, v),
+        ValueType::StringValue(v) => visitor.visit_string(path, v),
+        ValueType::BytesValue(v) => visitor.visit_bytes(path, v),
+        ValueType::ReferenceValue(v) => visitor.visit_reference(path, v),
+        ValueType::GeoPointValue(v) => visitor.visit_geo_point(path, v),
+        ValueType::ArrayValue(v) => visitor.visit_array(path, v),
+        ValueType::MapValue(v) => visitor.visit_map(path, v),
+        ValueType::FieldReferenceValue(v) => visitor.visit_field_reference(path, v),
+        ValueType::VariableReferenceValue(v) => visitor.visit_variable_reference(path, v),
+        ValueType::FunctionValue(v) => visitor.visit_function(path, v),
+        ValueType::PipelineValue(v) => visitor.visit_pipeline(path, v),
+    }
+}
+
+/// Visits each element of an `arrayValue`.
+pub fn walk_array<'v, V>(visitor: &mut V, path: &mut ValuePath<'v>, value: &'v ArrayValue)
+where
+    V: ValueVisitor<'v> + ?Sized,
+{
+    for (index, value) in value.values.iter().enumerate() {
+        path.with(PathSegment::Index(index), |path| {
+            visitor.visit_value(path, value)
+        });
+    }
+}
+
+/// Visits each field of a `mapValue`.
+pub fn walk_map<'v, V>(visitor: &mut V, path: &mut ValuePath<'v>, value: &'v MapValue)
+where
+    V: ValueVisitor<'v> + ?Sized,
+{
+    for (key, value) in value.fields.iter() {
+        path.with(PathSegment::Field(key), |path| {
+            visitor.visit_value(path, value)
+        });
+    }
+}
+
+/// Visits the args and options of a `functionValue`.
+pub fn walk_function<'v, V>(visitor: &mut V, path: &mut ValuePath<'v>, value: &'v Function)
+where
+    V: ValueVisitor<'v> + ?Sized,
+{
+    for (index, value) in value.args.iter().enumerate() {
+        path.with(PathSegment::FunctionArg(index), |path| {
+            visitor.visit_value(path, value)
+        });
+    }
+    for (key, value) in value.options.iter() {
+        path.with(PathSegment::FunctionOption(key), |path| {
+            visitor.visit_value(path, value)
+        });
+    }
+}
+
+/// Visits each stage of a `pipelineValue`.
+pub fn walk_pipeline<'v, V>(visitor: &mut V, path: &mut ValuePath<'v>, value: &'v Pipeline)
+where
+    V: ValueVisitor<'v> + ?Sized,
+{
+    for (index, stage) in value.stages.iter().enumerate() {
+        path.with(PathSegment::Stage(index), |path| {
+            visitor.visit_stage(path, stage)
+        });
+    }
+}
+
+/// Visits the args and options of a stage.
+pub fn walk_stage<'v, V>(visitor: &mut V, path: &mut ValuePath<'v>, value: &'v Stage)
+where
+    V: ValueVisitor<'v> + ?Sized,
+{
+    for (index, value) in value.args.iter().enumerate() {
+        path.with(PathSegment::StageArg(index), |path| {
+            visitor.visit_value(path, value)
+        });
+    }
+    for (key, value) in value.options.iter() {
+        path.with(PathSegment::StageOption(key), |path| {
+            visitor.visit_value(path, value)
+        });
+    }
+}
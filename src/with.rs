@@ -69,19 +69,77 @@
 //! # }
 //! ```
 
+#[cfg(feature = "bigdecimal")]
+pub mod big_decimal_as_scaled_integer;
+#[cfg(feature = "bigdecimal")]
+pub mod big_decimal_as_string;
+pub mod bytes;
 #[cfg(feature = "chrono")]
 pub mod chrono_date_time_as_timestamp;
+#[cfg(feature = "chrono")]
+pub mod chrono_fixed_offset_date_time_as_string;
+#[cfg(feature = "chrono")]
+pub mod chrono_fixed_offset_date_time_as_timestamp;
+#[cfg(feature = "chrono")]
+pub mod chrono_naive_date_as_string;
+#[cfg(feature = "chrono")]
+pub mod chrono_naive_date_as_timestamp;
+#[cfg(feature = "chrono")]
+pub mod chrono_naive_date_time_as_string;
+#[cfg(feature = "chrono")]
+pub mod chrono_naive_date_time_as_timestamp;
+#[cfg(feature = "decimal")]
+pub mod decimal_as_scaled_integer;
+#[cfg(feature = "decimal")]
+pub mod decimal_as_string;
+pub mod duration;
+#[cfg(feature = "geo-types")]
+pub mod geo_point;
+#[cfg(feature = "jiff")]
+pub mod jiff_timestamp_as_timestamp;
+#[cfg(feature = "serde_json")]
+pub mod json_string;
 pub mod lat_lng;
+#[cfg(feature = "geo-types")]
+pub mod line_string_as_geo_points;
+pub mod option_bytes;
 #[cfg(feature = "chrono")]
 pub mod option_chrono_date_time_as_timestamp;
+#[cfg(feature = "jiff")]
+pub mod option_jiff_timestamp_as_timestamp;
 pub mod option_lat_lng;
 pub mod option_string_as_reference;
+pub mod option_system_time_as_timestamp;
 #[cfg(feature = "time")]
 pub mod option_time_offset_date_time_as_timestamp;
 pub mod option_timestamp;
+#[cfg(feature = "uuid")]
+pub mod option_uuid_as_bytes;
+#[cfg(feature = "uuid")]
+pub mod option_uuid_as_string;
+pub mod proto_bytes;
+pub mod raw_value;
 pub mod string_as_field_reference;
 pub mod string_as_reference;
+pub mod system_time_as_timestamp;
+#[cfg(feature = "time")]
+pub mod time_date_as_string;
+#[cfg(feature = "time")]
+pub mod time_date_as_timestamp;
 #[cfg(feature = "time")]
 pub mod time_offset_date_time_as_timestamp;
+#[cfg(feature = "time")]
+pub mod time_primitive_date_time_as_string;
+#[cfg(feature = "time")]
+pub mod time_primitive_date_time_as_timestamp;
 pub mod timestamp;
+#[cfg(feature = "uuid")]
+pub mod uuid_as_bytes;
+#[cfg(feature = "uuid")]
+pub mod uuid_as_string;
+pub mod vec_bytes;
 pub mod vec_string_as_reference;
+#[cfg(feature = "uuid")]
+pub mod vec_uuid_as_bytes;
+#[cfg(feature = "uuid")]
+pub mod vec_uuid_as_string;
@@ -0,0 +1,111 @@
+//! (De)serialize `bigdecimal::BigDecimal` as `integerValue` with a fixed scale.
+//!
+//! The value is stored as `decimal * 10^SCALE`, e.g. `12.50` with `SCALE = 2`
+//! is stored as `1250`. The scale is a const generic parameter, so specify
+//! it with `serialize_with` and `deserialize_with`:
+//!
+//! ```rust
+//! use serde_firestore_value::with::big_decimal_as_scaled_integer;
+//!
+//! #[derive(serde::Deserialize, serde::Serialize)]
+//! struct Price {
+//!     #[serde(
+//!         deserialize_with = "big_decimal_as_scaled_integer::deserialize::<2, _>",
+//!         serialize_with = "big_decimal_as_scaled_integer::serialize::<2, _>"
+//!     )]
+//!     amount: bigdecimal::BigDecimal,
+//! }
+//! ```
+//!
+//! Serialization fails instead of rounding if the value has more than
+//! `SCALE` significant fractional digits or does not fit in `i64`.
+
+use bigdecimal::ToPrimitive as _;
+use serde::{Deserialize as _, Serialize as _, ser::Error as _};
+
+/// Deserialize `bigdecimal::BigDecimal` from `integerValue` with a fixed scale.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{from_value, with::big_decimal_as_scaled_integer};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(
+///     #[serde(deserialize_with = "big_decimal_as_scaled_integer::deserialize::<2, _>")]
+///     bigdecimal::BigDecimal,
+/// );
+///
+/// let o = S("12.50".parse::<bigdecimal::BigDecimal>()?);
+/// let v = Value {
+///     value_type: Some(ValueType::IntegerValue(1250)),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, const SCALE: u32, D>(
+    deserializer: D,
+) -> Result<bigdecimal::BigDecimal, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let scaled = i64::deserialize(deserializer)?;
+    Ok(bigdecimal::BigDecimal::new(
+        bigdecimal::num_bigint::BigInt::from(scaled),
+        i64::from(SCALE),
+    ))
+}
+
+/// Serialize `bigdecimal::BigDecimal` as `integerValue` with a fixed scale.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{to_value, with::big_decimal_as_scaled_integer};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(
+///     #[serde(serialize_with = "big_decimal_as_scaled_integer::serialize::<2, _>")]
+///     bigdecimal::BigDecimal,
+/// );
+///
+/// let o = S("12.5".parse::<bigdecimal::BigDecimal>()?);
+/// let v = Value {
+///     value_type: Some(ValueType::IntegerValue(1250)),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<const SCALE: u32, S>(
+    decimal: &bigdecimal::BigDecimal,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let scaled = decimal.with_scale(i64::from(SCALE));
+    if scaled != *decimal {
+        return Err(S::Error::custom(format!(
+            "decimal {} loses precision with scale {}",
+            decimal, SCALE
+        )));
+    }
+    let (digits, _) = scaled.into_bigint_and_exponent();
+    digits
+        .to_i64()
+        .ok_or_else(|| {
+            S::Error::custom(format!(
+                "decimal {} is out of range with scale {}",
+                decimal, SCALE
+            ))
+        })?
+        .serialize(serializer)
+}
@@ -0,0 +1,73 @@
+//! (De)serialize `bigdecimal::BigDecimal` as `stringValue`.
+//!
+//! The string is the canonical decimal notation, e.g. `-12.50`. The scale
+//! is kept, so trailing zeros survive a round trip. Exponents, a leading
+//! `+`, whitespace, redundant leading zeros and negative zero are rejected
+//! on deserialization.
+
+use serde::{Deserialize as _, Serialize as _, de::Error as _};
+
+/// Deserialize `bigdecimal::BigDecimal` from `stringValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{from_value, with::big_decimal_as_string};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(
+///     #[serde(deserialize_with = "big_decimal_as_string::deserialize")] bigdecimal::BigDecimal,
+/// );
+///
+/// let o = S("12.50".parse::<bigdecimal::BigDecimal>()?);
+/// let v = Value {
+///     value_type: Some(ValueType::StringValue("12.50".to_string())),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D>(deserializer: D) -> Result<bigdecimal::BigDecimal, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    if !crate::decimal::is_canonical(&s) {
+        return Err(D::Error::custom(format!("invalid decimal `{}`", s)));
+    }
+    s.parse::<bigdecimal::BigDecimal>()
+        .map_err(|e| D::Error::custom(format!("invalid decimal `{}`: {}", s, e)))
+}
+
+/// Serialize `bigdecimal::BigDecimal` as `stringValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{to_value, with::big_decimal_as_string};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(
+///     #[serde(serialize_with = "big_decimal_as_string::serialize")] bigdecimal::BigDecimal,
+/// );
+///
+/// let o = S("12.50".parse::<bigdecimal::BigDecimal>()?);
+/// let v = Value {
+///     value_type: Some(ValueType::StringValue("12.50".to_string())),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(decimal: &bigdecimal::BigDecimal, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    decimal.to_plain_string().serialize(serializer)
+}
@@ -0,0 +1,64 @@
+//! (De)serialize `T` as `bytesValue`.
+//!
+//! `T` is any type that implements `AsRef<[u8]>` and `From<Vec<u8>>`, such as
+//! `Vec<u8>`, `Box<[u8]>` and `prost::bytes::Bytes`.
+
+/// Deserialize `T` from `bytesValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, ArrayValue, Value};
+/// use serde_firestore_value::{from_value, with::bytes};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(#[serde(deserialize_with = "bytes::deserialize")] Vec<u8>);
+///
+/// let o = S(vec![1_u8, 2_u8]);
+/// let v = Value {
+///     value_type: Some(ValueType::BytesValue(vec![1_u8, 2_u8])),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: From<Vec<u8>>,
+{
+    crate::de::with::bytes::deserialize_bytes(deserializer)
+}
+
+/// Serialize `T` as `bytesValue`.
+///
+/// It is an error if the length exceeds 1,048,487 bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, ArrayValue, Value};
+/// use serde_firestore_value::{to_value, with::bytes};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(#[serde(serialize_with = "bytes::serialize")] Vec<u8>);
+///
+/// let o = S(vec![1_u8, 2_u8]);
+/// let v = Value {
+///     value_type: Some(ValueType::BytesValue(vec![1_u8, 2_u8])),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S, T>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    T: AsRef<[u8]>,
+{
+    crate::ser::with::bytes::serialize_bytes(bytes, serializer)
+}
@@ -0,0 +1,77 @@
+//! (De)serialize `chrono::DateTime<chrono::FixedOffset>` as `stringValue`.
+//!
+//! The string is formatted as RFC 3339 and keeps the offset.
+//! Strings without an offset are rejected.
+
+use serde::{Deserialize as _, Serialize as _, de::Error as _, ser::Error as _};
+
+/// Deserialize `chrono::DateTime<chrono::FixedOffset>` from `stringValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{from_value, with::chrono_fixed_offset_date_time_as_string};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(#[serde(deserialize_with = "chrono_fixed_offset_date_time_as_string::deserialize")] chrono::DateTime<chrono::FixedOffset>);
+///
+/// let o = S(chrono::DateTime::parse_from_rfc3339("2000-01-02T03:04:05+09:00")?);
+/// let v = Value {
+///     value_type: Some(ValueType::StringValue("2000-01-02T03:04:05+09:00".to_string())),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D>(
+    deserializer: D,
+) -> Result<chrono::DateTime<chrono::FixedOffset>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    chrono::DateTime::parse_from_rfc3339(&s)
+        .map_err(|e| D::Error::custom(format!("invalid RFC 3339 date time `{}`: {}", s, e)))
+}
+
+/// Serialize `chrono::DateTime<chrono::FixedOffset>` as `stringValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{to_value, with::chrono_fixed_offset_date_time_as_string};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(#[serde(serialize_with = "chrono_fixed_offset_date_time_as_string::serialize")] chrono::DateTime<chrono::FixedOffset>);
+///
+/// let o = S(chrono::DateTime::parse_from_rfc3339("2000-01-02T03:04:05+09:00")?);
+/// let v = Value {
+///     value_type: Some(ValueType::StringValue("2000-01-02T03:04:05+09:00".to_string())),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(
+    date_time: &chrono::DateTime<chrono::FixedOffset>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    if !(0..=9999).contains(&chrono::Datelike::year(date_time)) {
+        return Err(S::Error::custom(format!(
+            "date time out of range ({})",
+            date_time
+        )));
+    }
+    date_time
+        .to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
+        .serialize(serializer)
+}
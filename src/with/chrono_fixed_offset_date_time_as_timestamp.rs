@@ -0,0 +1,81 @@
+//! (De)serialize `chrono::DateTime<chrono::FixedOffset>` as `timestampValue`.
+//!
+//! `timestampValue` has no offset. The offset is dropped on serialization
+//! and the deserialized value always has the offset `+00:00`.
+
+use serde::{de::Error as _, ser::Error as _};
+
+use crate::Timestamp;
+
+/// Deserialize `chrono::DateTime<chrono::FixedOffset>` from `timestampValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{from_value, with::chrono_fixed_offset_date_time_as_timestamp};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(#[serde(deserialize_with = "chrono_fixed_offset_date_time_as_timestamp::deserialize")] chrono::DateTime<chrono::FixedOffset>);
+///
+/// let o = S(chrono::DateTime::parse_from_rfc3339("1970-01-01T00:00:01.000000002Z")?);
+/// let v = Value {
+///     value_type: Some(ValueType::TimestampValue(prost_types::Timestamp {
+///         seconds: 1_i64,
+///         nanos: 2_i32,
+///     })),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D>(
+    deserializer: D,
+) -> Result<chrono::DateTime<chrono::FixedOffset>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    chrono::DateTime::<chrono::Utc>::try_from(Timestamp::from(crate::with::timestamp::deserialize(
+        deserializer,
+    )?))
+    .map(|date_time| date_time.fixed_offset())
+    .map_err(D::Error::custom)
+}
+
+/// Serialize `chrono::DateTime<chrono::FixedOffset>` as `timestampValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{to_value, with::chrono_fixed_offset_date_time_as_timestamp};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(#[serde(serialize_with = "chrono_fixed_offset_date_time_as_timestamp::serialize")] chrono::DateTime<chrono::FixedOffset>);
+///
+/// let o = S(chrono::DateTime::parse_from_rfc3339("1970-01-01T00:00:01.000000002Z")?);
+/// let v = Value {
+///     value_type: Some(ValueType::TimestampValue(prost_types::Timestamp {
+///         seconds: 1_i64,
+///         nanos: 2_i32,
+///     })),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(
+    date_time: &chrono::DateTime<chrono::FixedOffset>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let timestamp =
+        Timestamp::try_from(date_time.with_timezone(&chrono::Utc)).map_err(S::Error::custom)?;
+    crate::with::timestamp::serialize(&prost_types::Timestamp::from(timestamp), serializer)
+}
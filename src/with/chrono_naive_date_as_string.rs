@@ -0,0 +1,66 @@
+//! (De)serialize `chrono::NaiveDate` as `stringValue`.
+//!
+//! The string is formatted as `YYYY-MM-DD`.
+
+use serde::{Deserialize as _, Serialize as _, de::Error as _, ser::Error as _};
+
+/// Deserialize `chrono::NaiveDate` from `stringValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{from_value, with::chrono_naive_date_as_string};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(#[serde(deserialize_with = "chrono_naive_date_as_string::deserialize")] chrono::NaiveDate);
+///
+/// let o = S(chrono::NaiveDate::from_ymd_opt(2000, 1, 2).expect("date"));
+/// let v = Value {
+///     value_type: Some(ValueType::StringValue("2000-01-02".to_string())),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D>(deserializer: D) -> Result<chrono::NaiveDate, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    chrono::NaiveDate::parse_from_str(&s, "%Y-%m-%d")
+        .map_err(|e| D::Error::custom(format!("invalid date `{}`: {}", s, e)))
+}
+
+/// Serialize `chrono::NaiveDate` as `stringValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{to_value, with::chrono_naive_date_as_string};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(#[serde(serialize_with = "chrono_naive_date_as_string::serialize")] chrono::NaiveDate);
+///
+/// let o = S(chrono::NaiveDate::from_ymd_opt(2000, 1, 2).expect("date"));
+/// let v = Value {
+///     value_type: Some(ValueType::StringValue("2000-01-02".to_string())),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(date: &chrono::NaiveDate, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    if !(0..=9999).contains(&chrono::Datelike::year(date)) {
+        return Err(S::Error::custom(format!("date out of range ({})", date)));
+    }
+    date.format("%Y-%m-%d").to_string().serialize(serializer)
+}
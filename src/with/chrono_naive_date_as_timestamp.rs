@@ -0,0 +1,83 @@
+//! (De)serialize `chrono::NaiveDate` as `timestampValue`.
+//!
+//! The date is stored as midnight UTC.
+
+use serde::{de::Error as _, ser::Error as _};
+
+use crate::Timestamp;
+
+/// Deserialize `chrono::NaiveDate` from `timestampValue`.
+///
+/// It is an error if the timestamp is not midnight UTC.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{from_value, with::chrono_naive_date_as_timestamp};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(#[serde(deserialize_with = "chrono_naive_date_as_timestamp::deserialize")] chrono::NaiveDate);
+///
+/// let o = S(chrono::NaiveDate::from_ymd_opt(1970, 1, 2).expect("date"));
+/// let v = Value {
+///     value_type: Some(ValueType::TimestampValue(prost_types::Timestamp {
+///         seconds: 86_400_i64,
+///         nanos: 0_i32,
+///     })),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D>(deserializer: D) -> Result<chrono::NaiveDate, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let date_time = chrono::DateTime::<chrono::Utc>::try_from(Timestamp::from(
+        crate::with::timestamp::deserialize(deserializer)?,
+    ))
+    .map_err(D::Error::custom)?;
+    if date_time.time() != chrono::NaiveTime::MIN {
+        return Err(D::Error::custom(format!(
+            "timestamp is not midnight UTC ({})",
+            date_time
+        )));
+    }
+    Ok(date_time.date_naive())
+}
+
+/// Serialize `chrono::NaiveDate` as `timestampValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{to_value, with::chrono_naive_date_as_timestamp};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(#[serde(serialize_with = "chrono_naive_date_as_timestamp::serialize")] chrono::NaiveDate);
+///
+/// let o = S(chrono::NaiveDate::from_ymd_opt(1970, 1, 2).expect("date"));
+/// let v = Value {
+///     value_type: Some(ValueType::TimestampValue(prost_types::Timestamp {
+///         seconds: 86_400_i64,
+///         nanos: 0_i32,
+///     })),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(date: &chrono::NaiveDate, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let timestamp = Timestamp::try_from(date.and_time(chrono::NaiveTime::MIN).and_utc())
+        .map_err(S::Error::custom)?;
+    crate::with::timestamp::serialize(&prost_types::Timestamp::from(timestamp), serializer)
+}
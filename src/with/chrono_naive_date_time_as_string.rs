@@ -0,0 +1,77 @@
+//! (De)serialize `chrono::NaiveDateTime` as `stringValue`.
+//!
+//! The string is formatted as `YYYY-MM-DDTHH:MM:SS[.fffffffff]` without an offset.
+//! Strings with an offset are rejected.
+
+use serde::{Deserialize as _, Serialize as _, de::Error as _, ser::Error as _};
+
+/// Deserialize `chrono::NaiveDateTime` from `stringValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{from_value, with::chrono_naive_date_time_as_string};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(#[serde(deserialize_with = "chrono_naive_date_time_as_string::deserialize")] chrono::NaiveDateTime);
+///
+/// let o = S(chrono::NaiveDate::from_ymd_opt(2000, 1, 2)
+///     .and_then(|date| date.and_hms_milli_opt(3, 4, 5, 6))
+///     .expect("date_time"));
+/// let v = Value {
+///     value_type: Some(ValueType::StringValue("2000-01-02T03:04:05.006".to_string())),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D>(deserializer: D) -> Result<chrono::NaiveDateTime, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    chrono::NaiveDateTime::parse_from_str(&s, "%Y-%m-%dT%H:%M:%S%.f")
+        .map_err(|e| D::Error::custom(format!("invalid date time `{}`: {}", s, e)))
+}
+
+/// Serialize `chrono::NaiveDateTime` as `stringValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{to_value, with::chrono_naive_date_time_as_string};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(#[serde(serialize_with = "chrono_naive_date_time_as_string::serialize")] chrono::NaiveDateTime);
+///
+/// let o = S(chrono::NaiveDate::from_ymd_opt(2000, 1, 2)
+///     .and_then(|date| date.and_hms_milli_opt(3, 4, 5, 6))
+///     .expect("date_time"));
+/// let v = Value {
+///     value_type: Some(ValueType::StringValue("2000-01-02T03:04:05.006".to_string())),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(date_time: &chrono::NaiveDateTime, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    if !(0..=9999).contains(&chrono::Datelike::year(date_time)) {
+        return Err(S::Error::custom(format!(
+            "date time out of range ({})",
+            date_time
+        )));
+    }
+    date_time
+        .format("%Y-%m-%dT%H:%M:%S%.f")
+        .to_string()
+        .serialize(serializer)
+}
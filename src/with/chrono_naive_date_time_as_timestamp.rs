@@ -0,0 +1,78 @@
+//! (De)serialize `chrono::NaiveDateTime` as `timestampValue`.
+//!
+//! The date time is interpreted as UTC.
+
+use serde::{de::Error as _, ser::Error as _};
+
+use crate::Timestamp;
+
+/// Deserialize `chrono::NaiveDateTime` from `timestampValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{from_value, with::chrono_naive_date_time_as_timestamp};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(#[serde(deserialize_with = "chrono_naive_date_time_as_timestamp::deserialize")] chrono::NaiveDateTime);
+///
+/// let o = S(chrono::NaiveDate::from_ymd_opt(1970, 1, 1)
+///     .and_then(|date| date.and_hms_nano_opt(0, 0, 1, 2))
+///     .expect("date_time"));
+/// let v = Value {
+///     value_type: Some(ValueType::TimestampValue(prost_types::Timestamp {
+///         seconds: 1_i64,
+///         nanos: 2_i32,
+///     })),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D>(deserializer: D) -> Result<chrono::NaiveDateTime, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    chrono::DateTime::<chrono::Utc>::try_from(Timestamp::from(crate::with::timestamp::deserialize(
+        deserializer,
+    )?))
+    .map(|date_time| date_time.naive_utc())
+    .map_err(D::Error::custom)
+}
+
+/// Serialize `chrono::NaiveDateTime` as `timestampValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{to_value, with::chrono_naive_date_time_as_timestamp};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(#[serde(serialize_with = "chrono_naive_date_time_as_timestamp::serialize")] chrono::NaiveDateTime);
+///
+/// let o = S(chrono::NaiveDate::from_ymd_opt(1970, 1, 1)
+///     .and_then(|date| date.and_hms_nano_opt(0, 0, 1, 2))
+///     .expect("date_time"));
+/// let v = Value {
+///     value_type: Some(ValueType::TimestampValue(prost_types::Timestamp {
+///         seconds: 1_i64,
+///         nanos: 2_i32,
+///     })),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(date_time: &chrono::NaiveDateTime, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let timestamp = Timestamp::try_from(date_time.and_utc()).map_err(S::Error::custom)?;
+    crate::with::timestamp::serialize(&prost_types::Timestamp::from(timestamp), serializer)
+}
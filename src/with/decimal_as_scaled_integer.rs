@@ -0,0 +1,117 @@
+//! (De)serialize `rust_decimal::Decimal` as `integerValue` with a fixed scale.
+//!
+//! The value is stored as `decimal * 10^SCALE`, e.g. `12.50` with `SCALE = 2`
+//! is stored as `1250`. The scale is a const generic parameter, so specify
+//! it with `serialize_with` and `deserialize_with`:
+//!
+//! ```rust
+//! use serde_firestore_value::with::decimal_as_scaled_integer;
+//!
+//! #[derive(serde::Deserialize, serde::Serialize)]
+//! struct Price {
+//!     #[serde(
+//!         deserialize_with = "decimal_as_scaled_integer::deserialize::<2, _>",
+//!         serialize_with = "decimal_as_scaled_integer::serialize::<2, _>"
+//!     )]
+//!     amount: rust_decimal::Decimal,
+//! }
+//! ```
+//!
+//! Serialization fails instead of rounding if the value has more than
+//! `SCALE` significant fractional digits or does not fit in `i64`.
+
+use serde::{Deserialize as _, Serialize as _, de::Error as _, ser::Error as _};
+
+/// Deserialize `rust_decimal::Decimal` from `integerValue` with a fixed scale.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{from_value, with::decimal_as_scaled_integer};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(
+///     #[serde(deserialize_with = "decimal_as_scaled_integer::deserialize::<2, _>")]
+///     rust_decimal::Decimal,
+/// );
+///
+/// let o = S(rust_decimal::Decimal::new(1250, 2));
+/// let v = Value {
+///     value_type: Some(ValueType::IntegerValue(1250)),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, const SCALE: u32, D>(
+    deserializer: D,
+) -> Result<rust_decimal::Decimal, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let scaled = i64::deserialize(deserializer)?;
+    rust_decimal::Decimal::try_from_i128_with_scale(i128::from(scaled), SCALE).map_err(|e| {
+        D::Error::custom(format!(
+            "invalid scaled decimal ({} with scale {}): {}",
+            scaled, SCALE, e
+        ))
+    })
+}
+
+/// Serialize `rust_decimal::Decimal` as `integerValue` with a fixed scale.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{to_value, with::decimal_as_scaled_integer};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(
+///     #[serde(serialize_with = "decimal_as_scaled_integer::serialize::<2, _>")]
+///     rust_decimal::Decimal,
+/// );
+///
+/// let o = S(rust_decimal::Decimal::new(125, 1));
+/// let v = Value {
+///     value_type: Some(ValueType::IntegerValue(1250)),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<const SCALE: u32, S>(
+    decimal: &rust_decimal::Decimal,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    if decimal.normalize().scale() > SCALE {
+        return Err(S::Error::custom(format!(
+            "decimal {} loses precision with scale {}",
+            decimal, SCALE
+        )));
+    }
+    let mut scaled = *decimal;
+    scaled.rescale(SCALE);
+    if scaled != *decimal || scaled.scale() != SCALE {
+        return Err(S::Error::custom(format!(
+            "decimal {} is out of range with scale {}",
+            decimal, SCALE
+        )));
+    }
+    i64::try_from(scaled.mantissa())
+        .map_err(|_| {
+            S::Error::custom(format!(
+                "decimal {} is out of range with scale {}",
+                decimal, SCALE
+            ))
+        })?
+        .serialize(serializer)
+}
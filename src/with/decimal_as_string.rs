@@ -0,0 +1,73 @@
+//! (De)serialize `rust_decimal::Decimal` as `stringValue`.
+//!
+//! The string is the canonical decimal notation, e.g. `-12.50`. The scale
+//! is kept, so trailing zeros survive a round trip. Exponents, a leading
+//! `+`, whitespace, redundant leading zeros and negative zero are rejected
+//! on deserialization.
+
+use serde::{Deserialize as _, Serialize as _, de::Error as _};
+
+/// Deserialize `rust_decimal::Decimal` from `stringValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{from_value, with::decimal_as_string};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(#[serde(deserialize_with = "decimal_as_string::deserialize")] rust_decimal::Decimal);
+///
+/// let o = S(rust_decimal::Decimal::new(1250, 2));
+/// let v = Value {
+///     value_type: Some(ValueType::StringValue("12.50".to_string())),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D>(deserializer: D) -> Result<rust_decimal::Decimal, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    if !crate::decimal::is_canonical(&s) {
+        return Err(D::Error::custom(format!("invalid decimal `{}`", s)));
+    }
+    rust_decimal::Decimal::from_str_exact(&s)
+        .map_err(|e| D::Error::custom(format!("invalid decimal `{}`: {}", s, e)))
+}
+
+/// Serialize `rust_decimal::Decimal` as `stringValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{to_value, with::decimal_as_string};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(#[serde(serialize_with = "decimal_as_string::serialize")] rust_decimal::Decimal);
+///
+/// let o = S(rust_decimal::Decimal::new(1250, 2));
+/// let v = Value {
+///     value_type: Some(ValueType::StringValue("12.50".to_string())),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(decimal: &rust_decimal::Decimal, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let mut decimal = *decimal;
+    if decimal.is_zero() {
+        decimal.set_sign_positive(true);
+    }
+    decimal.to_string().serialize(serializer)
+}
@@ -0,0 +1,10 @@
+//! (De)serialize `std::time::Duration`.
+//!
+//! Select the representation by module:
+//!
+//! - [`seconds_and_nanos`]: a `mapValue` of `{ seconds, nanos }`, matching
+//!   `google.protobuf.Duration`
+//! - [`millis`]: an `integerValue` of milliseconds
+
+pub mod millis;
+pub mod seconds_and_nanos;
@@ -0,0 +1,73 @@
+//! (De)serialize `std::time::Duration` as `integerValue` of milliseconds.
+
+use serde::{Deserialize as _, Serialize as _, de::Error as _, ser::Error as _};
+
+/// Deserialize `std::time::Duration` from `integerValue` of milliseconds.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{from_value, with::duration::millis};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(#[serde(deserialize_with = "millis::deserialize")] std::time::Duration);
+///
+/// let o = S(std::time::Duration::from_millis(1_500));
+/// let v = Value {
+///     value_type: Some(ValueType::IntegerValue(1_500)),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D>(deserializer: D) -> Result<std::time::Duration, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let millis = i64::deserialize(deserializer)?;
+    let millis = u64::try_from(millis)
+        .map_err(|_| D::Error::custom(format!("duration millis out of range ({})", millis)))?;
+    Ok(std::time::Duration::from_millis(millis))
+}
+
+/// Serialize `std::time::Duration` as `integerValue` of milliseconds.
+///
+/// Fails if the duration has sub-millisecond precision.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{to_value, with::duration::millis};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(#[serde(serialize_with = "millis::serialize")] std::time::Duration);
+///
+/// let o = S(std::time::Duration::from_millis(1_500));
+/// let v = Value {
+///     value_type: Some(ValueType::IntegerValue(1_500)),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(duration: &std::time::Duration, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    if !duration.subsec_nanos().is_multiple_of(1_000_000) {
+        return Err(S::Error::custom(format!(
+            "duration has sub-millisecond precision ({:?})",
+            duration
+        )));
+    }
+    let millis = duration.as_millis();
+    i64::try_from(millis)
+        .map_err(|_| S::Error::custom(format!("duration millis out of range ({})", millis)))?
+        .serialize(serializer)
+}
@@ -0,0 +1,110 @@
+//! (De)serialize `std::time::Duration` as `mapValue` of `{ seconds, nanos }`.
+
+use serde::{Deserialize as _, Serialize as _, de::Error as _, ser::Error as _};
+
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(rename = "Duration")]
+struct SecondsAndNanos {
+    seconds: i64,
+    nanos: i32,
+}
+
+/// Deserialize `std::time::Duration` from `mapValue` of `{ seconds, nanos }`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, MapValue, Value};
+/// use serde_firestore_value::{from_value, with::duration::seconds_and_nanos};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(#[serde(deserialize_with = "seconds_and_nanos::deserialize")] std::time::Duration);
+///
+/// let o = S(std::time::Duration::new(1, 2));
+/// let v = Value {
+///     value_type: Some(ValueType::MapValue(MapValue {
+///         fields: std::collections::HashMap::from([
+///             (
+///                 "seconds".to_string(),
+///                 Value {
+///                     value_type: Some(ValueType::IntegerValue(1)),
+///                 },
+///             ),
+///             (
+///                 "nanos".to_string(),
+///                 Value {
+///                     value_type: Some(ValueType::IntegerValue(2)),
+///                 },
+///             ),
+///         ]),
+///     })),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D>(deserializer: D) -> Result<std::time::Duration, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let SecondsAndNanos { seconds, nanos } = SecondsAndNanos::deserialize(deserializer)?;
+    let seconds = u64::try_from(seconds)
+        .map_err(|_| D::Error::custom(format!("duration seconds out of range ({})", seconds)))?;
+    let nanos = u32::try_from(nanos)
+        .ok()
+        .filter(|nanos| *nanos < 1_000_000_000)
+        .ok_or_else(|| D::Error::custom(format!("duration nanos out of range ({})", nanos)))?;
+    Ok(std::time::Duration::new(seconds, nanos))
+}
+
+/// Serialize `std::time::Duration` as `mapValue` of `{ seconds, nanos }`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, MapValue, Value};
+/// use serde_firestore_value::{to_value, with::duration::seconds_and_nanos};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(#[serde(serialize_with = "seconds_and_nanos::serialize")] std::time::Duration);
+///
+/// let o = S(std::time::Duration::new(1, 2));
+/// let v = Value {
+///     value_type: Some(ValueType::MapValue(MapValue {
+///         fields: std::collections::HashMap::from([
+///             (
+///                 "seconds".to_string(),
+///                 Value {
+///                     value_type: Some(ValueType::IntegerValue(1)),
+///                 },
+///             ),
+///             (
+///                 "nanos".to_string(),
+///                 Value {
+///                     value_type: Some(ValueType::IntegerValue(2)),
+///                 },
+///             ),
+///         ]),
+///     })),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(duration: &std::time::Duration, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let seconds = i64::try_from(duration.as_secs()).map_err(|_| {
+        S::Error::custom(format!(
+            "duration seconds out of range ({})",
+            duration.as_secs()
+        ))
+    })?;
+    let nanos = i32::try_from(duration.subsec_nanos()).expect("subsec_nanos < 1_000_000_000");
+    SecondsAndNanos { seconds, nanos }.serialize(serializer)
+}
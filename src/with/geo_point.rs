@@ -0,0 +1,80 @@
+//! (De)serialize `geo_types::Point<f64>` as `geoPointValue`.
+//!
+//! `x` is the longitude and `y` is the latitude. Out-of-range latitudes and
+//! longitudes are rejected in both directions.
+
+use serde::{de::Error as _, ser::Error as _};
+
+use crate::LatLng;
+
+/// Deserialize `geo_types::Point<f64>` from `geoPointValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::{
+///     firestore::v1::{value::ValueType, Value},
+///     r#type::LatLng,
+/// };
+/// use serde_firestore_value::{from_value, with::geo_point};
+///
+/// #[derive(Debug, PartialEq, serde::Deserialize)]
+/// struct S(#[serde(deserialize_with = "geo_point::deserialize")] geo_types::Point<f64>);
+///
+/// let o = S(geo_types::Point::new(139.7, 35.6));
+/// let v = Value {
+///     value_type: Some(ValueType::GeoPointValue(LatLng {
+///         latitude: 35.6_f64,
+///         longitude: 139.7_f64,
+///     })),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D>(deserializer: D) -> Result<geo_types::Point<f64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let lat_lng = LatLng::from(crate::with::lat_lng::deserialize(deserializer)?);
+    let point = geo_types::Point::from(lat_lng);
+    LatLng::try_from(point).map_err(D::Error::custom)?;
+    Ok(point)
+}
+
+/// Serialize `geo_types::Point<f64>` as `geoPointValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::{
+///     firestore::v1::{value::ValueType, Value},
+///     r#type::LatLng,
+/// };
+/// use serde_firestore_value::{to_value, with::geo_point};
+///
+/// #[derive(Debug, PartialEq, serde::Serialize)]
+/// struct S(#[serde(serialize_with = "geo_point::serialize")] geo_types::Point<f64>);
+///
+/// let o = S(geo_types::Point::new(139.7, 35.6));
+/// let v = Value {
+///     value_type: Some(ValueType::GeoPointValue(LatLng {
+///         latitude: 35.6_f64,
+///         longitude: 139.7_f64,
+///     })),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(point: &geo_types::Point<f64>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let lat_lng = LatLng::try_from(*point).map_err(S::Error::custom)?;
+    crate::with::lat_lng::serialize(&lat_lng.into(), serializer)
+}
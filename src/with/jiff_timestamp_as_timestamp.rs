@@ -0,0 +1,75 @@
+//! (De)serialize `jiff::Timestamp` as `timestampValue`.
+
+use serde::{de::Error as _, ser::Error as _};
+
+use crate::Timestamp;
+
+/// Deserialize `jiff::Timestamp` from `timestampValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{from_value, with::jiff_timestamp_as_timestamp};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(
+///     #[serde(deserialize_with = "jiff_timestamp_as_timestamp::deserialize")]
+///     jiff::Timestamp,
+/// );
+///
+/// let o = S("1970-01-01T00:00:01.000000002Z".parse::<jiff::Timestamp>()?);
+/// let v = Value {
+///     value_type: Some(ValueType::TimestampValue(prost_types::Timestamp {
+///         seconds: 1_i64,
+///         nanos: 2_i32,
+///     })),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D>(deserializer: D) -> Result<jiff::Timestamp, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let timestamp = crate::with::timestamp::deserialize(deserializer)?;
+    jiff::Timestamp::try_from(Timestamp::from(timestamp)).map_err(D::Error::custom)
+}
+
+/// Serialize `jiff::Timestamp` as `timestampValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{to_value, with::jiff_timestamp_as_timestamp};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(
+///     #[serde(serialize_with = "jiff_timestamp_as_timestamp::serialize")]
+///     jiff::Timestamp,
+/// );
+///
+/// let o = S("1970-01-01T00:00:01.000000002Z".parse::<jiff::Timestamp>()?);
+/// let v = Value {
+///     value_type: Some(ValueType::TimestampValue(prost_types::Timestamp {
+///         seconds: 1_i64,
+///         nanos: 2_i32,
+///     })),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(timestamp: &jiff::Timestamp, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let timestamp = Timestamp::try_from(*timestamp).map_err(S::Error::custom)?;
+    crate::with::timestamp::serialize(&prost_types::Timestamp::from(timestamp), serializer)
+}
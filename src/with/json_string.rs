@@ -0,0 +1,67 @@
+//! (De)serialize any `T` as `stringValue` of compact JSON.
+//!
+//! Use this for values whose serde output does not fit Firestore, such as
+//! nested arrays or `u64`, or for values that are never queried.
+
+use serde::{Deserialize as _, Serialize as _, de::Error as _, ser::Error as _};
+
+/// Deserialize `T` from `stringValue` of JSON.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{from_value, with::json_string};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(#[serde(deserialize_with = "json_string::deserialize")] Vec<Vec<u64>>);
+///
+/// let o = S(vec![vec![u64::MAX]]);
+/// let v = Value {
+///     value_type: Some(ValueType::StringValue("[[18446744073709551615]]".to_string())),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::de::DeserializeOwned,
+{
+    let s = String::deserialize(deserializer)?;
+    serde_json::from_str::<T>(&s).map_err(D::Error::custom)
+}
+
+/// Serialize `T` as `stringValue` of compact JSON.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{to_value, with::json_string};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(#[serde(serialize_with = "json_string::serialize")] Vec<Vec<u64>>);
+///
+/// let o = S(vec![vec![u64::MAX]]);
+/// let v = Value {
+///     value_type: Some(ValueType::StringValue("[[18446744073709551615]]".to_string())),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    T: ?Sized + serde::Serialize,
+{
+    serde_json::to_string(value)
+        .map_err(S::Error::custom)?
+        .serialize(serializer)
+}
@@ -0,0 +1,130 @@
+//! (De)serialize `geo_types::LineString<f64>` as `arrayValue` of `geoPointValue`.
+//!
+//! Each coordinate's `x` is the longitude and `y` is the latitude.
+//! Out-of-range latitudes and longitudes are rejected in both directions.
+
+use serde::{Deserialize as _, de::Error as _, ser::Error as _};
+
+use crate::LatLng;
+use crate::google::r#type::LatLng as GoogleApiProtoLatLng;
+
+struct GeoPoint(GoogleApiProtoLatLng);
+
+impl<'de> serde::Deserialize<'de> for GeoPoint {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        crate::with::lat_lng::deserialize(deserializer).map(Self)
+    }
+}
+
+impl serde::Serialize for GeoPoint {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        crate::with::lat_lng::serialize(&self.0, serializer)
+    }
+}
+
+/// Deserialize `geo_types::LineString<f64>` from `arrayValue` of `geoPointValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::{
+///     firestore::v1::{value::ValueType, ArrayValue, Value},
+///     r#type::LatLng,
+/// };
+/// use serde_firestore_value::{from_value, with::line_string_as_geo_points};
+///
+/// #[derive(Debug, PartialEq, serde::Deserialize)]
+/// struct S(
+///     #[serde(deserialize_with = "line_string_as_geo_points::deserialize")]
+///     geo_types::LineString<f64>,
+/// );
+///
+/// let o = S(geo_types::LineString::from(vec![(139.7, 35.6)]));
+/// let v = Value {
+///     value_type: Some(ValueType::ArrayValue(ArrayValue {
+///         values: vec![Value {
+///             value_type: Some(ValueType::GeoPointValue(LatLng {
+///                 latitude: 35.6_f64,
+///                 longitude: 139.7_f64,
+///             })),
+///         }],
+///     })),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D>(deserializer: D) -> Result<geo_types::LineString<f64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Vec::<GeoPoint>::deserialize(deserializer)?
+        .into_iter()
+        .map(|GeoPoint(lat_lng)| {
+            let coord = geo_types::Coord::from(LatLng::from(lat_lng));
+            LatLng::try_from(coord).map_err(D::Error::custom)?;
+            Ok(coord)
+        })
+        .collect::<Result<Vec<_>, D::Error>>()
+        .map(geo_types::LineString::new)
+}
+
+/// Serialize `geo_types::LineString<f64>` as `arrayValue` of `geoPointValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::{
+///     firestore::v1::{value::ValueType, ArrayValue, Value},
+///     r#type::LatLng,
+/// };
+/// use serde_firestore_value::{to_value, with::line_string_as_geo_points};
+///
+/// #[derive(Debug, PartialEq, serde::Serialize)]
+/// struct S(
+///     #[serde(serialize_with = "line_string_as_geo_points::serialize")]
+///     geo_types::LineString<f64>,
+/// );
+///
+/// let o = S(geo_types::LineString::from(vec![(139.7, 35.6)]));
+/// let v = Value {
+///     value_type: Some(ValueType::ArrayValue(ArrayValue {
+///         values: vec![Value {
+///             value_type: Some(ValueType::GeoPointValue(LatLng {
+///                 latitude: 35.6_f64,
+///                 longitude: 139.7_f64,
+///             })),
+///         }],
+///     })),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(
+    line_string: &geo_types::LineString<f64>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let geo_points = line_string
+        .coords()
+        .map(|coord| {
+            LatLng::try_from(*coord)
+                .map(|lat_lng| GeoPoint(lat_lng.into()))
+                .map_err(S::Error::custom)
+        })
+        .collect::<Result<Vec<_>, S::Error>>()?;
+    serializer.collect_seq(geo_points)
+}
@@ -0,0 +1,81 @@
+//! (De)serialize `Option<jiff::Timestamp>` as `timestampValue` or `nullValue`.
+
+use serde::{de::Error as _, ser::Error as _};
+
+use crate::Timestamp;
+
+/// Deserialize `Option<jiff::Timestamp>` from `timestampValue` or `nullValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{from_value, with::option_jiff_timestamp_as_timestamp};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(
+///     #[serde(deserialize_with = "option_jiff_timestamp_as_timestamp::deserialize")]
+///     Option<jiff::Timestamp>,
+/// );
+///
+/// let o = S(Some("1970-01-01T00:00:01.000000002Z".parse::<jiff::Timestamp>()?));
+/// let v = Value {
+///     value_type: Some(ValueType::TimestampValue(prost_types::Timestamp {
+///         seconds: 1_i64,
+///         nanos: 2_i32,
+///     })),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<jiff::Timestamp>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    crate::with::option_timestamp::deserialize(deserializer)?
+        .map(|timestamp| {
+            jiff::Timestamp::try_from(Timestamp::from(timestamp)).map_err(D::Error::custom)
+        })
+        .transpose()
+}
+
+/// Serialize `Option<jiff::Timestamp>` as `timestampValue` or `nullValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{to_value, with::option_jiff_timestamp_as_timestamp};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(
+///     #[serde(serialize_with = "option_jiff_timestamp_as_timestamp::serialize")]
+///     Option<jiff::Timestamp>,
+/// );
+///
+/// let o = S(None);
+/// let v = Value {
+///     value_type: Some(ValueType::NullValue(0)),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(
+    option_timestamp: &Option<jiff::Timestamp>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let option_timestamp = option_timestamp
+        .map(|timestamp| Timestamp::try_from(timestamp).map(prost_types::Timestamp::from))
+        .transpose()
+        .map_err(S::Error::custom)?;
+    crate::with::option_timestamp::serialize(&option_timestamp, serializer)
+}
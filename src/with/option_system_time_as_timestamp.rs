@@ -0,0 +1,81 @@
+//! (De)serialize `Option<std::time::SystemTime>` as `timestampValue` or `nullValue`.
+
+use serde::{de::Error as _, ser::Error as _};
+
+use crate::Timestamp;
+
+/// Deserialize `Option<std::time::SystemTime>` from `timestampValue` or `nullValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{from_value, with::option_system_time_as_timestamp};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(
+///     #[serde(deserialize_with = "option_system_time_as_timestamp::deserialize")]
+///     Option<std::time::SystemTime>,
+/// );
+///
+/// let o = S(Some(std::time::UNIX_EPOCH + std::time::Duration::new(1, 2)));
+/// let v = Value {
+///     value_type: Some(ValueType::TimestampValue(prost_types::Timestamp {
+///         seconds: 1_i64,
+///         nanos: 2_i32,
+///     })),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<std::time::SystemTime>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    crate::with::option_timestamp::deserialize(deserializer)?
+        .map(|timestamp| {
+            std::time::SystemTime::try_from(Timestamp::from(timestamp)).map_err(D::Error::custom)
+        })
+        .transpose()
+}
+
+/// Serialize `Option<std::time::SystemTime>` as `timestampValue` or `nullValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{to_value, with::option_system_time_as_timestamp};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(
+///     #[serde(serialize_with = "option_system_time_as_timestamp::serialize")]
+///     Option<std::time::SystemTime>,
+/// );
+///
+/// let o = S(None);
+/// let v = Value {
+///     value_type: Some(ValueType::NullValue(0)),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(
+    option_timestamp: &Option<std::time::SystemTime>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let option_timestamp = option_timestamp
+        .map(|timestamp| Timestamp::try_from(timestamp).map(prost_types::Timestamp::from))
+        .transpose()
+        .map_err(S::Error::custom)?;
+    crate::with::option_timestamp::serialize(&option_timestamp, serializer)
+}
@@ -0,0 +1,59 @@
+//! (De)serialize `Option<uuid::Uuid>` as `bytesValue` or `nullValue`.
+//!
+//! UUIDs are serialized as their 16 bytes in big-endian order.
+
+/// Deserialize `Option<uuid::Uuid>` from `bytesValue` or `nullValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, ArrayValue, Value};
+/// use serde_firestore_value::{from_value, with::option_uuid_as_bytes};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(#[serde(deserialize_with = "option_uuid_as_bytes::deserialize")] Option<uuid::Uuid>);
+///
+/// let o = S(None);
+/// let v = Value {
+///     value_type: Some(ValueType::NullValue(0)),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<uuid::Uuid>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    crate::de::with::uuid_as_bytes::deserialize_option_uuid_as_bytes(deserializer)
+}
+
+/// Serialize `Option<uuid::Uuid>` as `bytesValue` or `nullValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, ArrayValue, Value};
+/// use serde_firestore_value::{to_value, with::option_uuid_as_bytes};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(#[serde(serialize_with = "option_uuid_as_bytes::serialize")] Option<uuid::Uuid>);
+///
+/// let o = S(None);
+/// let v = Value {
+///     value_type: Some(ValueType::NullValue(0)),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(option_uuid: &Option<uuid::Uuid>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    crate::ser::with::uuid_as_bytes::serialize_option_uuid_as_bytes(option_uuid, serializer)
+}
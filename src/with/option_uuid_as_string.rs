@@ -0,0 +1,60 @@
+//! (De)serialize `Option<uuid::Uuid>` as `stringValue` or `nullValue`.
+//!
+//! UUIDs are serialized in the hyphenated lowercase form. Deserialization
+//! also accepts the simple, braced, URN and uppercase forms.
+
+/// Deserialize `Option<uuid::Uuid>` from `stringValue` or `nullValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, ArrayValue, Value};
+/// use serde_firestore_value::{from_value, with::option_uuid_as_string};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(#[serde(deserialize_with = "option_uuid_as_string::deserialize")] Option<uuid::Uuid>);
+///
+/// let o = S(Some(uuid::Uuid::from_u128(0x67e55044_10b1_426f_9247_bb680e5fe0c8)));
+/// let v = Value {
+///     value_type: Some(ValueType::StringValue("67e55044-10b1-426f-9247-bb680e5fe0c8".to_string())),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<uuid::Uuid>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    crate::de::with::uuid_as_string::deserialize_option_uuid_as_string(deserializer)
+}
+
+/// Serialize `Option<uuid::Uuid>` as `stringValue` or `nullValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, ArrayValue, Value};
+/// use serde_firestore_value::{to_value, with::option_uuid_as_string};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(#[serde(serialize_with = "option_uuid_as_string::serialize")] Option<uuid::Uuid>);
+///
+/// let o = S(Some(uuid::Uuid::from_u128(0x67e55044_10b1_426f_9247_bb680e5fe0c8)));
+/// let v = Value {
+///     value_type: Some(ValueType::StringValue("67e55044-10b1-426f-9247-bb680e5fe0c8".to_string())),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(option_uuid: &Option<uuid::Uuid>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    crate::ser::with::uuid_as_string::serialize_option_uuid_as_string(option_uuid, serializer)
+}
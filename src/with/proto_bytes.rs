@@ -0,0 +1,74 @@
+//! (De)serialize any `prost::Message` as `bytesValue`.
+//!
+//! The message is stored in the protobuf binary encoding.
+
+use serde::{Deserialize as _, de::Error as _};
+
+use crate::Bytes;
+
+/// Deserialize `T: prost::Message` from `bytesValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{from_value, with::proto_bytes};
+///
+/// #[derive(Debug, PartialEq, serde::Deserialize)]
+/// struct S(#[serde(deserialize_with = "proto_bytes::deserialize")] prost_types::Duration);
+///
+/// let o = S(prost_types::Duration {
+///     seconds: 1,
+///     nanos: 2,
+/// });
+/// let v = Value {
+///     value_type: Some(ValueType::BytesValue(vec![0x08, 0x01, 0x10, 0x02])),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: prost::Message + Default,
+{
+    let Bytes(v) = Bytes::deserialize(deserializer)?;
+    T::decode(v.as_slice()).map_err(D::Error::custom)
+}
+
+/// Serialize `T: prost::Message` as `bytesValue`.
+///
+/// It is an error if the encoded length exceeds 1,048,487 bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{to_value, with::proto_bytes};
+///
+/// #[derive(Debug, PartialEq, serde::Serialize)]
+/// struct S(#[serde(serialize_with = "proto_bytes::serialize")] prost_types::Duration);
+///
+/// let o = S(prost_types::Duration {
+///     seconds: 1,
+///     nanos: 2,
+/// });
+/// let v = Value {
+///     value_type: Some(ValueType::BytesValue(vec![0x08, 0x01, 0x10, 0x02])),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S, T>(message: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    T: prost::Message,
+{
+    serializer.serialize_bytes(&message.encode_to_vec())
+}
@@ -0,0 +1,59 @@
+//! (De)serialize `Value` as is.
+//!
+//! See [`RawValue`](crate::RawValue) for the encoding with other formats.
+
+use crate::google::firestore::v1::Value;
+
+/// Deserialize `Value` as is.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{from_value, with::raw_value};
+///
+/// #[derive(Debug, PartialEq, serde::Deserialize)]
+/// struct S(#[serde(deserialize_with = "raw_value::deserialize")] Value);
+///
+/// let v = Value {
+///     value_type: Some(ValueType::FieldReferenceValue("a.b".to_string())),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, S(v));
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Value, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    crate::de::with::raw_value::deserialize_raw_value(deserializer)
+}
+
+/// Serialize `Value` as is.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{to_value, with::raw_value};
+///
+/// #[derive(Debug, PartialEq, serde::Serialize)]
+/// struct S(#[serde(serialize_with = "raw_value::serialize")] Value);
+///
+/// let v = Value {
+///     value_type: Some(ValueType::FieldReferenceValue("a.b".to_string())),
+/// };
+/// let s = to_value(&S(v.clone()))?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(value: &Value, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    crate::ser::with::raw_value::serialize_raw_value(value, serializer)
+}
@@ -1,4 +1,4 @@
-//! Deserialize `String` as `fieldReferenceValue`.
+//! (De)serialize `String` as `fieldReferenceValue`.
 
 /// Deserialize `String` from `fieldReferenceValue`.
 ///
@@ -54,3 +54,31 @@ where
 {
     crate::de::with::string_as_field_reference::deserialize_string_as_field_reference(deserializer)
 }
+
+/// Serialize `String` as `fieldReferenceValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{to_value, with::string_as_field_reference};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(#[serde(serialize_with = "string_as_field_reference::serialize")] String);
+///
+/// let o = S("field_name".to_string());
+/// let v = Value {
+///     value_type: Some(ValueType::FieldReferenceValue("field_name".to_string())),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(s: &str, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    crate::ser::with::string_as_field_reference::serialize_string_as_field_reference(s, serializer)
+}
@@ -0,0 +1,75 @@
+//! (De)serialize `std::time::SystemTime` as `timestampValue`.
+
+use serde::{de::Error as _, ser::Error as _};
+
+use crate::Timestamp;
+
+/// Deserialize `std::time::SystemTime` from `timestampValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{from_value, with::system_time_as_timestamp};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(
+///     #[serde(deserialize_with = "system_time_as_timestamp::deserialize")]
+///     std::time::SystemTime,
+/// );
+///
+/// let o = S(std::time::UNIX_EPOCH + std::time::Duration::new(1, 2));
+/// let v = Value {
+///     value_type: Some(ValueType::TimestampValue(prost_types::Timestamp {
+///         seconds: 1_i64,
+///         nanos: 2_i32,
+///     })),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D>(deserializer: D) -> Result<std::time::SystemTime, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let timestamp = crate::with::timestamp::deserialize(deserializer)?;
+    std::time::SystemTime::try_from(Timestamp::from(timestamp)).map_err(D::Error::custom)
+}
+
+/// Serialize `std::time::SystemTime` as `timestampValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{to_value, with::system_time_as_timestamp};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(
+///     #[serde(serialize_with = "system_time_as_timestamp::serialize")]
+///     std::time::SystemTime,
+/// );
+///
+/// let o = S(std::time::UNIX_EPOCH + std::time::Duration::new(1, 2));
+/// let v = Value {
+///     value_type: Some(ValueType::TimestampValue(prost_types::Timestamp {
+///         seconds: 1_i64,
+///         nanos: 2_i32,
+///     })),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(timestamp: &std::time::SystemTime, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let timestamp = Timestamp::try_from(*timestamp).map_err(S::Error::custom)?;
+    crate::with::timestamp::serialize(&prost_types::Timestamp::from(timestamp), serializer)
+}
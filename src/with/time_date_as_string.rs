@@ -0,0 +1,71 @@
+//! (De)serialize `time::Date` as `stringValue`.
+//!
+//! The string is formatted as `YYYY-MM-DD`.
+
+use serde::{Deserialize as _, Serialize as _, de::Error as _, ser::Error as _};
+
+const DATE_FORMAT: &[time::format_description::BorrowedFormatItem<'_>] =
+    time::macros::format_description!("[year]-[month]-[day]");
+
+/// Deserialize `time::Date` from `stringValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{from_value, with::time_date_as_string};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(#[serde(deserialize_with = "time_date_as_string::deserialize")] time::Date);
+///
+/// let o = S(time::Date::from_calendar_date(2000, time::Month::January, 2)?);
+/// let v = Value {
+///     value_type: Some(ValueType::StringValue("2000-01-02".to_string())),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D>(deserializer: D) -> Result<time::Date, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    time::Date::parse(&s, DATE_FORMAT)
+        .map_err(|e| D::Error::custom(format!("invalid date `{}`: {}", s, e)))
+}
+
+/// Serialize `time::Date` as `stringValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{to_value, with::time_date_as_string};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(#[serde(serialize_with = "time_date_as_string::serialize")] time::Date);
+///
+/// let o = S(time::Date::from_calendar_date(2000, time::Month::January, 2)?);
+/// let v = Value {
+///     value_type: Some(ValueType::StringValue("2000-01-02".to_string())),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(date: &time::Date, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    if !(0..=9999).contains(&date.year()) {
+        return Err(S::Error::custom(format!("date out of range ({})", date)));
+    }
+    date.format(DATE_FORMAT)
+        .map_err(S::Error::custom)?
+        .serialize(serializer)
+}
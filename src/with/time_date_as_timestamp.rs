@@ -0,0 +1,82 @@
+//! (De)serialize `time::Date` as `timestampValue`.
+//!
+//! The date is stored as midnight UTC.
+
+use serde::{de::Error as _, ser::Error as _};
+
+use crate::Timestamp;
+
+/// Deserialize `time::Date` from `timestampValue`.
+///
+/// It is an error if the timestamp is not midnight UTC.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{from_value, with::time_date_as_timestamp};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(#[serde(deserialize_with = "time_date_as_timestamp::deserialize")] time::Date);
+///
+/// let o = S(time::Date::from_calendar_date(1970, time::Month::January, 2)?);
+/// let v = Value {
+///     value_type: Some(ValueType::TimestampValue(prost_types::Timestamp {
+///         seconds: 86_400_i64,
+///         nanos: 0_i32,
+///     })),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D>(deserializer: D) -> Result<time::Date, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let offset_date_time = time::OffsetDateTime::try_from(Timestamp::from(
+        crate::with::timestamp::deserialize(deserializer)?,
+    ))
+    .map_err(D::Error::custom)?;
+    if offset_date_time.time() != time::Time::MIDNIGHT {
+        return Err(D::Error::custom(format!(
+            "timestamp is not midnight UTC ({})",
+            offset_date_time
+        )));
+    }
+    Ok(offset_date_time.date())
+}
+
+/// Serialize `time::Date` as `timestampValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{to_value, with::time_date_as_timestamp};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(#[serde(serialize_with = "time_date_as_timestamp::serialize")] time::Date);
+///
+/// let o = S(time::Date::from_calendar_date(1970, time::Month::January, 2)?);
+/// let v = Value {
+///     value_type: Some(ValueType::TimestampValue(prost_types::Timestamp {
+///         seconds: 86_400_i64,
+///         nanos: 0_i32,
+///     })),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(date: &time::Date, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let timestamp = Timestamp::try_from(date.midnight().assume_utc()).map_err(S::Error::custom)?;
+    crate::with::timestamp::serialize(&prost_types::Timestamp::from(timestamp), serializer)
+}
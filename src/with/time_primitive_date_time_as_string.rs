@@ -0,0 +1,92 @@
+//! (De)serialize `time::PrimitiveDateTime` as `stringValue`.
+//!
+//! The string is formatted as `YYYY-MM-DDTHH:MM:SS[.fffffffff]` without an offset.
+//! Strings with an offset are rejected.
+
+use serde::{Deserialize as _, Serialize as _, de::Error as _, ser::Error as _};
+
+const DATE_TIME_FORMAT: &[time::format_description::BorrowedFormatItem<'_>] =
+    time::macros::format_description!("[year]-[month]-[day]T[hour]:[minute]:[second]");
+const DATE_TIME_WITH_SUBSECOND_FORMAT: &[time::format_description::BorrowedFormatItem<'_>] =
+    time::macros::format_description!("[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond]");
+const DATE_TIME_PARSE_FORMAT: &[time::format_description::BorrowedFormatItem<'_>] = time::macros::format_description!(
+    "[year]-[month]-[day]T[hour]:[minute]:[second][optional [.[subsecond]]]"
+);
+
+/// Deserialize `time::PrimitiveDateTime` from `stringValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{from_value, with::time_primitive_date_time_as_string};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(#[serde(deserialize_with = "time_primitive_date_time_as_string::deserialize")] time::PrimitiveDateTime);
+///
+/// let o = S(time::PrimitiveDateTime::new(
+///     time::Date::from_calendar_date(2000, time::Month::January, 2)?,
+///     time::Time::from_hms_milli(3, 4, 5, 6)?,
+/// ));
+/// let v = Value {
+///     value_type: Some(ValueType::StringValue("2000-01-02T03:04:05.006".to_string())),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D>(deserializer: D) -> Result<time::PrimitiveDateTime, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    time::PrimitiveDateTime::parse(&s, DATE_TIME_PARSE_FORMAT)
+        .map_err(|e| D::Error::custom(format!("invalid date time `{}`: {}", s, e)))
+}
+
+/// Serialize `time::PrimitiveDateTime` as `stringValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{to_value, with::time_primitive_date_time_as_string};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(#[serde(serialize_with = "time_primitive_date_time_as_string::serialize")] time::PrimitiveDateTime);
+///
+/// let o = S(time::PrimitiveDateTime::new(
+///     time::Date::from_calendar_date(2000, time::Month::January, 2)?,
+///     time::Time::from_hms_milli(3, 4, 5, 6)?,
+/// ));
+/// let v = Value {
+///     value_type: Some(ValueType::StringValue("2000-01-02T03:04:05.006".to_string())),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(date_time: &time::PrimitiveDateTime, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    if !(0..=9999).contains(&date_time.year()) {
+        return Err(S::Error::custom(format!(
+            "date time out of range ({})",
+            date_time
+        )));
+    }
+    let format = if date_time.nanosecond() == 0 {
+        DATE_TIME_FORMAT
+    } else {
+        DATE_TIME_WITH_SUBSECOND_FORMAT
+    };
+    date_time
+        .format(format)
+        .map_err(S::Error::custom)?
+        .serialize(serializer)
+}
@@ -0,0 +1,82 @@
+//! (De)serialize `time::PrimitiveDateTime` as `timestampValue`.
+//!
+//! The date time is interpreted as UTC.
+
+use serde::{de::Error as _, ser::Error as _};
+
+use crate::Timestamp;
+
+/// Deserialize `time::PrimitiveDateTime` from `timestampValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{from_value, with::time_primitive_date_time_as_timestamp};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(#[serde(deserialize_with = "time_primitive_date_time_as_timestamp::deserialize")] time::PrimitiveDateTime);
+///
+/// let o = S(time::PrimitiveDateTime::new(
+///     time::Date::from_calendar_date(1970, time::Month::January, 1)?,
+///     time::Time::from_hms_nano(0, 0, 1, 2)?,
+/// ));
+/// let v = Value {
+///     value_type: Some(ValueType::TimestampValue(prost_types::Timestamp {
+///         seconds: 1_i64,
+///         nanos: 2_i32,
+///     })),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D>(deserializer: D) -> Result<time::PrimitiveDateTime, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    time::OffsetDateTime::try_from(Timestamp::from(crate::with::timestamp::deserialize(
+        deserializer,
+    )?))
+    .map(|offset_date_time| {
+        time::PrimitiveDateTime::new(offset_date_time.date(), offset_date_time.time())
+    })
+    .map_err(D::Error::custom)
+}
+
+/// Serialize `time::PrimitiveDateTime` as `timestampValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, Value};
+/// use serde_firestore_value::{to_value, with::time_primitive_date_time_as_timestamp};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(#[serde(serialize_with = "time_primitive_date_time_as_timestamp::serialize")] time::PrimitiveDateTime);
+///
+/// let o = S(time::PrimitiveDateTime::new(
+///     time::Date::from_calendar_date(1970, time::Month::January, 1)?,
+///     time::Time::from_hms_nano(0, 0, 1, 2)?,
+/// ));
+/// let v = Value {
+///     value_type: Some(ValueType::TimestampValue(prost_types::Timestamp {
+///         seconds: 1_i64,
+///         nanos: 2_i32,
+///     })),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(date_time: &time::PrimitiveDateTime, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let timestamp = Timestamp::try_from(date_time.assume_utc()).map_err(S::Error::custom)?;
+    crate::with::timestamp::serialize(&prost_types::Timestamp::from(timestamp), serializer)
+}
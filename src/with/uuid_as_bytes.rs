@@ -0,0 +1,59 @@
+//! (De)serialize `uuid::Uuid` as `bytesValue`.
+//!
+//! UUIDs are serialized as their 16 bytes in big-endian order.
+
+/// Deserialize `uuid::Uuid` from `bytesValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, ArrayValue, Value};
+/// use serde_firestore_value::{from_value, with::uuid_as_bytes};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(#[serde(deserialize_with = "uuid_as_bytes::deserialize")] uuid::Uuid);
+///
+/// let o = S(uuid::Uuid::from_u128(0x67e55044_10b1_426f_9247_bb680e5fe0c8));
+/// let v = Value {
+///     value_type: Some(ValueType::BytesValue(vec![0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f, 0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f, 0xe0, 0xc8])),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D>(deserializer: D) -> Result<uuid::Uuid, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    crate::de::with::uuid_as_bytes::deserialize_uuid_as_bytes(deserializer)
+}
+
+/// Serialize `uuid::Uuid` as `bytesValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, ArrayValue, Value};
+/// use serde_firestore_value::{to_value, with::uuid_as_bytes};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(#[serde(serialize_with = "uuid_as_bytes::serialize")] uuid::Uuid);
+///
+/// let o = S(uuid::Uuid::from_u128(0x67e55044_10b1_426f_9247_bb680e5fe0c8));
+/// let v = Value {
+///     value_type: Some(ValueType::BytesValue(vec![0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f, 0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f, 0xe0, 0xc8])),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(uuid: &uuid::Uuid, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    crate::ser::with::uuid_as_bytes::serialize_uuid_as_bytes(uuid, serializer)
+}
@@ -0,0 +1,60 @@
+//! (De)serialize `uuid::Uuid` as `stringValue`.
+//!
+//! UUIDs are serialized in the hyphenated lowercase form. Deserialization
+//! also accepts the simple, braced, URN and uppercase forms.
+
+/// Deserialize `uuid::Uuid` from `stringValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, ArrayValue, Value};
+/// use serde_firestore_value::{from_value, with::uuid_as_string};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(#[serde(deserialize_with = "uuid_as_string::deserialize")] uuid::Uuid);
+///
+/// let o = S(uuid::Uuid::from_u128(0x67e55044_10b1_426f_9247_bb680e5fe0c8));
+/// let v = Value {
+///     value_type: Some(ValueType::StringValue("67e55044-10b1-426f-9247-bb680e5fe0c8".to_string())),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D>(deserializer: D) -> Result<uuid::Uuid, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    crate::de::with::uuid_as_string::deserialize_uuid_as_string(deserializer)
+}
+
+/// Serialize `uuid::Uuid` as `stringValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, ArrayValue, Value};
+/// use serde_firestore_value::{to_value, with::uuid_as_string};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(#[serde(serialize_with = "uuid_as_string::serialize")] uuid::Uuid);
+///
+/// let o = S(uuid::Uuid::from_u128(0x67e55044_10b1_426f_9247_bb680e5fe0c8));
+/// let v = Value {
+///     value_type: Some(ValueType::StringValue("67e55044-10b1-426f-9247-bb680e5fe0c8".to_string())),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(uuid: &uuid::Uuid, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    crate::ser::with::uuid_as_string::serialize_uuid_as_string(uuid, serializer)
+}
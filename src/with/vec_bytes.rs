@@ -0,0 +1,72 @@
+//! (De)serialize `Vec<T>` as `arrayValue` of `bytesValue`.
+//!
+//! `T` is any type that implements `AsRef<[u8]>` and `From<Vec<u8>>`, such as
+//! `Vec<u8>`, `Box<[u8]>` and `prost::bytes::Bytes`.
+
+/// Deserialize `Vec<T>` from `arrayValue` of `bytesValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, ArrayValue, Value};
+/// use serde_firestore_value::{from_value, with::vec_bytes};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(#[serde(deserialize_with = "vec_bytes::deserialize")] Vec<Vec<u8>>);
+///
+/// let o = S(vec![vec![1_u8, 2_u8]]);
+/// let v = Value {
+///     value_type: Some(ValueType::ArrayValue(ArrayValue {
+///         values: vec![Value {
+///             value_type: Some(ValueType::BytesValue(vec![1_u8, 2_u8])),
+///         }],
+///     })),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: From<Vec<u8>>,
+{
+    crate::de::with::bytes::deserialize_vec_bytes(deserializer)
+}
+
+/// Serialize `Vec<T>` as `arrayValue` of `bytesValue`.
+///
+/// It is an error if the length exceeds 1,048,487 bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, ArrayValue, Value};
+/// use serde_firestore_value::{to_value, with::vec_bytes};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(#[serde(serialize_with = "vec_bytes::serialize")] Vec<Vec<u8>>);
+///
+/// let o = S(vec![vec![1_u8, 2_u8]]);
+/// let v = Value {
+///     value_type: Some(ValueType::ArrayValue(ArrayValue {
+///         values: vec![Value {
+///             value_type: Some(ValueType::BytesValue(vec![1_u8, 2_u8])),
+///         }],
+///     })),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S, T>(vec_bytes: &[T], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    T: AsRef<[u8]>,
+{
+    crate::ser::with::bytes::serialize_vec_bytes(vec_bytes, serializer)
+}
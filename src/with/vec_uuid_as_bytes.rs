@@ -0,0 +1,59 @@
+//! (De)serialize `Vec<uuid::Uuid>` as `arrayValue` of `bytesValue`.
+//!
+//! UUIDs are serialized as their 16 bytes in big-endian order.
+
+/// Deserialize `Vec<uuid::Uuid>` from `arrayValue` of `bytesValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, ArrayValue, Value};
+/// use serde_firestore_value::{from_value, with::vec_uuid_as_bytes};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(#[serde(deserialize_with = "vec_uuid_as_bytes::deserialize")] Vec<uuid::Uuid>);
+///
+/// let o = S(vec![]);
+/// let v = Value {
+///     value_type: Some(ValueType::ArrayValue(ArrayValue { values: vec![] })),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<uuid::Uuid>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    crate::de::with::uuid_as_bytes::deserialize_vec_uuid_as_bytes(deserializer)
+}
+
+/// Serialize `Vec<uuid::Uuid>` as `arrayValue` of `bytesValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, ArrayValue, Value};
+/// use serde_firestore_value::{to_value, with::vec_uuid_as_bytes};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(#[serde(serialize_with = "vec_uuid_as_bytes::serialize")] Vec<uuid::Uuid>);
+///
+/// let o = S(vec![]);
+/// let v = Value {
+///     value_type: Some(ValueType::ArrayValue(ArrayValue { values: vec![] })),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(vec_uuid: &[uuid::Uuid], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    crate::ser::with::uuid_as_bytes::serialize_vec_uuid_as_bytes(vec_uuid, serializer)
+}
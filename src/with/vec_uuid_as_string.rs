@@ -0,0 +1,72 @@
+//! (De)serialize `Vec<uuid::Uuid>` as `arrayValue` of `stringValue`.
+//!
+//! UUIDs are serialized in the hyphenated lowercase form. Deserialization
+//! also accepts the simple, braced, URN and uppercase forms.
+
+/// Deserialize `Vec<uuid::Uuid>` from `arrayValue` of `stringValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, ArrayValue, Value};
+/// use serde_firestore_value::{from_value, with::vec_uuid_as_string};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Deserialize)]
+/// struct S(#[serde(deserialize_with = "vec_uuid_as_string::deserialize")] Vec<uuid::Uuid>);
+///
+/// let o = S(vec![uuid::Uuid::from_u128(0x67e55044_10b1_426f_9247_bb680e5fe0c8)]);
+/// let v = Value {
+///     value_type: Some(ValueType::ArrayValue(ArrayValue {
+///         values: vec![Value {
+///             value_type: Some(ValueType::StringValue(
+///                 "67e55044-10b1-426f-9247-bb680e5fe0c8".to_string(),
+///             )),
+///         }],
+///     })),
+/// };
+/// let d = from_value::<'_, S>(&v)?;
+/// assert_eq!(d, o);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<uuid::Uuid>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    crate::de::with::uuid_as_string::deserialize_vec_uuid_as_string(deserializer)
+}
+
+/// Serialize `Vec<uuid::Uuid>` as `arrayValue` of `stringValue`.
+///
+/// # Examples
+///
+/// ```rust
+/// # fn main() -> anyhow::Result<()> {
+/// use serde_firestore_value::google::firestore::v1::{value::ValueType, ArrayValue, Value};
+/// use serde_firestore_value::{to_value, with::vec_uuid_as_string};
+///
+/// #[derive(Debug, Eq, PartialEq, serde::Serialize)]
+/// struct S(#[serde(serialize_with = "vec_uuid_as_string::serialize")] Vec<uuid::Uuid>);
+///
+/// let o = S(vec![uuid::Uuid::from_u128(0x67e55044_10b1_426f_9247_bb680e5fe0c8)]);
+/// let v = Value {
+///     value_type: Some(ValueType::ArrayValue(ArrayValue {
+///         values: vec![Value {
+///             value_type: Some(ValueType::StringValue(
+///                 "67e55044-10b1-426f-9247-bb680e5fe0c8".to_string(),
+///             )),
+///         }],
+///     })),
+/// };
+/// let s = to_value(&o)?;
+/// assert_eq!(s, v);
+/// #     Ok(())
+/// # }
+/// ```
+pub fn serialize<S>(vec_uuid: &[uuid::Uuid], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    crate::ser::with::uuid_as_string::serialize_vec_uuid_as_string(vec_uuid, serializer)
+}
@@ -180,6 +180,7 @@ fn test_function_with_nested_values() -> anyhow::Result<()> {
 }
 
 #[test]
+#[allow(clippy::approx_constant)]
 fn test_function_deserialize() -> anyhow::Result<()> {
     use serde_firestore_value::{Function, from_value, google, to_value};
     #[cfg(feature = "btree-map")]
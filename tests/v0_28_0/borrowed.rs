@@ -0,0 +1,114 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use serde_firestore_value::google::firestore::v1::{Value, value::ValueType};
+use serde_firestore_value::{Reference, from_value, to_value};
+
+#[test]
+fn test_borrowed_str_and_bytes() -> anyhow::Result<()> {
+    #[derive(Debug, serde::Deserialize)]
+    struct S<'a> {
+        s: &'a str,
+        #[serde(borrow)]
+        c: Cow<'a, str>,
+        b: &'a [u8],
+        #[serde(borrow)]
+        m: BTreeMap<&'a str, &'a str>,
+    }
+
+    #[derive(serde::Serialize)]
+    struct T {
+        s: String,
+        c: String,
+        #[serde(with = "bytes")]
+        b: Vec<u8>,
+        m: BTreeMap<String, String>,
+    }
+
+    mod bytes {
+        pub fn serialize<S: serde::Serializer>(v: &[u8], s: S) -> Result<S::Ok, S::Error> {
+            s.serialize_bytes(v)
+        }
+    }
+
+    let value = to_value(&T {
+        s: "s".to_string(),
+        c: "c".to_string(),
+        b: vec![1, 2, 3],
+        m: BTreeMap::from([("k".to_string(), "v".to_string())]),
+    })?;
+    let d = from_value::<'_, S>(&value)?;
+    assert_eq!(d.s, "s");
+    assert!(matches!(d.c, Cow::Borrowed("c")));
+    assert_eq!(d.b, &[1, 2, 3]);
+    assert_eq!(d.m, BTreeMap::from([("k", "v")]));
+
+    let Some(ValueType::MapValue(map)) = &value.value_type else {
+        anyhow::bail!("not mapValue");
+    };
+    let Some(ValueType::StringValue(s)) = &map.fields["s"].value_type else {
+        anyhow::bail!("not stringValue");
+    };
+    assert_eq!(d.s.as_ptr(), s.as_ptr());
+    let (k, _) = map.fields["m"]
+        .value_type
+        .as_ref()
+        .and_then(|value_type| match value_type {
+            ValueType::MapValue(map) => map.fields.iter().next(),
+            _ => None,
+        })
+        .ok_or_else(|| anyhow::anyhow!("not mapValue"))?;
+    assert_eq!(d.m.keys().next().map(|k| k.as_ptr()), Some(k.as_ptr()));
+    Ok(())
+}
+
+#[test]
+fn test_borrowed_reference() -> anyhow::Result<()> {
+    // deserialize_any visits a referenceValue as a single entry map
+    struct Any<'a>(Vec<(&'a str, &'a str)>);
+
+    impl<'de> serde::Deserialize<'de> for Any<'de> {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            struct V;
+            impl<'de> serde::de::Visitor<'de> for V {
+                type Value = Any<'de>;
+
+                fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    f.write_str("a map")
+                }
+
+                fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+                where
+                    A: serde::de::MapAccess<'de>,
+                {
+                    let mut entries = vec![];
+                    while let Some(entry) = map.next_entry()? {
+                        entries.push(entry);
+                    }
+                    Ok(Any(entries))
+                }
+            }
+            deserializer.deserialize_any(V)
+        }
+    }
+
+    let value = to_value(&Reference(
+        "projects/p/databases/d/documents/c/1".to_string(),
+    ))?;
+    let d = from_value::<'_, Any>(&value)?;
+    let Some(ValueType::ReferenceValue(r)) = &value.value_type else {
+        anyhow::bail!("not referenceValue");
+    };
+    assert_eq!(d.0.len(), 1);
+    assert_eq!(d.0[0].1, "projects/p/databases/d/documents/c/1");
+    assert_eq!(d.0[0].1.as_ptr(), r.as_ptr());
+
+    let value = Value {
+        value_type: Some(ValueType::StringValue("s".to_string())),
+    };
+    assert_eq!(from_value::<'_, &str>(&value)?, "s");
+    Ok(())
+}
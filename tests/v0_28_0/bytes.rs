@@ -0,0 +1,133 @@
+use serde_firestore_value::google::firestore::v1::{ArrayValue, MapValue, Value, value::ValueType};
+use serde_firestore_value::with::{bytes, option_bytes, vec_bytes};
+use serde_firestore_value::{Bytes, from_value, to_value};
+
+#[cfg(feature = "bytes")]
+fn bytes_value(data: &'static [u8]) -> Value {
+    Value {
+        value_type: Some(ValueType::BytesValue(prost::bytes::Bytes::from_static(
+            data,
+        ))),
+    }
+}
+
+#[cfg(feature = "vec-u8")]
+fn bytes_value(data: &'static [u8]) -> Value {
+    Value {
+        value_type: Some(ValueType::BytesValue(data.to_vec())),
+    }
+}
+
+#[test]
+fn test_bytes_type() -> anyhow::Result<()> {
+    let o = Bytes(vec![1, 2, 3]);
+    let s = to_value(&o)?;
+    assert_eq!(s, bytes_value(&[1, 2, 3]));
+    assert_eq!(from_value::<'_, Bytes>(&s)?, o);
+
+    // a plain Vec<u8> is still an arrayValue
+    assert!(matches!(
+        to_value(&vec![1_u8, 2, 3])?.value_type,
+        Some(ValueType::ArrayValue(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_with() -> anyhow::Result<()> {
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct S {
+        #[serde(with = "bytes")]
+        a: Vec<u8>,
+        #[serde(with = "bytes")]
+        b: Box<[u8]>,
+        #[serde(with = "bytes")]
+        c: prost::bytes::Bytes,
+        #[serde(with = "option_bytes")]
+        d: Option<Vec<u8>>,
+        #[serde(with = "option_bytes")]
+        e: Option<Vec<u8>>,
+        #[serde(with = "vec_bytes")]
+        f: Vec<Vec<u8>>,
+    }
+
+    let o = S {
+        a: vec![1],
+        b: vec![2].into_boxed_slice(),
+        c: prost::bytes::Bytes::from_static(&[3]),
+        d: Some(vec![4]),
+        e: None,
+        f: vec![vec![5], vec![]],
+    };
+    let s = to_value(&o)?;
+    let ValueType::MapValue(MapValue { fields }) = s.value_type.clone().unwrap() else {
+        anyhow::bail!("not a map");
+    };
+    assert_eq!(fields["a"], bytes_value(&[1]));
+    assert_eq!(fields["b"], bytes_value(&[2]));
+    assert_eq!(fields["c"], bytes_value(&[3]));
+    assert_eq!(fields["d"], bytes_value(&[4]));
+    assert_eq!(
+        fields["e"],
+        Value {
+            value_type: Some(ValueType::NullValue(0)),
+        }
+    );
+    assert_eq!(
+        fields["f"],
+        Value {
+            value_type: Some(ValueType::ArrayValue(ArrayValue {
+                values: vec![bytes_value(&[5]), bytes_value(&[])],
+            })),
+        }
+    );
+    assert_eq!(from_value::<'_, S>(&s)?, o);
+    Ok(())
+}
+
+#[test]
+fn test_max_byte_len() -> anyhow::Result<()> {
+    const MAX_BYTE_LEN: usize = 1_048_487;
+
+    #[derive(serde::Serialize)]
+    struct S(#[serde(with = "bytes")] Vec<u8>);
+
+    assert!(to_value(&Bytes(vec![0; MAX_BYTE_LEN])).is_ok());
+    assert!(to_value(&Bytes(vec![0; MAX_BYTE_LEN + 1])).is_err());
+    assert!(to_value(&S(vec![0; MAX_BYTE_LEN + 1])).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_serde_bytes() -> anyhow::Result<()> {
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct S(#[serde(with = "serde_bytes")] Vec<u8>);
+
+    let s = to_value(&S(vec![1, 2]))?;
+    assert_eq!(s, to_value(&Bytes(vec![1, 2]))?);
+    assert_eq!(from_value::<'_, Bytes>(&s)?, Bytes(vec![1, 2]));
+    assert_eq!(
+        from_value::<'_, S>(&to_value(&Bytes(vec![1, 2]))?)?,
+        S(vec![1, 2])
+    );
+    assert_eq!(
+        from_value::<'_, serde_bytes::ByteBuf>(&s)?,
+        serde_bytes::ByteBuf::from(vec![1, 2])
+    );
+
+    let json = serde_json::to_string(&Bytes(vec![1, 2]))?;
+    assert_eq!(
+        json,
+        serde_json::to_string(&serde_bytes::ByteBuf::from(vec![1, 2]))?
+    );
+    assert_eq!(serde_json::from_str::<Bytes>(&json)?, Bytes(vec![1, 2]));
+    Ok(())
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn test_prost_bytes_conversions() {
+    let b = prost::bytes::Bytes::from_static(&[1, 2]);
+    assert_eq!(Bytes::from(b.clone()), Bytes(vec![1, 2]));
+    assert_eq!(prost::bytes::Bytes::from(Bytes(vec![1, 2])), b);
+}
@@ -0,0 +1,157 @@
+#[cfg(feature = "chrono")]
+fn v(
+    value_type: serde_firestore_value::google::firestore::v1::value::ValueType,
+) -> serde_firestore_value::google::firestore::v1::Value {
+    serde_firestore_value::google::firestore::v1::Value {
+        value_type: Some(value_type),
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_naive_date() -> anyhow::Result<()> {
+    use serde_firestore_value::google::firestore::v1::value::ValueType;
+    use serde_firestore_value::with::{
+        chrono_naive_date_as_string, chrono_naive_date_as_timestamp,
+    };
+    use serde_firestore_value::{from_value, to_value};
+
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct S(#[serde(with = "chrono_naive_date_as_string")] chrono::NaiveDate);
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct T(#[serde(with = "chrono_naive_date_as_timestamp")] chrono::NaiveDate);
+
+    let date = chrono::NaiveDate::from_ymd_opt(2000, 1, 2).expect("date");
+
+    let s = to_value(&S(date))?;
+    assert_eq!(s, v(ValueType::StringValue("2000-01-02".to_string())));
+    assert_eq!(from_value::<'_, S>(&s)?, S(date));
+    assert!(
+        from_value::<'_, S>(&v(ValueType::StringValue(
+            "2000-01-02T00:00:00Z".to_string()
+        )))
+        .is_err()
+    );
+
+    let t = to_value(&T(date))?;
+    assert_eq!(
+        t,
+        v(ValueType::TimestampValue(prost_types::Timestamp {
+            seconds: 946_771_200,
+            nanos: 0,
+        }))
+    );
+    assert_eq!(from_value::<'_, T>(&t)?, T(date));
+    assert!(
+        from_value::<'_, T>(&v(ValueType::TimestampValue(prost_types::Timestamp {
+            seconds: 946_771_200,
+            nanos: 1,
+        })))
+        .is_err()
+    );
+    Ok(())
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_naive_date_time() -> anyhow::Result<()> {
+    use serde_firestore_value::google::firestore::v1::value::ValueType;
+    use serde_firestore_value::with::{
+        chrono_naive_date_time_as_string, chrono_naive_date_time_as_timestamp,
+    };
+    use serde_firestore_value::{from_value, to_value};
+
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct S(#[serde(with = "chrono_naive_date_time_as_string")] chrono::NaiveDateTime);
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct T(#[serde(with = "chrono_naive_date_time_as_timestamp")] chrono::NaiveDateTime);
+
+    let date_time = chrono::NaiveDate::from_ymd_opt(2000, 1, 2)
+        .and_then(|date| date.and_hms_nano_opt(3, 4, 5, 6))
+        .expect("date_time");
+
+    let s = to_value(&S(date_time))?;
+    assert_eq!(
+        s,
+        v(ValueType::StringValue(
+            "2000-01-02T03:04:05.000000006".to_string()
+        ))
+    );
+    assert_eq!(from_value::<'_, S>(&s)?, S(date_time));
+    assert_eq!(
+        from_value::<'_, S>(&v(ValueType::StringValue(
+            "2000-01-02T03:04:05".to_string()
+        )))?,
+        S(chrono::Timelike::with_nanosecond(&date_time, 0).expect("date_time"))
+    );
+    assert!(
+        from_value::<'_, S>(&v(ValueType::StringValue(
+            "2000-01-02T03:04:05Z".to_string()
+        )))
+        .is_err()
+    );
+
+    let t = to_value(&T(date_time))?;
+    assert_eq!(
+        t,
+        v(ValueType::TimestampValue(prost_types::Timestamp {
+            seconds: 946_782_245,
+            nanos: 6,
+        }))
+    );
+    assert_eq!(from_value::<'_, T>(&t)?, T(date_time));
+    Ok(())
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_fixed_offset_date_time() -> anyhow::Result<()> {
+    use serde_firestore_value::google::firestore::v1::value::ValueType;
+    use serde_firestore_value::with::{
+        chrono_fixed_offset_date_time_as_string, chrono_fixed_offset_date_time_as_timestamp,
+    };
+    use serde_firestore_value::{from_value, to_value};
+
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct S(
+        #[serde(with = "chrono_fixed_offset_date_time_as_string")]
+        chrono::DateTime<chrono::FixedOffset>,
+    );
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct T(
+        #[serde(with = "chrono_fixed_offset_date_time_as_timestamp")]
+        chrono::DateTime<chrono::FixedOffset>,
+    );
+
+    let date_time = chrono::DateTime::parse_from_rfc3339("2000-01-02T03:04:05.006+09:00")?;
+
+    let s = to_value(&S(date_time))?;
+    assert_eq!(
+        s,
+        v(ValueType::StringValue(
+            "2000-01-02T03:04:05.006+09:00".to_string()
+        ))
+    );
+    let d = from_value::<'_, S>(&s)?;
+    assert_eq!(d, S(date_time));
+    assert_eq!(d.0.offset(), date_time.offset());
+    assert!(
+        from_value::<'_, S>(&v(ValueType::StringValue(
+            "2000-01-02T03:04:05".to_string()
+        )))
+        .is_err()
+    );
+
+    let t = to_value(&T(date_time))?;
+    assert_eq!(
+        t,
+        v(ValueType::TimestampValue(prost_types::Timestamp {
+            seconds: 946_749_845,
+            nanos: 6_000_000,
+        }))
+    );
+    let d = from_value::<'_, T>(&t)?;
+    assert_eq!(d, T(date_time));
+    assert_eq!(d.0.offset().local_minus_utc(), 0);
+    Ok(())
+}
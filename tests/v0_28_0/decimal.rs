@@ -0,0 +1,123 @@
+#[cfg(any(feature = "bigdecimal", feature = "decimal"))]
+fn v(
+    value_type: serde_firestore_value::google::firestore::v1::value::ValueType,
+) -> serde_firestore_value::google::firestore::v1::Value {
+    serde_firestore_value::google::firestore::v1::Value {
+        value_type: Some(value_type),
+    }
+}
+
+#[cfg(feature = "decimal")]
+#[test]
+fn test_decimal_as_string() -> anyhow::Result<()> {
+    use serde_firestore_value::google::firestore::v1::value::ValueType;
+    use serde_firestore_value::{from_value, to_value, with::decimal_as_string};
+
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct S(#[serde(with = "decimal_as_string")] rust_decimal::Decimal);
+
+    for (decimal, s) in [
+        (rust_decimal::Decimal::new(-1250, 2), "-12.50"),
+        (rust_decimal::Decimal::new(0, 0), "0"),
+        (-rust_decimal::Decimal::new(0, 1), "0.0"),
+    ] {
+        let value = to_value(&S(decimal))?;
+        assert_eq!(value, v(ValueType::StringValue(s.to_string())));
+        let d = from_value::<'_, S>(&value)?;
+        assert_eq!(d, S(decimal));
+        assert_eq!(d.0.scale(), decimal.scale());
+    }
+
+    for s in [
+        "1e2",
+        "+1",
+        " 1",
+        "01",
+        ".5",
+        "-0",
+        "1_000",
+        "0.00000000000000000000000000001",
+    ] {
+        assert!(from_value::<'_, S>(&v(ValueType::StringValue(s.to_string()))).is_err());
+    }
+    Ok(())
+}
+
+#[cfg(feature = "decimal")]
+#[test]
+fn test_decimal_as_scaled_integer() -> anyhow::Result<()> {
+    use serde_firestore_value::google::firestore::v1::value::ValueType;
+    use serde_firestore_value::{from_value, to_value, with::decimal_as_scaled_integer};
+
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct S(
+        #[serde(
+            deserialize_with = "decimal_as_scaled_integer::deserialize::<2, _>",
+            serialize_with = "decimal_as_scaled_integer::serialize::<2, _>"
+        )]
+        rust_decimal::Decimal,
+    );
+
+    let value = to_value(&S(rust_decimal::Decimal::new(-125, 1)))?;
+    assert_eq!(value, v(ValueType::IntegerValue(-1250)));
+    assert_eq!(
+        from_value::<'_, S>(&value)?,
+        S(rust_decimal::Decimal::new(-1250, 2))
+    );
+    assert_eq!(
+        to_value(&S(rust_decimal::Decimal::new(12500, 3)))?,
+        v(ValueType::IntegerValue(1250))
+    );
+    assert!(to_value(&S(rust_decimal::Decimal::new(12501, 3))).is_err());
+    assert!(to_value(&S(rust_decimal::Decimal::MAX)).is_err());
+    Ok(())
+}
+
+#[cfg(feature = "bigdecimal")]
+#[test]
+fn test_big_decimal_as_string() -> anyhow::Result<()> {
+    use serde_firestore_value::google::firestore::v1::value::ValueType;
+    use serde_firestore_value::{from_value, to_value, with::big_decimal_as_string};
+
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct S(#[serde(with = "big_decimal_as_string")] bigdecimal::BigDecimal);
+
+    for s in ["-12.50", "0", "123456789012345678901234567890.000000001"] {
+        let decimal = s.parse::<bigdecimal::BigDecimal>()?;
+        let value = to_value(&S(decimal.clone()))?;
+        assert_eq!(value, v(ValueType::StringValue(s.to_string())));
+        assert_eq!(from_value::<'_, S>(&value)?, S(decimal));
+    }
+    assert_eq!(
+        to_value(&S("1e3".parse::<bigdecimal::BigDecimal>()?))?,
+        v(ValueType::StringValue("1000".to_string()))
+    );
+
+    for s in ["1e2", "+1", "1 ", "00", "1.", "-0.0"] {
+        assert!(from_value::<'_, S>(&v(ValueType::StringValue(s.to_string()))).is_err());
+    }
+    Ok(())
+}
+
+#[cfg(feature = "bigdecimal")]
+#[test]
+fn test_big_decimal_as_scaled_integer() -> anyhow::Result<()> {
+    use serde_firestore_value::google::firestore::v1::value::ValueType;
+    use serde_firestore_value::{from_value, to_value, with::big_decimal_as_scaled_integer};
+
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct S(
+        #[serde(
+            deserialize_with = "big_decimal_as_scaled_integer::deserialize::<2, _>",
+            serialize_with = "big_decimal_as_scaled_integer::serialize::<2, _>"
+        )]
+        bigdecimal::BigDecimal,
+    );
+
+    let value = to_value(&S("-12.5".parse()?))?;
+    assert_eq!(value, v(ValueType::IntegerValue(-1250)));
+    assert_eq!(from_value::<'_, S>(&value)?, S("-12.50".parse()?));
+    assert!(to_value(&S("12.501".parse()?)).is_err());
+    assert!(to_value(&S("1e30".parse()?)).is_err());
+    Ok(())
+}
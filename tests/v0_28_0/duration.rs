@@ -0,0 +1,59 @@
+#[cfg(feature = "btree-map")]
+use std::collections::BTreeMap as Map;
+#[cfg(feature = "hash-map")]
+use std::collections::HashMap as Map;
+use std::time::Duration;
+
+use serde_firestore_value::google::firestore::v1::{MapValue, Value, value::ValueType};
+use serde_firestore_value::with::duration::{millis, seconds_and_nanos};
+use serde_firestore_value::{from_value, to_value};
+
+fn v(value_type: ValueType) -> Value {
+    Value {
+        value_type: Some(value_type),
+    }
+}
+
+#[test]
+fn test_seconds_and_nanos() -> anyhow::Result<()> {
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct S(#[serde(with = "seconds_and_nanos")] Duration);
+
+    let o = S(Duration::new(3, 4));
+    let expected = v(ValueType::MapValue(MapValue {
+        fields: Map::from([
+            ("seconds".to_string(), v(ValueType::IntegerValue(3))),
+            ("nanos".to_string(), v(ValueType::IntegerValue(4))),
+        ]),
+    }));
+    let s = to_value(&o)?;
+    assert_eq!(s, expected);
+    assert_eq!(from_value::<'_, S>(&s)?, o);
+
+    for (seconds, nanos) in [(-1, 0), (0, -1), (0, 1_000_000_000)] {
+        let invalid = v(ValueType::MapValue(MapValue {
+            fields: Map::from([
+                ("seconds".to_string(), v(ValueType::IntegerValue(seconds))),
+                ("nanos".to_string(), v(ValueType::IntegerValue(nanos))),
+            ]),
+        }));
+        assert!(from_value::<'_, S>(&invalid).is_err());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_millis() -> anyhow::Result<()> {
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct S(#[serde(with = "millis")] Duration);
+
+    let s = to_value(&S(Duration::new(1, 500_000_000)))?;
+    assert_eq!(s, v(ValueType::IntegerValue(1_500)));
+    assert_eq!(from_value::<'_, S>(&s)?, S(Duration::from_millis(1_500)));
+
+    assert!(to_value(&S(Duration::new(1, 500_999_999))).is_err());
+
+    assert!(from_value::<'_, S>(&v(ValueType::IntegerValue(-1))).is_err());
+    assert!(to_value(&S(Duration::MAX)).is_err());
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use serde_firestore_value::google::firestore::v1::{MapValue, Value, value::ValueType};
+use serde_firestore_value::with::proto_bytes;
+use serde_firestore_value::{from_value, to_value};
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn test_json_string() -> anyhow::Result<()> {
+    use serde_firestore_value::with::json_string;
+
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct ThirdParty {
+        big: u64,
+        nested: Vec<Vec<i32>>,
+    }
+
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct S {
+        #[serde(with = "json_string")]
+        t: ThirdParty,
+        #[serde(with = "json_string")]
+        o: Option<ThirdParty>,
+    }
+
+    let o = S {
+        t: ThirdParty {
+            big: u64::MAX,
+            nested: vec![vec![1, 2], vec![]],
+        },
+        o: None,
+    };
+    let s = to_value(&o)?;
+    let ValueType::MapValue(MapValue { fields }) = s.value_type.clone().unwrap() else {
+        anyhow::bail!("not a map");
+    };
+    assert_eq!(
+        fields["t"],
+        Value {
+            value_type: Some(ValueType::StringValue(
+                r#"{"big":18446744073709551615,"nested":[[1,2],[]]}"#.to_string()
+            )),
+        }
+    );
+    assert_eq!(
+        fields["o"],
+        Value {
+            value_type: Some(ValueType::StringValue("null".to_string())),
+        }
+    );
+    assert_eq!(from_value::<'_, S>(&s)?, o);
+
+    #[derive(Debug, serde::Deserialize)]
+    struct T(
+        #[allow(dead_code)]
+        #[serde(with = "json_string")]
+        ThirdParty,
+    );
+    assert!(
+        from_value::<'_, T>(&Value {
+            value_type: Some(ValueType::StringValue("{".to_string())),
+        })
+        .is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_proto_bytes() -> anyhow::Result<()> {
+    use prost::Message as _;
+
+    #[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct S {
+        #[serde(with = "proto_bytes")]
+        v: Value,
+    }
+
+    let o = S {
+        v: Value {
+            value_type: Some(ValueType::StringValue("blob".to_string())),
+        },
+    };
+    let s = to_value(&o)?;
+    let ValueType::MapValue(MapValue { fields }) = s.value_type.clone().unwrap() else {
+        anyhow::bail!("not a map");
+    };
+    let Some(ValueType::BytesValue(encoded)) = fields["v"].value_type.clone() else {
+        anyhow::bail!("not bytes");
+    };
+    assert_eq!(Value::decode(encoded.as_ref())?, o.v);
+    assert_eq!(from_value::<'_, S>(&s)?, o);
+
+    #[derive(Debug, serde::Deserialize)]
+    struct T(
+        #[allow(dead_code)]
+        #[serde(with = "proto_bytes")]
+        prost_types::Timestamp,
+    );
+    assert!(
+        from_value::<'_, T>(&Value {
+            value_type: Some(ValueType::StringValue("blob".to_string())),
+        })
+        .is_err()
+    );
+    Ok(())
+}
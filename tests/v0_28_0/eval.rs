@@ -0,0 +1,469 @@
+use serde_firestore_value::eval::{
+    execute_pipeline, execute_structured_query, execute_structured_query_limit_to_last,
+};
+use serde_firestore_value::expr::{count_all, field, lit};
+use serde_firestore_value::google::firestore::v1::{
+    ArrayValue, Document, MapValue, Value, value::ValueType,
+};
+use serde_firestore_value::stage::{Direction, Ordering};
+use serde_firestore_value::structured_query::{Op, Query, UnaryOp, field_filter, or};
+use serde_firestore_value::{Pipeline, Reference, from_value, to_value};
+
+#[derive(Debug, PartialEq, serde::Deserialize)]
+struct NameAge {
+    name: String,
+    age: i64,
+}
+
+fn json(value: serde_json::Value) -> Value {
+    let value_type = match value {
+        serde_json::Value::Null => ValueType::NullValue(0),
+        serde_json::Value::Bool(b) => ValueType::BooleanValue(b),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => ValueType::IntegerValue(i),
+            None => ValueType::DoubleValue(n.as_f64().unwrap()),
+        },
+        serde_json::Value::String(s) => ValueType::StringValue(s),
+        serde_json::Value::Array(values) => ValueType::ArrayValue(ArrayValue {
+            values: values.into_iter().map(json).collect(),
+        }),
+        serde_json::Value::Object(fields) => ValueType::MapValue(MapValue {
+            fields: fields.into_iter().map(|(k, v)| (k, json(v))).collect(),
+        }),
+    };
+    Value {
+        value_type: Some(value_type),
+    }
+}
+
+fn document(path: &str, value: serde_json::Value) -> anyhow::Result<Document> {
+    let Some(ValueType::MapValue(map)) = json(value).value_type else {
+        anyhow::bail!("not a map");
+    };
+    Ok(Document {
+        name: format!("projects/p/databases/(default)/documents/{path}"),
+        fields: map.fields,
+        create_time: None,
+        update_time: None,
+    })
+}
+
+fn documents() -> anyhow::Result<Vec<Document>> {
+    Ok(vec![
+        document(
+            "users/alice",
+            serde_json::json!({"name": "alice", "age": 30, "city": "tokyo", "active": true}),
+        )?,
+        document(
+            "users/bob",
+            serde_json::json!({"name": "bob", "age": 17, "city": "osaka", "active": true}),
+        )?,
+        document(
+            "users/carol",
+            serde_json::json!({"name": "carol", "age": 25.5, "city": "tokyo", "active": false}),
+        )?,
+        document(
+            "users/dave",
+            serde_json::json!({"name": "dave", "city": "tokyo"}),
+        )?,
+        document(
+            "users/alice/posts/1",
+            serde_json::json!({"name": "post", "age": 99}),
+        )?,
+    ])
+}
+
+fn decode<T: serde::de::DeserializeOwned>(rows: &[Value]) -> anyhow::Result<Vec<T>> {
+    Ok(rows
+        .iter()
+        .map(from_value::<T>)
+        .collect::<Result<Vec<_>, _>>()?)
+}
+
+#[test]
+fn test_where_sort_limit() -> anyhow::Result<()> {
+    let pipeline = Pipeline::collection("users")
+        .where_(field("age").gte(lit(18)).and(field("active").eq(lit(true))))
+        .select(["name", "age"]);
+    let rows = execute_pipeline(&pipeline, &documents()?)?;
+    assert_eq!(
+        decode::<NameAge>(&rows)?,
+        vec![NameAge {
+            name: "alice".to_string(),
+            age: 30
+        }]
+    );
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Name {
+        name: String,
+    }
+    let pipeline = Pipeline::collection("users")
+        .sort([Ordering::descending(field("age"))])
+        .offset(1)
+        .limit(2)
+        .select(["name"]);
+    let rows = execute_pipeline(&pipeline, &documents()?)?;
+    assert_eq!(
+        decode::<Name>(&rows)?,
+        vec![
+            Name {
+                name: "carol".to_string()
+            },
+            Name {
+                name: "bob".to_string()
+            }
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_add_fields_and_arithmetic() -> anyhow::Result<()> {
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct S {
+        name: String,
+        double: f64,
+    }
+    let pipeline = Pipeline::collection("users")
+        .where_(field("age").exists())
+        .add_fields([("double", field("age").multiply(lit(2.0)))])
+        .select(["name", "double"]);
+    let rows = execute_pipeline(&pipeline, &documents()?)?;
+    assert_eq!(
+        decode::<S>(&rows)?,
+        vec![
+            S {
+                name: "alice".to_string(),
+                double: 60.0
+            },
+            S {
+                name: "bob".to_string(),
+                double: 34.0
+            },
+            S {
+                name: "carol".to_string(),
+                double: 51.0
+            },
+        ]
+    );
+
+    let pipeline = Pipeline::collection("users").where_(field("name").add(lit(1)).eq(lit(1)));
+    assert!(execute_pipeline(&pipeline, &documents()?).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_aggregate_and_distinct() -> anyhow::Result<()> {
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct S {
+        city: String,
+        count: i64,
+        max_age: Option<i64>,
+    }
+    let pipeline = Pipeline::collection("users").aggregate(
+        [("count", count_all()), ("max_age", field("age").maximum())],
+        [("city", field("city"))],
+    );
+    let rows = execute_pipeline(&pipeline, &documents()?)?;
+    assert_eq!(
+        decode::<S>(&rows)?,
+        vec![
+            S {
+                city: "osaka".to_string(),
+                count: 1,
+                max_age: Some(17)
+            },
+            S {
+                city: "tokyo".to_string(),
+                count: 3,
+                max_age: Some(30)
+            },
+        ]
+    );
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Total {
+        total: i64,
+        sum: f64,
+    }
+    let pipeline = Pipeline::collection("users").aggregate(
+        [("total", count_all()), ("sum", field("age").sum())],
+        std::iter::empty::<&str>(),
+    );
+    let rows = execute_pipeline(&pipeline, &documents()?)?;
+    assert_eq!(
+        decode::<Total>(&rows)?,
+        vec![Total {
+            total: 4,
+            sum: 72.5
+        }]
+    );
+
+    let pipeline = Pipeline::collection("users").distinct(["city"]);
+    let rows = execute_pipeline(&pipeline, &documents()?)?;
+    assert_eq!(rows.len(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_collection_group_and_value_ordering() -> anyhow::Result<()> {
+    let pipeline = Pipeline::collection_group("posts");
+    assert_eq!(execute_pipeline(&pipeline, &documents()?)?.len(), 1);
+    let pipeline = Pipeline::collection_group_in("users/alice", "posts");
+    assert_eq!(execute_pipeline(&pipeline, &documents()?)?.len(), 1);
+    let pipeline = Pipeline::collection_group_in("users/bob", "posts");
+    assert_eq!(execute_pipeline(&pipeline, &documents()?)?.len(), 0);
+    let pipeline = Pipeline::collection_group_in("users/ali", "posts");
+    assert_eq!(execute_pipeline(&pipeline, &documents()?)?.len(), 0);
+
+    // Numbers compare across integer and double; other types never match.
+    let documents = vec![
+        document("c/a", serde_json::json!({"v": 1}))?,
+        document("c/b", serde_json::json!({"v": 1.0}))?,
+        document("c/c", serde_json::json!({"v": "1"}))?,
+        document("c/d", serde_json::json!({"v": null}))?,
+    ];
+    let pipeline = Pipeline::collection("c").where_(field("v").eq(lit(1)));
+    assert_eq!(execute_pipeline(&pipeline, &documents)?.len(), 2);
+    let pipeline = Pipeline::collection("c").where_(field("v").gt(lit(0)));
+    assert_eq!(execute_pipeline(&pipeline, &documents)?.len(), 2);
+    let pipeline = Pipeline::collection("c").where_(field("v").is_null());
+    assert_eq!(execute_pipeline(&pipeline, &documents)?.len(), 1);
+
+    let pipeline = Pipeline::collection("c")
+        .sort([Ordering::ascending(field("v"))])
+        .select(["v"]);
+    let rows = execute_pipeline(&pipeline, &documents)?;
+    assert_eq!(
+        rows,
+        vec![
+            json(serde_json::json!({"v": null})),
+            json(serde_json::json!({"v": 1})),
+            json(serde_json::json!({"v": 1.0})),
+            json(serde_json::json!({"v": "1"})),
+        ]
+    );
+    Ok(())
+}
+
+fn paths(documents: &[Document]) -> Vec<&str> {
+    documents
+        .iter()
+        .map(|document| {
+            document
+                .name
+                .trim_start_matches("projects/p/databases/(default)/documents/")
+        })
+        .collect()
+}
+
+#[test]
+fn test_structured_query_filters() -> anyhow::Result<()> {
+    let run = |query: Query| -> anyhow::Result<Vec<String>> {
+        let documents = execute_structured_query("", &query.build()?, &documents()?)?;
+        Ok(paths(&documents).into_iter().map(String::from).collect())
+    };
+    // The inequality field is ordered implicitly.
+    assert_eq!(
+        run(Query::collection("users").where_field("age", Op::Gte, &18))?,
+        ["users/carol", "users/alice"]
+    );
+    assert_eq!(
+        run(Query::collection("users").where_field("city", Op::Neq, &"osaka"))?,
+        ["users/alice", "users/carol", "users/dave"]
+    );
+    assert_eq!(
+        run(Query::collection("users")
+            .where_field("city", Op::Eq, &"tokyo")
+            .where_field("active", Op::Eq, &true))?,
+        ["users/alice"]
+    );
+    // `age` is ordered implicitly, so `dave` without `age` is excluded.
+    assert_eq!(
+        run(Query::collection("users").where_(or([
+            field_filter("age", Op::Lt, &18)?,
+            field_filter("active", Op::Eq, &false)?,
+        ])))?,
+        ["users/bob", "users/carol"]
+    );
+    assert_eq!(
+        run(Query::collection("users").order_by("age", Direction::Descending))?,
+        ["users/alice", "users/carol", "users/bob"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_structured_query_value_semantics() -> anyhow::Result<()> {
+    let mut nan = document("c/e", serde_json::json!({}))?;
+    nan.fields.insert("v".to_string(), to_value(&f64::NAN)?);
+    let documents = vec![
+        document("c/a", serde_json::json!({"v": 1}))?,
+        document("c/b", serde_json::json!({"v": 1.0}))?,
+        document("c/c", serde_json::json!({"v": "1"}))?,
+        document("c/d", serde_json::json!({"v": null}))?,
+        nan,
+        document("c/f", serde_json::json!({}))?,
+        document("c/g", serde_json::json!({"v": [1, "x"]}))?,
+    ];
+    let run = |query: Query| -> anyhow::Result<Vec<String>> {
+        let documents = execute_structured_query("", &query.build()?, &documents)?;
+        Ok(paths(&documents).into_iter().map(String::from).collect())
+    };
+    let c = || Query::collection("c");
+
+    assert_eq!(run(c().where_field("v", Op::Eq, &1))?, ["c/a", "c/b"]);
+    assert_eq!(run(c().where_field("v", Op::Eq, &()))?, ["c/d"]);
+    assert_eq!(run(c().where_field("v", Op::Eq, &f64::NAN))?, ["c/e"]);
+    // NaN sorts before any other number.
+    assert_eq!(
+        run(c().where_field("v", Op::Lt, &2))?,
+        ["c/e", "c/a", "c/b"]
+    );
+    assert_eq!(run(c().where_field("v", Op::Gt, &0.5))?, ["c/a", "c/b"]);
+    // Not-equal filters match any type, but neither null nor missing fields.
+    assert_eq!(
+        run(c().where_field("v", Op::Neq, &1))?,
+        ["c/e", "c/c", "c/g"]
+    );
+    assert_eq!(
+        run(c().where_field("v", Op::NotIn, &[1.0]))?,
+        ["c/e", "c/c", "c/g"]
+    );
+    assert_eq!(
+        run(c().where_field("v", Op::NotIn, &[None, Some(2)]))?,
+        Vec::<String>::new()
+    );
+    assert_eq!(
+        run(c().where_unary("v", UnaryOp::IsNotNull))?,
+        ["c/e", "c/a", "c/b", "c/c", "c/g"]
+    );
+    assert_eq!(
+        run(c().where_unary("v", UnaryOp::IsNotNan))?,
+        ["c/a", "c/b", "c/c", "c/g"]
+    );
+    assert_eq!(
+        run(c().where_field("v", Op::In, &(1.0, "1")))?,
+        ["c/a", "c/b", "c/c"]
+    );
+    assert_eq!(run(c().where_field("v", Op::ArrayContains, &"x"))?, ["c/g"]);
+    assert_eq!(
+        run(c().where_field("v", Op::ArrayContainsAny, &[1.0]))?,
+        ["c/g"]
+    );
+
+    // The full value ordering.
+    assert_eq!(
+        run(c().order_by("v", Direction::Ascending))?,
+        ["c/d", "c/e", "c/a", "c/b", "c/c", "c/g"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_structured_query_cursors_and_limits() -> anyhow::Result<()> {
+    let documents = documents()?;
+    let run = |query: Query| -> anyhow::Result<Vec<String>> {
+        let documents = execute_structured_query("", &query.build()?, &documents)?;
+        Ok(paths(&documents).into_iter().map(String::from).collect())
+    };
+    let by_age = || Query::collection("users").order_by("age", Direction::Descending);
+
+    assert_eq!(
+        run(by_age().start_after(&(30,)))?,
+        ["users/carol", "users/bob"]
+    );
+    assert_eq!(
+        run(by_age().start_at(&(25.5,)).end_at(&(17,)))?,
+        ["users/carol", "users/bob"]
+    );
+    assert_eq!(
+        run(by_age().end_before(&(17,)))?,
+        ["users/alice", "users/carol"]
+    );
+    // A value which is not a sequence is a cursor with one value.
+    assert_eq!(
+        run(by_age().start_after(&30))?,
+        ["users/carol", "users/bob"]
+    );
+    assert_eq!(run(by_age().offset(1).limit(1))?, ["users/carol"]);
+    assert_eq!(run(by_age().limit(0))?, Vec::<String>::new());
+
+    // A cursor on the implicit `__name__` ordering.
+    let carol = Reference("projects/p/databases/(default)/documents/users/carol".to_string());
+    assert_eq!(
+        run(Query::collection("users")
+            .order_by("city", Direction::Ascending)
+            .start_at(&("tokyo", &carol)))?,
+        ["users/carol", "users/dave"]
+    );
+    assert!(run(by_age().start_at(&(1, &carol, 2))).is_err());
+
+    // `limit_to_last` returns the last results in the query order.
+    let by_age = Query::collection("users")
+        .order_by("age", Direction::Ascending)
+        .build()?;
+    let last = execute_structured_query_limit_to_last("", &by_age, 2, &documents)?;
+    assert_eq!(paths(&last), ["users/carol", "users/alice"]);
+    let before_alice = Query::collection("users")
+        .order_by("age", Direction::Ascending)
+        .end_before(&(30,))
+        .build()?;
+    let last = execute_structured_query_limit_to_last("", &before_alice, 1, &documents)?;
+    assert_eq!(paths(&last), ["users/carol"]);
+    Ok(())
+}
+
+#[test]
+fn test_structured_query_scope_and_projection() -> anyhow::Result<()> {
+    let mut documents = documents()?;
+    documents.push(document(
+        "users/bob/posts/2",
+        serde_json::json!({"name": "reply", "meta": {"x": 1, "y": 2}}),
+    )?);
+    let run = |parent: &str, query: Query| -> anyhow::Result<Vec<String>> {
+        let documents = execute_structured_query(parent, &query.build()?, &documents)?;
+        Ok(paths(&documents).into_iter().map(String::from).collect())
+    };
+
+    assert_eq!(
+        run("", Query::collection_group("posts"))?,
+        ["users/alice/posts/1", "users/bob/posts/2"]
+    );
+    assert_eq!(
+        run(
+            "projects/p/databases/(default)/documents/users/bob",
+            Query::collection_group("posts")
+        )?,
+        ["users/bob/posts/2"]
+    );
+    assert_eq!(
+        run("users/alice", Query::collection("posts"))?,
+        ["users/alice/posts/1"]
+    );
+    assert_eq!(run("", Query::collection("posts"))?, Vec::<String>::new());
+    assert_eq!(
+        run("users/carol", Query::collection("posts"))?,
+        Vec::<String>::new()
+    );
+
+    let query = Query::collection("posts")
+        .select(["meta.x", "name", "missing"])
+        .build()?;
+    let projected = execute_structured_query("users/bob", &query, &documents)?;
+    assert_eq!(paths(&projected), ["users/bob/posts/2"]);
+    assert_eq!(
+        Value {
+            value_type: Some(ValueType::MapValue(MapValue {
+                fields: projected[0].fields.clone(),
+            })),
+        },
+        json(serde_json::json!({"name": "reply", "meta": {"x": 1}}))
+    );
+
+    let query = Query::collection("posts").select(["__name__"]).build()?;
+    let projected = execute_structured_query("users/bob", &query, &documents)?;
+    assert!(projected[0].fields.is_empty());
+    Ok(())
+}
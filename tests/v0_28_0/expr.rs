@@ -0,0 +1,135 @@
+use serde_firestore_value::expr::{Expr, conditional, field, function, lit, names, try_lit, var};
+use serde_firestore_value::google::firestore::v1::{Value, value::ValueType};
+use serde_firestore_value::{
+    Bytes, FieldReference, Function, LatLng, Reference, Timestamp, VariableReference, from_value,
+    to_value,
+};
+
+fn field_value(s: &str) -> Value {
+    Value {
+        value_type: Some(ValueType::FieldReferenceValue(s.to_string())),
+    }
+}
+
+fn integer_value(i: i64) -> Value {
+    Value {
+        value_type: Some(ValueType::IntegerValue(i)),
+    }
+}
+
+#[test]
+fn test_build() -> anyhow::Result<()> {
+    let expr = field("price")
+        .multiply(lit(2))
+        .gt(lit(100))
+        .and(field("active").eq(lit(true)));
+    let f = Function::try_from(expr)?;
+    assert_eq!(f.name, names::AND);
+    assert_eq!(f.args.len(), 2);
+
+    let gt: Function = from_value(&f.args[0])?;
+    assert_eq!(gt.name, "greater_than");
+    assert_eq!(gt.args[1], integer_value(100));
+    let multiply: Function = from_value(&gt.args[0])?;
+    assert_eq!(multiply.name, "multiply");
+    assert_eq!(multiply.args, vec![field_value("price"), integer_value(2)]);
+
+    let eq: Function = from_value(&f.args[1])?;
+    assert_eq!(eq.name, "equal");
+    assert_eq!(
+        eq.args,
+        vec![
+            field_value("active"),
+            Value {
+                value_type: Some(ValueType::BooleanValue(true))
+            }
+        ]
+    );
+    assert_eq!(
+        from_value::<FieldReference>(&eq.args[0])?,
+        FieldReference("active".to_string())
+    );
+    Ok(())
+}
+
+#[test]
+fn test_variadic() -> anyhow::Result<()> {
+    let expr = field("a").and(field("b")).and(field("c")).or(field("d"));
+    let Expr::Function(or) = expr else {
+        anyhow::bail!("not a function");
+    };
+    assert_eq!(or.name, "or");
+    assert_eq!(or.args.len(), 2);
+    assert_eq!(or.args[0].function_name(), Some("and"));
+    let Expr::Function(and) = &or.args[0] else {
+        anyhow::bail!("not a function");
+    };
+    assert_eq!(and.args, vec![field("a"), field("b"), field("c")]);
+    Ok(())
+}
+
+#[test]
+fn test_lit() -> anyhow::Result<()> {
+    assert_eq!(lit("x"), Expr::Constant(to_value(&"x")?));
+    assert_eq!(lit(FieldReference("a".to_string())), field("a"));
+    assert_eq!(lit(VariableReference("v".to_string())), var("v"));
+    assert_eq!(
+        lit(Function {
+            name: "not".to_string(),
+            args: vec![field_value("a")],
+            options: Default::default(),
+        }),
+        field("a").not()
+    );
+    assert!(try_lit(f64::NAN).is_ok());
+    assert!(try_lit(u64::MAX).is_err());
+
+    // `lit` is the same as `try_lit` for the types it accepts.
+    assert_eq!(lit(u32::MAX), try_lit(u32::MAX)?);
+    assert_eq!(lit(1.5_f32), try_lit(1.5_f32)?);
+    assert_eq!(lit('c'), try_lit('c')?);
+    assert_eq!(lit(()), try_lit(())?);
+    assert_eq!(lit(Some(1)), try_lit(Some(1))?);
+    assert_eq!(lit(None::<i64>), try_lit(None::<i64>)?);
+    assert_eq!(lit(["a", "b"]), try_lit(["a", "b"])?);
+    assert_eq!(lit(vec![vec![1]]), try_lit(vec![vec![1]])?);
+    assert_eq!(lit(Bytes(vec![1, 2])), try_lit(Bytes(vec![1, 2]))?);
+    assert_eq!(
+        lit(LatLng {
+            latitude: 1.5,
+            longitude: 2.5
+        }),
+        try_lit(LatLng {
+            latitude: 1.5,
+            longitude: 2.5
+        })?
+    );
+    let reference = Reference("projects/p/databases/d/documents/c/1".to_string());
+    assert_eq!(lit(&reference), try_lit(&reference)?);
+    let timestamp = Timestamp {
+        seconds: 1,
+        nanos: 2,
+    };
+    assert_eq!(lit(timestamp), try_lit(timestamp)?);
+    Ok(())
+}
+
+#[test]
+fn test_round_trip() -> anyhow::Result<()> {
+    let expr = conditional(
+        field("n").is_null(),
+        lit(0),
+        function("add", [field("n"), var("offset")]),
+    );
+    let value = Value::from(expr.clone());
+    assert_eq!(Expr::from(value.clone()), expr);
+
+    let f: Function = from_value(&value)?;
+    assert_eq!(f.name, "conditional");
+    assert_eq!(Expr::from(f.clone()), expr);
+    assert_eq!(Function::try_from(Expr::from(f.clone()))?, f);
+    assert_eq!(to_value(&f)?, value);
+
+    assert!(Function::try_from(field("a")).is_err());
+    Ok(())
+}
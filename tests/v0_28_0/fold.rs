@@ -0,0 +1,120 @@
+use serde_firestore_value::fold::ValueFolder;
+use serde_firestore_value::google::firestore::v1::{MapValue, Value, value::ValueType};
+
+#[test]
+fn test_identity() -> anyhow::Result<()> {
+    use serde_firestore_value::{LatLng, Reference, Timestamp, to_value};
+
+    struct Identity;
+    impl ValueFolder for Identity {}
+
+    #[derive(serde::Serialize)]
+    struct S {
+        b: bool,
+        g: LatLng,
+        r: Reference,
+        t: Timestamp,
+        v: Vec<Option<i64>>,
+    }
+
+    let value = to_value(&S {
+        b: true,
+        g: LatLng {
+            latitude: 1_f64,
+            longitude: 2_f64,
+        },
+        r: Reference("projects/p/databases/d/documents/c/1".to_string()),
+        t: Timestamp {
+            seconds: 1,
+            nanos: 2,
+        },
+        v: vec![Some(1), None],
+    })?;
+    assert_eq!(Identity.fold_value(value.clone()), value);
+    Ok(())
+}
+
+#[test]
+fn test_redact() -> anyhow::Result<()> {
+    use serde_firestore_value::fold::fold_map;
+    use serde_firestore_value::to_value;
+
+    struct Redact;
+    impl ValueFolder for Redact {
+        fn fold_map(&mut self, value: MapValue) -> Value {
+            let mut value = fold_map(self, value);
+            if let Some(password) = value.fields.get_mut("password") {
+                *password = Value {
+                    value_type: Some(ValueType::NullValue(0)),
+                };
+            }
+            Value {
+                value_type: Some(ValueType::MapValue(value)),
+            }
+        }
+    }
+
+    #[derive(serde::Serialize)]
+    struct User {
+        name: String,
+        password: Option<String>,
+    }
+
+    #[derive(serde::Serialize)]
+    struct S {
+        users: Vec<User>,
+    }
+
+    let value = to_value(&S {
+        users: vec![User {
+            name: "bouzuya".to_string(),
+            password: Some("secret".to_string()),
+        }],
+    })?;
+    assert_eq!(
+        Redact.fold_value(value),
+        to_value(&S {
+            users: vec![User {
+                name: "bouzuya".to_string(),
+                password: None,
+            }],
+        })?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_function_args() -> anyhow::Result<()> {
+    use serde_firestore_value::{FieldReference, Function, to_value};
+
+    struct Rename;
+    impl ValueFolder for Rename {
+        fn fold_field_reference(&mut self, value: String) -> Value {
+            Value {
+                value_type: Some(ValueType::FieldReferenceValue(format!("new_{}", value))),
+            }
+        }
+    }
+
+    let function = Function {
+        name: "eq".to_string(),
+        args: vec![
+            to_value(&FieldReference("a".to_string()))?,
+            to_value(&1_i64)?,
+        ],
+        options: Default::default(),
+    };
+    let expected = Function {
+        name: "eq".to_string(),
+        args: vec![
+            to_value(&FieldReference("new_a".to_string()))?,
+            to_value(&1_i64)?,
+        ],
+        options: Default::default(),
+    };
+    assert_eq!(
+        Rename.fold_value(to_value(&function)?),
+        to_value(&expected)?
+    );
+    Ok(())
+}
@@ -0,0 +1,101 @@
+#[cfg(feature = "geo-types")]
+fn geo_point(latitude: f64, longitude: f64) -> serde_firestore_value::google::firestore::v1::Value {
+    use serde_firestore_value::google::firestore::v1::{Value, value::ValueType};
+    use serde_firestore_value::google::r#type::LatLng;
+
+    Value {
+        value_type: Some(ValueType::GeoPointValue(LatLng {
+            latitude,
+            longitude,
+        })),
+    }
+}
+
+#[cfg(feature = "geo-types")]
+#[test]
+fn test_conversions() -> anyhow::Result<()> {
+    use serde_firestore_value::LatLng;
+
+    let lat_lng = LatLng {
+        latitude: 35.6,
+        longitude: 139.7,
+    };
+    let point = geo_types::Point::from(lat_lng);
+    assert_eq!(point.x(), 139.7);
+    assert_eq!(point.y(), 35.6);
+    assert_eq!(LatLng::try_from(point)?, lat_lng);
+
+    let coord = geo_types::Coord::from(lat_lng);
+    assert_eq!(coord, geo_types::coord! { x: 139.7, y: 35.6 });
+    assert_eq!(LatLng::try_from(coord)?, lat_lng);
+
+    for (x, y) in [
+        (0.0, 90.1),
+        (0.0, -90.1),
+        (180.1, 0.0),
+        (-180.1, 0.0),
+        (f64::NAN, 0.0),
+    ] {
+        assert!(LatLng::try_from(geo_types::Point::new(x, y)).is_err());
+    }
+    // swapped axes are caught when the latitude is out of range
+    assert!(LatLng::try_from(geo_types::Point::new(35.6, 139.7)).is_err());
+    Ok(())
+}
+
+#[cfg(feature = "geo-types")]
+#[test]
+fn test_with() -> anyhow::Result<()> {
+    use serde_firestore_value::google::firestore::v1::{
+        ArrayValue, MapValue, Value, value::ValueType,
+    };
+    use serde_firestore_value::with::{geo_point, line_string_as_geo_points};
+    use serde_firestore_value::{from_value, to_value};
+
+    #[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct S {
+        #[serde(with = "geo_point")]
+        p: geo_types::Point<f64>,
+        #[serde(with = "line_string_as_geo_points")]
+        l: geo_types::LineString<f64>,
+    }
+
+    let o = S {
+        p: geo_types::Point::new(139.7, 35.6),
+        l: geo_types::LineString::from(vec![(139.7, 35.6), (-0.1, 51.5)]),
+    };
+    let s = to_value(&o)?;
+    let ValueType::MapValue(MapValue { fields }) = s.value_type.clone().unwrap() else {
+        anyhow::bail!("not a map");
+    };
+    assert_eq!(fields["p"], geo_point(35.6, 139.7));
+    assert_eq!(
+        fields["l"],
+        Value {
+            value_type: Some(ValueType::ArrayValue(ArrayValue {
+                values: vec![geo_point(35.6, 139.7), geo_point(51.5, -0.1)],
+            })),
+        }
+    );
+    assert_eq!(from_value::<'_, S>(&s)?, o);
+
+    assert!(
+        to_value(&S {
+            p: geo_types::Point::new(35.6, 139.7),
+            l: geo_types::LineString::new(vec![]),
+        })
+        .is_err()
+    );
+    assert!(
+        to_value(&S {
+            p: geo_types::Point::new(139.7, 35.6),
+            l: geo_types::LineString::from(vec![(0.0, 91.0)]),
+        })
+        .is_err()
+    );
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct P(#[serde(with = "geo_point")] geo_types::Point<f64>);
+    assert!(from_value::<'_, P>(&geo_point(139.7, 35.6)).is_err());
+    Ok(())
+}
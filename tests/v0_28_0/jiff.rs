@@ -0,0 +1,125 @@
+#[cfg(feature = "jiff")]
+#[test]
+fn test_try_from() -> anyhow::Result<()> {
+    use serde_firestore_value::Timestamp;
+
+    let timestamp = Timestamp {
+        seconds: -2,
+        nanos: 500_000_000,
+    };
+    let jiff_timestamp = jiff::Timestamp::try_from(timestamp)?;
+    assert_eq!(
+        jiff_timestamp,
+        "1969-12-31T23:59:58.5Z".parse::<jiff::Timestamp>()?
+    );
+    assert_eq!(Timestamp::try_from(jiff_timestamp)?, timestamp);
+
+    let zoned = jiff::Zoned::try_from(timestamp)?;
+    assert_eq!(zoned.time_zone(), &jiff::tz::TimeZone::UTC);
+    assert_eq!(Timestamp::try_from(zoned)?, timestamp);
+
+    assert!(
+        jiff::Timestamp::try_from(Timestamp {
+            seconds: 0,
+            nanos: 1_000_000_000,
+        })
+        .is_err()
+    );
+    assert!(
+        jiff::Timestamp::try_from(Timestamp {
+            seconds: 0,
+            nanos: -1,
+        })
+        .is_err()
+    );
+    assert!(
+        jiff::Timestamp::try_from(Timestamp {
+            seconds: i64::MAX,
+            nanos: 0,
+        })
+        .is_err()
+    );
+    Ok(())
+}
+
+#[cfg(feature = "jiff")]
+#[test]
+fn test_with() -> anyhow::Result<()> {
+    use serde_firestore_value::google::firestore::v1::{MapValue, Value, value::ValueType};
+    use serde_firestore_value::with::{
+        jiff_timestamp_as_timestamp, option_jiff_timestamp_as_timestamp,
+    };
+    use serde_firestore_value::{from_value, to_value};
+
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct S {
+        #[serde(with = "jiff_timestamp_as_timestamp")]
+        a: jiff::Timestamp,
+        #[serde(with = "option_jiff_timestamp_as_timestamp")]
+        b: Option<jiff::Timestamp>,
+        #[serde(with = "option_jiff_timestamp_as_timestamp")]
+        c: Option<jiff::Timestamp>,
+    }
+
+    let o = S {
+        a: "1970-01-01T00:00:01.000000002Z".parse()?,
+        b: Some("1969-12-31T23:59:59.5Z".parse()?),
+        c: None,
+    };
+    let s = to_value(&o)?;
+    let ValueType::MapValue(MapValue { fields }) = s.value_type.clone().unwrap() else {
+        anyhow::bail!("not a map");
+    };
+    assert_eq!(
+        fields["a"],
+        Value {
+            value_type: Some(ValueType::TimestampValue(prost_types::Timestamp {
+                seconds: 1,
+                nanos: 2,
+            })),
+        }
+    );
+    assert_eq!(
+        fields["b"],
+        Value {
+            value_type: Some(ValueType::TimestampValue(prost_types::Timestamp {
+                seconds: -1,
+                nanos: 500_000_000,
+            })),
+        }
+    );
+    assert_eq!(
+        fields["c"],
+        Value {
+            value_type: Some(ValueType::NullValue(0)),
+        }
+    );
+    assert_eq!(from_value::<'_, S>(&s)?, o);
+
+    let invalid = Value {
+        value_type: Some(ValueType::MapValue(MapValue {
+            fields: fields
+                .into_iter()
+                .map(|(k, v)| {
+                    if k == "a" {
+                        (
+                            k,
+                            Value {
+                                value_type: Some(ValueType::TimestampValue(
+                                    prost_types::Timestamp {
+                                        seconds: 0,
+                                        nanos: -1,
+                                    },
+                                )),
+                            },
+                        )
+                    } else {
+                        (k, v)
+                    }
+                })
+                .collect(),
+        })),
+    };
+    assert!(from_value::<'_, S>(&invalid).is_err());
+    Ok(())
+}
@@ -0,0 +1,28 @@
+#![allow(missing_docs)]
+
+mod borrowed;
+mod bytes;
+mod chrono_calendar;
+mod decimal;
+mod duration;
+mod embedded;
+mod eval;
+mod expr;
+mod fold;
+mod geo_types;
+mod jiff;
+mod non_firestore_format;
+mod owned_deserializer;
+mod raw_value;
+mod rest_json;
+mod serde_as;
+mod serde_json;
+mod stage;
+mod structured_query;
+mod system_time;
+mod text;
+mod time_calendar;
+mod typed_args;
+mod uuid;
+mod validate;
+mod visit;
@@ -0,0 +1,116 @@
+use serde_firestore_value::google::firestore::v1::{Value, value::ValueType};
+use serde_firestore_value::{
+    FieldReference, LatLng, Reference, Timestamp, VariableReference, from_value, to_value,
+};
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct S {
+    f: FieldReference,
+    l: LatLng,
+    r: Reference,
+    t: Timestamp,
+    v: VariableReference,
+}
+
+fn s() -> S {
+    S {
+        f: FieldReference("a.b".to_string()),
+        l: LatLng {
+            latitude: 1.5,
+            longitude: 2.5,
+        },
+        r: Reference("projects/p/databases/d/documents/c/1".to_string()),
+        t: Timestamp {
+            seconds: 1,
+            nanos: 2_000_000,
+        },
+        v: VariableReference("x".to_string()),
+    }
+}
+
+#[test]
+fn test_serde_json() -> anyhow::Result<()> {
+    let json = serde_json::json!({
+        "f": "a.b",
+        "l": { "lat": 1.5, "lng": 2.5 },
+        "r": "projects/p/databases/d/documents/c/1",
+        "t": "1970-01-01T00:00:01.002Z",
+        "v": "x",
+    });
+    assert_eq!(serde_json::to_value(s())?, json);
+    assert_eq!(serde_json::to_string(&s())?, serde_json::to_string(&json)?);
+    assert_eq!(serde_json::from_value::<S>(json)?, s());
+    assert_eq!(
+        serde_json::from_str::<S>(&serde_json::to_string(&s())?)?,
+        s()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_serde_json_alternative_encodings() -> anyhow::Result<()> {
+    let json = serde_json::json!({
+        "f": "a.b",
+        "l": { "latitude": 1.5, "longitude": 2.5 },
+        "r": "projects/p/databases/d/documents/c/1",
+        "t": { "seconds": 1, "nanos": 2_000_000 },
+        "v": "x",
+    });
+    assert_eq!(serde_json::from_value::<S>(json)?, s());
+    assert_eq!(
+        serde_json::from_value::<Timestamp>(serde_json::json!("1970-01-01T09:00:01.002+09:00"))?,
+        s().t
+    );
+    assert!(serde_json::from_value::<Timestamp>(serde_json::json!("1970-01-01")).is_err());
+    assert!(
+        serde_json::to_value(Timestamp {
+            seconds: i64::MAX,
+            nanos: 0
+        })
+        .is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_firestore_value_is_unchanged() -> anyhow::Result<()> {
+    let value = to_value(&s())?;
+    let Some(ValueType::MapValue(map)) = &value.value_type else {
+        anyhow::bail!("not mapValue");
+    };
+    assert_eq!(
+        map.fields["t"],
+        Value {
+            value_type: Some(ValueType::TimestampValue(prost_types::Timestamp {
+                seconds: 1,
+                nanos: 2_000_000,
+            })),
+        }
+    );
+    assert_eq!(
+        map.fields["r"],
+        Value {
+            value_type: Some(ValueType::ReferenceValue(
+                "projects/p/databases/d/documents/c/1".to_string()
+            )),
+        }
+    );
+    assert_eq!(from_value::<'_, S>(&value)?, s());
+    Ok(())
+}
+
+#[test]
+fn test_serde_json_buffered() -> anyhow::Result<()> {
+    // `untagged` deserializes from a buffered copy of the input.
+    #[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+    #[serde(untagged)]
+    enum E {
+        S(S),
+        N(i64),
+    }
+
+    let e = E::S(s());
+    assert_eq!(serde_json::to_value(&e)?, serde_json::to_value(s())?);
+    assert_eq!(serde_json::from_value::<E>(serde_json::to_value(&e)?)?, e);
+    Ok(())
+}
@@ -0,0 +1,169 @@
+use serde_firestore_value::google::firestore::v1::{Value, value::ValueType};
+use serde_firestore_value::{
+    FieldReference, Function, LatLng, Pipeline, Reference, Stage, Timestamp, from_value,
+    from_value_owned, to_value,
+};
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+enum E {
+    A,
+    B(i64),
+    C(i64, String),
+    D { x: String },
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct S {
+    b: bool,
+    c: char,
+    d: f64,
+    e: Vec<E>,
+    f: FieldReference,
+    g: LatLng,
+    i: i8,
+    m: std::collections::BTreeMap<String, Option<String>>,
+    o: Option<Box<S>>,
+    p: Pipeline,
+    r: Reference,
+    s: String,
+    t: Timestamp,
+    u: (u16, String),
+    x: Function,
+}
+
+fn s() -> S {
+    let function = Function {
+        name: "f".to_string(),
+        args: vec![Value {
+            value_type: Some(ValueType::IntegerValue(1)),
+        }],
+        options: Default::default(),
+    };
+    let inner = S {
+        b: false,
+        c: 'c',
+        d: 1.5,
+        e: vec![],
+        f: FieldReference("a.b".to_string()),
+        g: LatLng {
+            latitude: 1.0,
+            longitude: 2.0,
+        },
+        i: -1,
+        m: Default::default(),
+        o: None,
+        p: Pipeline { stages: vec![] },
+        r: Reference("projects/p/databases/d/documents/c/1".to_string()),
+        s: "inner".to_string(),
+        t: Timestamp {
+            seconds: 1,
+            nanos: 2,
+        },
+        u: (1, "u".to_string()),
+        x: function.clone(),
+    };
+    S {
+        b: true,
+        e: vec![
+            E::A,
+            E::B(1),
+            E::C(2, "c".to_string()),
+            E::D { x: "x".to_string() },
+        ],
+        m: std::collections::BTreeMap::from([
+            ("k1".to_string(), Some("v".to_string())),
+            ("k2".to_string(), None),
+        ]),
+        o: Some(Box::new(inner.clone())),
+        p: Pipeline {
+            stages: vec![Stage {
+                name: "where".to_string(),
+                args: vec![Value {
+                    value_type: Some(ValueType::FunctionValue(
+                        serde_firestore_value::google::firestore::v1::Function {
+                            name: function.name.clone(),
+                            args: function.args.clone(),
+                            options: Default::default(),
+                        },
+                    )),
+                }],
+                options: Default::default(),
+            }],
+        },
+        s: "outer".to_string(),
+        ..inner
+    }
+}
+
+#[test]
+fn test_same_as_from_value() -> anyhow::Result<()> {
+    let value = to_value(&s())?;
+    let borrowed = from_value::<'_, S>(&value)?;
+    let owned = from_value_owned::<S>(value.clone())?;
+    assert_eq!(borrowed, s());
+    assert_eq!(owned, s());
+
+    assert_eq!(
+        from_value_owned::<serde_json::Value>(value.clone())?,
+        from_value::<'_, serde_json::Value>(&value)?
+    );
+    assert_eq!(
+        from_value_owned::<serde_firestore_value::RawValue>(value.clone())?.0,
+        value
+    );
+    Ok(())
+}
+
+#[test]
+fn test_moves_strings() -> anyhow::Result<()> {
+    let s = "a".repeat(1_024);
+    let ptr = s.as_ptr();
+    let value = Value {
+        value_type: Some(ValueType::StringValue(s)),
+    };
+    let d = from_value_owned::<String>(value)?;
+    assert_eq!(d.as_ptr(), ptr);
+
+    let s = "b".repeat(1_024);
+    let ptr = s.as_ptr();
+    let value = Value {
+        value_type: Some(ValueType::MapValue(
+            serde_firestore_value::google::firestore::v1::MapValue {
+                fields: [(
+                    "k".to_string(),
+                    Value {
+                        value_type: Some(ValueType::ArrayValue(
+                            serde_firestore_value::google::firestore::v1::ArrayValue {
+                                values: vec![Value {
+                                    value_type: Some(ValueType::StringValue(s)),
+                                }],
+                            },
+                        )),
+                    },
+                )]
+                .into_iter()
+                .collect(),
+            },
+        )),
+    };
+    let d = from_value_owned::<std::collections::BTreeMap<String, Vec<String>>>(value)?;
+    assert_eq!(d["k"][0].as_ptr(), ptr);
+    Ok(())
+}
+
+#[test]
+fn test_errors() {
+    assert!(
+        from_value_owned::<String>(Value {
+            value_type: Some(ValueType::IntegerValue(1)),
+        })
+        .is_err()
+    );
+    assert!(from_value_owned::<String>(Value { value_type: None }).is_err());
+    assert!(
+        from_value_owned::<E>(Value {
+            value_type: Some(ValueType::StringValue("Z".to_string())),
+        })
+        .is_err()
+    );
+}
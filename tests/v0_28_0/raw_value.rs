@@ -0,0 +1,155 @@
+#[cfg(feature = "btree-map")]
+use std::collections::BTreeMap as Map;
+#[cfg(feature = "hash-map")]
+use std::collections::HashMap as Map;
+
+use serde_firestore_value::google::firestore::v1::{
+    ArrayValue, Function, MapValue, Pipeline, Value, pipeline::Stage, value::ValueType,
+};
+use serde_firestore_value::{RawValue, from_value, to_value};
+
+fn v(value_type: ValueType) -> Value {
+    Value {
+        value_type: Some(value_type),
+    }
+}
+
+fn payload() -> Value {
+    let function = Function {
+        name: "add".to_string(),
+        args: vec![
+            v(ValueType::FieldReferenceValue("a".to_string())),
+            v(ValueType::IntegerValue(1)),
+        ],
+        options: Map::from([("o".to_string(), v(ValueType::BooleanValue(true)))]),
+    };
+    v(ValueType::MapValue(MapValue {
+        fields: Map::from([
+            ("n".to_string(), v(ValueType::NullValue(0))),
+            ("b".to_string(), v(ValueType::BooleanValue(false))),
+            ("i".to_string(), v(ValueType::IntegerValue(-1))),
+            ("d".to_string(), v(ValueType::DoubleValue(2.0))),
+            (
+                "t".to_string(),
+                v(ValueType::TimestampValue(prost_types::Timestamp {
+                    seconds: 1,
+                    nanos: 2,
+                })),
+            ),
+            ("s".to_string(), v(ValueType::StringValue("s".to_string()))),
+            (
+                "r".to_string(),
+                v(ValueType::ReferenceValue(
+                    "projects/p/databases/d/documents/c/1".to_string(),
+                )),
+            ),
+            (
+                "g".to_string(),
+                v(ValueType::GeoPointValue(
+                    serde_firestore_value::google::r#type::LatLng {
+                        latitude: 1.5,
+                        longitude: 2.0,
+                    },
+                )),
+            ),
+            (
+                "a".to_string(),
+                v(ValueType::ArrayValue(ArrayValue {
+                    values: vec![v(ValueType::IntegerValue(1)), v(ValueType::NullValue(0))],
+                })),
+            ),
+            (
+                "x".to_string(),
+                v(ValueType::VariableReferenceValue("x".to_string())),
+            ),
+            (
+                "f".to_string(),
+                v(ValueType::FunctionValue(function.clone())),
+            ),
+            (
+                "p".to_string(),
+                v(ValueType::PipelineValue(Pipeline {
+                    stages: vec![Stage {
+                        name: "where".to_string(),
+                        args: vec![v(ValueType::FunctionValue(function))],
+                        options: Map::new(),
+                    }],
+                })),
+            ),
+        ]),
+    }))
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+struct S {
+    id: String,
+    payload: RawValue,
+    #[serde(with = "serde_firestore_value::with::raw_value")]
+    other: Value,
+}
+
+#[test]
+fn test_firestore_value() -> anyhow::Result<()> {
+    let o = S {
+        id: "1".to_string(),
+        payload: RawValue(payload()),
+        other: v(ValueType::BytesValue(bytes(&[1_u8, 2, 3]))),
+    };
+    let s = to_value(&o)?;
+    let Some(ValueType::MapValue(MapValue { fields })) = &s.value_type else {
+        anyhow::bail!("not mapValue");
+    };
+    assert_eq!(fields["payload"], payload());
+    assert_eq!(fields["other"], o.other);
+    assert_eq!(from_value::<'_, S>(&s)?, o);
+    Ok(())
+}
+
+#[test]
+fn test_serde_json() -> anyhow::Result<()> {
+    let o = S {
+        id: "1".to_string(),
+        payload: RawValue(payload()),
+        other: v(ValueType::StringValue("s".to_string())),
+    };
+    let json = serde_json::to_string(&o)?;
+    assert_eq!(serde_json::from_str::<S>(&json)?, o);
+    Ok(())
+}
+
+#[test]
+fn test_value_type_none() -> anyhow::Result<()> {
+    let o = RawValue(Value { value_type: None });
+    assert!(to_value(&o).is_err());
+
+    let nested = RawValue(v(ValueType::ArrayValue(ArrayValue {
+        values: vec![Value { value_type: None }],
+    })));
+    assert!(to_value(&nested).is_err());
+    assert!(serde_json::to_string(&o).is_err());
+    assert!(serde_json::to_string(&nested).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_reserved_map_key() -> anyhow::Result<()> {
+    let o = RawValue(v(ValueType::MapValue(MapValue {
+        fields: Map::from([(
+            "$__serde-firestore-value_private_reference".to_string(),
+            v(ValueType::StringValue("s".to_string())),
+        )]),
+    })));
+    assert!(to_value(&o).is_err());
+    assert!(serde_json::to_string(&o).is_err());
+    Ok(())
+}
+
+#[cfg(feature = "bytes")]
+fn bytes(data: &'static [u8]) -> prost::bytes::Bytes {
+    prost::bytes::Bytes::from(data)
+}
+
+#[cfg(feature = "vec-u8")]
+fn bytes(data: &'static [u8]) -> Vec<u8> {
+    data.to_vec()
+}
@@ -0,0 +1,188 @@
+#[cfg(feature = "rest-json")]
+#[test]
+fn test_value() -> anyhow::Result<()> {
+    use serde_firestore_value::google::firestore::v1::Value;
+    use serde_firestore_value::{
+        FieldReference, LatLng, Reference, Timestamp, from_rest_json, to_rest_json, to_value,
+    };
+
+    #[derive(serde::Serialize)]
+    struct T {
+        a: Vec<Option<i64>>,
+        b: bool,
+        d: f64,
+        e: Vec<i64>,
+        f: FieldReference,
+        g: LatLng,
+        i: i64,
+        m: std::collections::BTreeMap<String, f64>,
+        r: Reference,
+        s: String,
+        t: Timestamp,
+    }
+
+    let value = to_value(&T {
+        a: vec![Some(1), None],
+        b: true,
+        d: 1.5,
+        e: vec![],
+        f: FieldReference("x.y".to_string()),
+        g: LatLng {
+            latitude: 0_f64,
+            longitude: 2_f64,
+        },
+        i: -9_007_199_254_740_993,
+        m: std::collections::BTreeMap::from([("k".to_string(), 2_f64)]),
+        r: Reference("projects/p/databases/(default)/documents/c/1".to_string()),
+        s: "\"".to_string(),
+        t: Timestamp {
+            seconds: 1,
+            nanos: 2_000_000,
+        },
+    })?;
+    let json = concat!(
+        r#"{"mapValue":{"fields":{"#,
+        r#""a":{"arrayValue":{"values":[{"integerValue":"1"},{"nullValue":null}]}},"#,
+        r#""b":{"booleanValue":true},"#,
+        r#""d":{"doubleValue":1.5},"#,
+        r#""e":{"arrayValue":{}},"#,
+        r#""f":{"fieldReferenceValue":"x.y"},"#,
+        r#""g":{"geoPointValue":{"longitude":2}},"#,
+        r#""i":{"integerValue":"-9007199254740993"},"#,
+        r#""m":{"mapValue":{"fields":{"k":{"doubleValue":2}}}},"#,
+        r#""r":{"referenceValue":"projects/p/databases/(default)/documents/c/1"},"#,
+        r#""s":{"stringValue":"\""},"#,
+        r#""t":{"timestampValue":"1970-01-01T00:00:01.002Z"}"#,
+        r#"}}}"#
+    );
+    assert_eq!(to_rest_json(&value)?, json);
+    assert_eq!(from_rest_json::<Value>(json)?, value);
+    Ok(())
+}
+
+#[cfg(feature = "rest-json")]
+#[test]
+fn test_bytes_and_doubles() -> anyhow::Result<()> {
+    use serde_firestore_value::google::firestore::v1::{Value, value::ValueType};
+    use serde_firestore_value::{from_rest_json, to_rest_json};
+
+    let value = from_rest_json::<Value>(r#"{"bytesValue":"+/8="}"#)?;
+    match value.value_type.as_ref() {
+        Some(ValueType::BytesValue(b)) => assert_eq!(&b[..], &[0xfb_u8, 0xff_u8]),
+        _ => unreachable!(),
+    }
+    assert_eq!(to_rest_json(&value)?, r#"{"bytesValue":"+/8="}"#);
+    assert_eq!(from_rest_json::<Value>(r#"{"bytesValue":"-_8"}"#)?, value);
+
+    let value = Value {
+        value_type: Some(ValueType::DoubleValue(f64::NEG_INFINITY)),
+    };
+    assert_eq!(to_rest_json(&value)?, r#"{"doubleValue":"-Infinity"}"#);
+    assert_eq!(
+        from_rest_json::<Value>(r#"{"doubleValue":"-Infinity"}"#)?,
+        value
+    );
+
+    let value = Value {
+        value_type: Some(ValueType::DoubleValue(-0_f64)),
+    };
+    assert_eq!(to_rest_json(&value)?, r#"{"doubleValue":-0.0}"#);
+    match from_rest_json::<Value>(&to_rest_json(&value)?)?.value_type {
+        Some(ValueType::DoubleValue(d)) => assert!(d == 0_f64 && d.is_sign_negative()),
+        _ => unreachable!(),
+    }
+
+    let value = Value {
+        value_type: Some(ValueType::IntegerValue(1)),
+    };
+    assert_eq!(from_rest_json::<Value>(r#"{"integerValue":1}"#)?, value);
+
+    assert!(from_rest_json::<Value>(r#"{"integerValue":"1.5"}"#).is_err());
+    assert!(from_rest_json::<Value>(r#"{"unknownValue":"1"}"#).is_err());
+    assert!(from_rest_json::<Value>(r#"{"integerValue":"1","stringValue":"1"}"#).is_err());
+    Ok(())
+}
+
+#[cfg(feature = "rest-json")]
+#[test]
+fn test_function_and_pipeline() -> anyhow::Result<()> {
+    use serde_firestore_value::google::firestore::v1::Value;
+    use serde_firestore_value::{
+        FieldReference, Function, Pipeline, Stage, from_rest_json, to_rest_json, to_value,
+    };
+
+    let value = to_value(&Pipeline {
+        stages: vec![
+            Stage {
+                name: "collection".to_string(),
+                args: vec![to_value(&serde_firestore_value::Reference(
+                    "/users".to_string(),
+                ))?],
+                options: Default::default(),
+            },
+            Stage {
+                name: "where".to_string(),
+                args: vec![to_value(&Function {
+                    name: "gt".to_string(),
+                    args: vec![
+                        to_value(&FieldReference("age".to_string()))?,
+                        to_value(&18_i64)?,
+                    ],
+                    options: Default::default(),
+                })?],
+                options: Default::default(),
+            },
+        ],
+    })?;
+    let json = concat!(
+        r#"{"pipelineValue":{"stages":["#,
+        r#"{"name":"collection","args":[{"referenceValue":"/users"}]},"#,
+        r#"{"name":"where","args":[{"functionValue":{"name":"gt","args":["#,
+        r#"{"fieldReferenceValue":"age"},{"integerValue":"18"}]}}]}"#,
+        r#"]}}"#
+    );
+    assert_eq!(to_rest_json(&value)?, json);
+    assert_eq!(from_rest_json::<Value>(json)?, value);
+    Ok(())
+}
+
+#[cfg(feature = "rest-json")]
+#[test]
+fn test_document() -> anyhow::Result<()> {
+    use serde_firestore_value::google::firestore::v1::{Document, Value, value::ValueType};
+    use serde_firestore_value::{from_rest_json, to_rest_json, to_rest_json_pretty};
+
+    let document = Document {
+        name: "projects/p/databases/(default)/documents/c/1".to_string(),
+        fields: [(
+            "n".to_string(),
+            Value {
+                value_type: Some(ValueType::IntegerValue(1)),
+            },
+        )]
+        .into_iter()
+        .collect(),
+        create_time: Some(prost_types::Timestamp {
+            seconds: 1_700_000_000,
+            nanos: 123_456_000,
+        }),
+        update_time: Some(prost_types::Timestamp {
+            seconds: 1_700_000_001,
+            nanos: 0,
+        }),
+    };
+    let json = r#"{
+  "name": "projects/p/databases/(default)/documents/c/1",
+  "fields": {
+    "n": {
+      "integerValue": "1"
+    }
+  },
+  "createTime": "2023-11-14T22:13:20.123456Z",
+  "updateTime": "2023-11-14T22:13:21Z"
+}"#;
+    assert_eq!(to_rest_json_pretty(&document)?, json);
+    assert_eq!(from_rest_json::<Document>(json)?, document);
+    assert_eq!(to_rest_json(&Document::default())?, r#"{}"#);
+    Ok(())
+}
@@ -0,0 +1,115 @@
+#[cfg(feature = "serde_with")]
+#[test]
+fn test_containers() -> anyhow::Result<()> {
+    use serde_firestore_value::google::firestore::v1::{
+        ArrayValue, MapValue, Value, value::ValueType,
+    };
+    use serde_firestore_value::google::r#type::LatLng;
+    use serde_firestore_value::serde_as::{AsFieldReference, AsGeoPoint, AsReference, AsTimestamp};
+    use serde_firestore_value::{from_value, to_value};
+
+    #[serde_with::serde_as]
+    #[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct S {
+        #[serde_as(as = "AsFieldReference")]
+        f: String,
+        #[serde_as(as = "Option<AsGeoPoint>")]
+        g: Option<LatLng>,
+        #[serde_as(as = "std::collections::BTreeMap<_, AsReference>")]
+        r: std::collections::BTreeMap<String, String>,
+        #[serde_as(as = "Vec<Option<AsTimestamp>>")]
+        t: Vec<Option<prost_types::Timestamp>>,
+    }
+
+    fn v(value_type: ValueType) -> Value {
+        Value {
+            value_type: Some(value_type),
+        }
+    }
+
+    let o = S {
+        f: "a.b".to_string(),
+        g: Some(LatLng {
+            latitude: 1.0,
+            longitude: 2.0,
+        }),
+        r: std::collections::BTreeMap::from([(
+            "k".to_string(),
+            "projects/p/databases/d/documents/c/1".to_string(),
+        )]),
+        t: vec![
+            Some(prost_types::Timestamp {
+                seconds: 1,
+                nanos: 2,
+            }),
+            None,
+        ],
+    };
+    let s = to_value(&o)?;
+    let ValueType::MapValue(MapValue { fields }) = s.value_type.clone().unwrap() else {
+        anyhow::bail!("not a map");
+    };
+    assert_eq!(
+        fields["f"],
+        v(ValueType::FieldReferenceValue("a.b".to_string()))
+    );
+    assert_eq!(
+        fields["g"],
+        v(ValueType::GeoPointValue(LatLng {
+            latitude: 1.0,
+            longitude: 2.0,
+        }))
+    );
+    assert_eq!(
+        fields["t"],
+        v(ValueType::ArrayValue(ArrayValue {
+            values: vec![
+                v(ValueType::TimestampValue(prost_types::Timestamp {
+                    seconds: 1,
+                    nanos: 2,
+                })),
+                v(ValueType::NullValue(0)),
+            ],
+        }))
+    );
+    let ValueType::MapValue(MapValue { fields: r }) = fields["r"].value_type.clone().unwrap()
+    else {
+        anyhow::bail!("not a map");
+    };
+    assert_eq!(
+        r["k"],
+        v(ValueType::ReferenceValue(
+            "projects/p/databases/d/documents/c/1".to_string()
+        ))
+    );
+    assert_eq!(from_value::<'_, S>(&s)?, o);
+    Ok(())
+}
+
+#[cfg(feature = "serde_with")]
+#[test]
+fn test_typ_types() -> anyhow::Result<()> {
+    use serde_firestore_value::serde_as::{AsReference, AsTimestamp};
+    use serde_firestore_value::{Reference, Timestamp, from_value, to_value};
+
+    #[serde_with::serde_as]
+    #[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct S {
+        #[serde_as(as = "Vec<AsReference>")]
+        r: Vec<Reference>,
+        #[serde_as(as = "Option<AsTimestamp>")]
+        t: Option<Timestamp>,
+    }
+
+    let o = S {
+        r: vec![Reference(
+            "projects/p/databases/d/documents/c/1".to_string(),
+        )],
+        t: Some(Timestamp {
+            seconds: 1,
+            nanos: 2,
+        }),
+    };
+    assert_eq!(from_value::<'_, S>(&to_value(&o)?)?, o);
+    Ok(())
+}
@@ -0,0 +1,189 @@
+#[cfg(feature = "serde_json")]
+#[test]
+fn test_round_trip() -> anyhow::Result<()> {
+    use serde_firestore_value::{
+        FieldReference, LatLng, Reference, Timestamp, from_value, json_to_value, to_value,
+        value_to_json,
+    };
+    use serde_json::json;
+
+    #[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct S {
+        b: bool,
+        d: f64,
+        f: FieldReference,
+        g: LatLng,
+        i: i64,
+        m: std::collections::BTreeMap<String, i64>,
+        n: Option<i64>,
+        r: Reference,
+        s: String,
+        t: Timestamp,
+        v: Vec<i64>,
+    }
+    let o = S {
+        b: true,
+        d: 1.5,
+        f: FieldReference("a.b".to_string()),
+        g: LatLng {
+            latitude: 1.0,
+            longitude: 2.0,
+        },
+        i: i64::MIN,
+        m: std::collections::BTreeMap::from([("$timestamp".to_string(), 1)]),
+        n: None,
+        r: Reference("projects/p/databases/d/documents/c/1".to_string()),
+        s: "2001-02-03T04:05:06Z".to_string(),
+        t: Timestamp {
+            seconds: 1,
+            nanos: 2_000_000,
+        },
+        v: vec![1, 2],
+    };
+    let value = to_value(&o)?;
+    let json = value_to_json(&value)?;
+    assert_eq!(
+        json,
+        json!({
+            "b": true,
+            "d": 1.5,
+            "f": { "$fieldReference": "a.b" },
+            "g": { "$geoPoint": { "latitude": 1.0, "longitude": 2.0 } },
+            "i": i64::MIN,
+            "m": { "$map": { "$timestamp": 1 } },
+            "n": null,
+            "r": { "$reference": "projects/p/databases/d/documents/c/1" },
+            "s": "2001-02-03T04:05:06Z",
+            "t": { "$timestamp": "1970-01-01T00:00:01.002Z" },
+            "v": [1, 2],
+        })
+    );
+    assert_eq!(json_to_value(&json)?, value);
+    assert_eq!(from_value::<'_, S>(&json_to_value(&json)?)?, o);
+    Ok(())
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn test_bytes() -> anyhow::Result<()> {
+    use serde_firestore_value::google::firestore::v1::{Value, value::ValueType};
+    use serde_firestore_value::{JsonOptions, Representation, json_to_value, value_to_json};
+    use serde_json::json;
+
+    let value = json_to_value(&json!({ "$bytes": "AQID" }))?;
+    let Some(ValueType::BytesValue(b)) = &value.value_type else {
+        anyhow::bail!("not bytesValue");
+    };
+    assert_eq!(&b[..], &[1_u8, 2, 3][..]);
+    assert_eq!(value_to_json(&value)?, json!({ "$bytes": "AQID" }));
+
+    let options = JsonOptions::new().bytes(Representation::Plain);
+    assert_eq!(options.value_to_json(&value)?, json!("AQID"));
+    assert_eq!(
+        options.json_to_value(&json!("AQID"))?,
+        Value {
+            value_type: Some(ValueType::StringValue("AQID".to_string()))
+        }
+    );
+
+    assert!(json_to_value(&json!({ "$bytes": "!" })).is_err());
+    Ok(())
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn test_plain() -> anyhow::Result<()> {
+    use serde_firestore_value::google::{
+        firestore::v1::{Value, value::ValueType},
+        r#type::LatLng,
+    };
+    use serde_firestore_value::{JsonOptions, Representation};
+    use serde_json::json;
+
+    let options = JsonOptions::new()
+        .geo_point(Representation::Plain)
+        .reference(Representation::Plain)
+        .timestamp(Representation::Plain)
+        .rfc3339_strings_as_timestamps(true);
+
+    let reference = Value {
+        value_type: Some(ValueType::ReferenceValue(
+            "projects/p/databases/d/documents/c/1".to_string(),
+        )),
+    };
+    assert_eq!(
+        options.value_to_json(&reference)?,
+        json!("projects/p/databases/d/documents/c/1")
+    );
+    assert_eq!(
+        options.json_to_value(&json!("projects/p/databases/d/documents/c/1"))?,
+        reference
+    );
+    assert_eq!(
+        options.json_to_value(&json!("projects/p/databases/d/documents/c"))?,
+        Value {
+            value_type: Some(ValueType::StringValue(
+                "projects/p/databases/d/documents/c".to_string()
+            ))
+        }
+    );
+
+    let geo_point = Value {
+        value_type: Some(ValueType::GeoPointValue(LatLng {
+            latitude: 1.0,
+            longitude: 2.0,
+        })),
+    };
+    let json = json!({ "latitude": 1.0, "longitude": 2.0 });
+    assert_eq!(options.value_to_json(&geo_point)?, json);
+    assert_eq!(options.json_to_value(&json)?, geo_point);
+
+    let timestamp = Value {
+        value_type: Some(ValueType::TimestampValue(prost_types::Timestamp {
+            seconds: 0,
+            nanos: 0,
+        })),
+    };
+    assert_eq!(
+        options.value_to_json(&timestamp)?,
+        json!("1970-01-01T00:00:00Z")
+    );
+    assert_eq!(
+        options.json_to_value(&json!("1970-01-01T09:00:00+09:00"))?,
+        timestamp
+    );
+    Ok(())
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn test_numbers() -> anyhow::Result<()> {
+    use serde_firestore_value::google::firestore::v1::{Value, value::ValueType};
+    use serde_firestore_value::{BigNumberStrategy, JsonOptions, json_to_value, value_to_json};
+    use serde_json::json;
+
+    assert!(json_to_value(&json!(u64::MAX)).is_err());
+    assert_eq!(
+        JsonOptions::new()
+            .big_number(BigNumberStrategy::Double)
+            .json_to_value(&json!(u64::MAX))?,
+        Value {
+            value_type: Some(ValueType::DoubleValue(u64::MAX as f64))
+        }
+    );
+    assert_eq!(
+        JsonOptions::new()
+            .big_number(BigNumberStrategy::String)
+            .json_to_value(&json!(u64::MAX))?,
+        Value {
+            value_type: Some(ValueType::StringValue(u64::MAX.to_string()))
+        }
+    );
+    assert!(
+        value_to_json(&Value {
+            value_type: Some(ValueType::DoubleValue(f64::NAN))
+        })
+        .is_err()
+    );
+    Ok(())
+}
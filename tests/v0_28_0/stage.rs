@@ -0,0 +1,226 @@
+use serde_firestore_value::expr::{count_all, field, lit};
+use serde_firestore_value::google::firestore::v1::{ArrayValue, MapValue, Value, value::ValueType};
+use serde_firestore_value::stage::{DistanceMeasure, FindNearest, Ordering, TypedStage};
+use serde_firestore_value::{FieldReference, Pipeline, Stage, from_value, to_value};
+
+fn field_value(s: &str) -> Value {
+    Value {
+        value_type: Some(ValueType::FieldReferenceValue(s.to_string())),
+    }
+}
+
+fn string_value(s: &str) -> Value {
+    Value {
+        value_type: Some(ValueType::StringValue(s.to_string())),
+    }
+}
+
+fn map_value(fields: Vec<(&str, Value)>) -> Value {
+    Value {
+        value_type: Some(ValueType::MapValue(MapValue {
+            fields: fields
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+        })),
+    }
+}
+
+#[test]
+fn test_encoding() -> anyhow::Result<()> {
+    let pipeline = Pipeline::collection("users")
+        .where_(field("age").gte(lit(18)))
+        .select(["name", "age"])
+        .sort([Ordering::descending(field("age"))])
+        .limit(10)
+        .offset(5)
+        .aggregate([("total", count_all())], [("city", field("city"))]);
+    let names = pipeline
+        .stages
+        .iter()
+        .map(|stage| stage.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            "collection",
+            "where",
+            "select",
+            "sort",
+            "limit",
+            "offset",
+            "aggregate"
+        ]
+    );
+
+    assert_eq!(
+        pipeline.stages[0].args,
+        vec![Value {
+            value_type: Some(ValueType::ReferenceValue("/users".to_string()))
+        }]
+    );
+    assert_eq!(
+        pipeline.stages[2].args,
+        vec![map_value(vec![
+            ("age", field_value("age")),
+            ("name", field_value("name"))
+        ])]
+    );
+    assert_eq!(
+        pipeline.stages[3].args,
+        vec![map_value(vec![
+            ("direction", string_value("descending")),
+            ("expression", field_value("age"))
+        ])]
+    );
+    assert_eq!(
+        pipeline.stages[4].args,
+        vec![Value {
+            value_type: Some(ValueType::IntegerValue(10))
+        }]
+    );
+    assert_eq!(pipeline.stages[6].args.len(), 2);
+    assert_eq!(
+        pipeline.stages[6].args[1],
+        map_value(vec![("city", field_value("city"))])
+    );
+
+    let value = to_value(&pipeline)?;
+    assert_eq!(from_value::<Pipeline>(&value)?, pipeline);
+    Ok(())
+}
+
+#[test]
+fn test_collection_group() -> anyhow::Result<()> {
+    let pipeline = Pipeline::collection_group("posts");
+    assert_eq!(
+        pipeline.stages[0].args,
+        vec![
+            Value {
+                value_type: Some(ValueType::ReferenceValue(String::new()))
+            },
+            string_value("posts")
+        ]
+    );
+    assert_eq!(
+        pipeline.typed_stages()?,
+        vec![TypedStage::CollectionGroup {
+            parent: String::new(),
+            collection_id: "posts".to_string(),
+        }]
+    );
+
+    let pipeline = Pipeline::collection_group_in("users/alice", "posts");
+    assert_eq!(
+        pipeline.stages[0].args,
+        vec![
+            Value {
+                value_type: Some(ValueType::ReferenceValue("/users/alice".to_string()))
+            },
+            string_value("posts")
+        ]
+    );
+    let typed = pipeline.typed_stages()?;
+    assert_eq!(
+        typed,
+        vec![TypedStage::CollectionGroup {
+            parent: "/users/alice".to_string(),
+            collection_id: "posts".to_string(),
+        }]
+    );
+    assert_eq!(Stage::from(typed[0].clone()), pipeline.stages[0]);
+    Ok(())
+}
+
+#[test]
+fn test_find_nearest() -> anyhow::Result<()> {
+    let find_nearest = FindNearest {
+        field: FieldReference("embedding".to_string()),
+        vector: vec![1.0, 2.0],
+        distance_measure: DistanceMeasure::Cosine,
+        limit: Some(3),
+        distance_field: Some(FieldReference("distance".to_string())),
+    };
+    let pipeline = Pipeline::collection("docs").find_nearest(find_nearest.clone());
+    let stage = &pipeline.stages[1];
+    assert_eq!(stage.name, "find_nearest");
+    assert_eq!(
+        stage.args,
+        vec![
+            field_value("embedding"),
+            map_value(vec![
+                ("__type__", string_value("__vector__")),
+                (
+                    "value",
+                    Value {
+                        value_type: Some(ValueType::ArrayValue(ArrayValue {
+                            values: vec![
+                                Value {
+                                    value_type: Some(ValueType::DoubleValue(1.0))
+                                },
+                                Value {
+                                    value_type: Some(ValueType::DoubleValue(2.0))
+                                }
+                            ]
+                        }))
+                    }
+                )
+            ]),
+            string_value("cosine"),
+        ]
+    );
+    assert_eq!(
+        stage.options["limit"],
+        Value {
+            value_type: Some(ValueType::IntegerValue(3))
+        }
+    );
+    assert_eq!(stage.options["distance_field"], field_value("distance"));
+    assert_eq!(
+        pipeline.typed_stages()?[1],
+        TypedStage::FindNearest(find_nearest)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_decode() -> anyhow::Result<()> {
+    let pipeline = Pipeline::collection("users")
+        .where_(field("a").eq(lit(1)))
+        .add_fields([("b", field("a").add(lit(1)))])
+        .remove_fields(["c"])
+        .distinct(["b"])
+        .stage(Stage {
+            name: "sample".to_string(),
+            args: vec![],
+            options: Default::default(),
+        });
+    let typed = pipeline.typed_stages()?;
+    assert_eq!(typed[0], TypedStage::Collection("/users".to_string()));
+    assert_eq!(typed[1], TypedStage::Where(field("a").eq(lit(1))));
+    assert_eq!(
+        typed[3],
+        TypedStage::RemoveFields(vec![FieldReference("c".to_string())])
+    );
+    assert!(matches!(&typed[5], TypedStage::Other(stage) if stage.name == "sample"));
+
+    let rebuilt = typed
+        .into_iter()
+        .fold(Pipeline { stages: vec![] }, |pipeline, stage| {
+            pipeline.stage(stage)
+        });
+    assert_eq!(rebuilt, pipeline);
+
+    let invalid = Pipeline {
+        stages: vec![Stage {
+            name: "limit".to_string(),
+            args: vec![],
+            options: Default::default(),
+        }],
+    };
+    assert_eq!(
+        invalid.typed_stages().unwrap_err().to_string(),
+        "invalid stage: `limit` expects 1 argument(s), got 0"
+    );
+    Ok(())
+}
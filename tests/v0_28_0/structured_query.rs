@@ -0,0 +1,850 @@
+use serde_firestore_value::eval::{execute_pipeline, execute_structured_query};
+use serde_firestore_value::expr::{field, function, lit};
+use serde_firestore_value::google::firestore::v1::{
+    Cursor, Document, StructuredQuery, Value,
+    structured_query::{
+        CollectionSelector, CompositeFilter, Direction, FieldFilter, FieldReference, Filter,
+        FindNearest, Order, Projection, UnaryFilter, composite_filter, field_filter,
+        filter::FilterType, find_nearest, unary_filter,
+    },
+};
+use serde_firestore_value::stage::{self, DistanceMeasure, Ordering};
+use serde_firestore_value::structured_query::{
+    self as query, Op, Query, UnaryOp, from_pipeline, limit_to_last, to_pipeline,
+};
+use serde_firestore_value::{Pipeline, Reference, from_value, to_value};
+
+const ROOT: &str = "projects/p/databases/(default)/documents";
+
+fn from(collection_id: &str, all_descendants: bool) -> Vec<CollectionSelector> {
+    vec![CollectionSelector {
+        collection_id: collection_id.to_string(),
+        all_descendants,
+    }]
+}
+
+fn field_reference(path: &str) -> Option<FieldReference> {
+    Some(FieldReference {
+        field_path: path.to_string(),
+    })
+}
+
+fn field_filter<T: serde::Serialize>(
+    path: &str,
+    op: field_filter::Operator,
+    value: T,
+) -> anyhow::Result<Filter> {
+    Ok(Filter {
+        filter_type: Some(FilterType::FieldFilter(FieldFilter {
+            field: field_reference(path),
+            op: op as i32,
+            value: Some(to_value(&value)?),
+        })),
+    })
+}
+
+fn unary_filter(path: &str, op: unary_filter::Operator) -> Filter {
+    Filter {
+        filter_type: Some(FilterType::UnaryFilter(UnaryFilter {
+            op: op as i32,
+            operand_type: Some(unary_filter::OperandType::Field(FieldReference {
+                field_path: path.to_string(),
+            })),
+        })),
+    }
+}
+
+fn composite(op: composite_filter::Operator, filters: Vec<Filter>) -> Filter {
+    Filter {
+        filter_type: Some(FilterType::CompositeFilter(CompositeFilter {
+            op: op as i32,
+            filters,
+        })),
+    }
+}
+
+fn order(path: &str, direction: Direction) -> Order {
+    Order {
+        field: field_reference(path),
+        direction: direction as i32,
+    }
+}
+
+fn document(path: &str, fields: Vec<(&str, Value)>) -> Document {
+    Document {
+        name: format!("{ROOT}/{path}"),
+        fields: fields
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect(),
+        create_time: None,
+        update_time: None,
+    }
+}
+
+fn posts() -> anyhow::Result<Vec<Document>> {
+    Ok(vec![
+        document(
+            "users/alice/posts/1",
+            vec![("likes", to_value(&5)?), ("tag", to_value(&"a")?)],
+        ),
+        document(
+            "users/alice/posts/2",
+            vec![("likes", to_value(&20)?), ("tag", to_value(&"b")?)],
+        ),
+        document(
+            "users/alice/posts/3",
+            vec![("likes", to_value(&20)?), ("tag", to_value(&"a")?)],
+        ),
+        document("users/alice/posts/4", vec![("tag", to_value(&"c")?)]),
+        document(
+            "users/alice/posts/5",
+            vec![("likes", to_value(&1.5)?), ("tag", to_value(&())?)],
+        ),
+        document("users/bob/posts/6", vec![("likes", to_value(&100)?)]),
+    ])
+}
+
+/// Runs `query` over the posts of alice and returns their `likes`.
+fn likes(query: &StructuredQuery) -> anyhow::Result<Vec<f64>> {
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    enum Number {
+        Integer(i64),
+        Double(f64),
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Post {
+        likes: Number,
+    }
+
+    let pipeline = to_pipeline(&format!("{ROOT}/users/alice"), query)?;
+    execute_pipeline(&pipeline, &posts()?)?
+        .iter()
+        .map(|row| {
+            Ok(match from_value::<Post>(row)?.likes {
+                Number::Integer(i) => i as f64,
+                Number::Double(d) => d,
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn test_to_pipeline() -> anyhow::Result<()> {
+    let query = StructuredQuery {
+        from: from("posts", false),
+        r#where: Some(field_filter(
+            "likes",
+            field_filter::Operator::GreaterThan,
+            10,
+        )?),
+        offset: 1,
+        limit: Some(20),
+        select: Some(Projection {
+            fields: vec![FieldReference {
+                field_path: "likes".to_string(),
+            }],
+        }),
+        ..Default::default()
+    };
+    assert_eq!(
+        to_pipeline(&format!("{ROOT}/users/alice"), &query)?,
+        Pipeline::collection("users/alice/posts")
+            .where_(field("likes").gt(lit(10)))
+            .sort([
+                Ordering::ascending(field("likes")),
+                Ordering::ascending(field("__name__")),
+            ])
+            .offset(1)
+            .limit(20)
+            .select(["likes"])
+    );
+    // A relative parent is the same.
+    assert_eq!(
+        to_pipeline("users/alice", &query)?,
+        to_pipeline(&format!("{ROOT}/users/alice"), &query)?
+    );
+
+    // Ordering by a field only matches documents which have it.
+    let query = StructuredQuery {
+        from: from("posts", true),
+        order_by: vec![order("likes", Direction::Descending)],
+        ..Default::default()
+    };
+    assert_eq!(
+        to_pipeline(ROOT, &query)?,
+        Pipeline::collection_group("posts")
+            .where_(field("likes").exists())
+            .sort([
+                Ordering::descending(field("likes")),
+                Ordering::descending(field("__name__")),
+            ])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_to_pipeline_filters() -> anyhow::Result<()> {
+    let query = StructuredQuery {
+        from: from("posts", false),
+        r#where: Some(composite(
+            composite_filter::Operator::And,
+            vec![
+                field_filter("tag", field_filter::Operator::In, ["a", "b"])?,
+                composite(
+                    composite_filter::Operator::Or,
+                    vec![
+                        unary_filter("likes", unary_filter::Operator::IsNotNan),
+                        unary_filter("tag", unary_filter::Operator::IsNull),
+                    ],
+                ),
+            ],
+        )),
+        ..Default::default()
+    };
+    assert_eq!(
+        to_pipeline("", &query)?,
+        Pipeline::collection("posts")
+            .where_(function(
+                "and",
+                [
+                    field("tag").equal_any(lit(["a", "b"])),
+                    function(
+                        "or",
+                        [
+                            field("likes")
+                                .is_nan()
+                                .not()
+                                .and(field("likes").is_null().not()),
+                            field("tag").is_null()
+                        ]
+                    ),
+                    field("likes").exists(),
+                ]
+            ))
+            .sort([
+                Ordering::ascending(field("likes")),
+                Ordering::ascending(field("__name__")),
+            ])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_to_pipeline_cursors() -> anyhow::Result<()> {
+    let name = Reference(format!("{ROOT}/users/alice/posts/3"));
+    let query = StructuredQuery {
+        from: from("posts", false),
+        order_by: vec![order("likes", Direction::Descending)],
+        start_at: Some(Cursor {
+            values: vec![to_value(&20)?, to_value(&name)?],
+            before: false,
+        }),
+        end_at: Some(Cursor {
+            values: vec![to_value(&5)?],
+            before: false,
+        }),
+        ..Default::default()
+    };
+    assert_eq!(
+        to_pipeline("users/alice", &query)?,
+        Pipeline::collection("users/alice/posts")
+            .where_(function(
+                "and",
+                [
+                    field("likes").exists(),
+                    function(
+                        "or",
+                        [
+                            field("likes").lt(lit(20)),
+                            field("likes")
+                                .eq(lit(20))
+                                .and(field("__name__").lt(lit(&name))),
+                        ]
+                    ),
+                    field("likes").gte(lit(5)),
+                ]
+            ))
+            .sort([
+                Ordering::descending(field("likes")),
+                Ordering::descending(field("__name__")),
+            ])
+    );
+    assert_eq!(likes(&query)?, [20.0, 5.0]);
+
+    let query = StructuredQuery {
+        start_at: Some(Cursor {
+            values: vec![to_value(&20)?],
+            before: true,
+        }),
+        end_at: Some(Cursor {
+            values: vec![to_value(&5)?],
+            before: true,
+        }),
+        ..query
+    };
+    assert_eq!(likes(&query)?, [20.0, 20.0]);
+
+    let query = StructuredQuery {
+        order_by: vec![],
+        start_at: Some(Cursor {
+            values: vec![to_value(&1)?, to_value(&2)?],
+            before: true,
+        }),
+        end_at: None,
+        ..query
+    };
+    assert!(to_pipeline("users/alice", &query).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_to_pipeline_execute() -> anyhow::Result<()> {
+    let query = StructuredQuery {
+        from: from("posts", false),
+        r#where: Some(field_filter("tag", field_filter::Operator::NotEqual, "b")?),
+        order_by: vec![order("likes", Direction::Ascending)],
+        ..Default::default()
+    };
+    // `tag != "b"` does not match a missing or null `tag`, and the ordering
+    // by `likes` requires `likes`.
+    assert_eq!(likes(&query)?, [5.0, 20.0]);
+
+    let query = StructuredQuery {
+        r#where: Some(unary_filter("tag", unary_filter::Operator::IsNull)),
+        ..query
+    };
+    assert_eq!(likes(&query)?, [1.5]);
+    Ok(())
+}
+
+#[test]
+fn test_to_pipeline_matches_structured_query() -> anyhow::Result<()> {
+    use field_filter::Operator as F;
+    use unary_filter::Operator as U;
+
+    // The array functions of pipelines fail on other types, so `a` is an
+    // array or missing.
+    let documents = vec![
+        document(
+            "c/1",
+            vec![("v", to_value(&1)?), ("a", to_value(&(1, "a"))?)],
+        ),
+        document(
+            "c/2",
+            vec![("v", to_value(&2.5)?), ("a", to_value(&((),))?)],
+        ),
+        document(
+            "c/3",
+            vec![("v", to_value(&f64::NAN)?), ("a", to_value(&(f64::NAN,))?)],
+        ),
+        document(
+            "c/4",
+            vec![("v", to_value(&())?), ("a", to_value(&Vec::<i64>::new())?)],
+        ),
+        document("c/5", vec![("w", to_value(&1)?)]),
+        document(
+            "c/6",
+            vec![("v", to_value(&"a")?), ("a", to_value(&("b",))?)],
+        ),
+        document("c/7", vec![("v", to_value(&(1, "a"))?)]),
+        document("c/8", vec![("v", to_value(&((),))?)]),
+    ];
+    let filters = vec![
+        field_filter("v", F::LessThan, 2)?,
+        field_filter("v", F::LessThanOrEqual, 2.5)?,
+        field_filter("v", F::GreaterThan, 1)?,
+        field_filter("v", F::GreaterThanOrEqual, 1)?,
+        field_filter("v", F::Equal, 1)?,
+        field_filter("v", F::Equal, ())?,
+        field_filter("v", F::NotEqual, 1)?,
+        field_filter("v", F::NotEqual, ())?,
+        field_filter("a", F::ArrayContains, 1)?,
+        field_filter("v", F::In, (1, "a"))?,
+        field_filter("a", F::ArrayContainsAny, (1, "a"))?,
+        field_filter("v", F::NotIn, (1, "a"))?,
+        unary_filter("v", U::IsNan),
+        unary_filter("v", U::IsNull),
+        unary_filter("v", U::IsNotNan),
+        unary_filter("v", U::IsNotNull),
+        composite(
+            composite_filter::Operator::Or,
+            vec![
+                field_filter("v", F::NotEqual, 1)?,
+                unary_filter("v", U::IsNull),
+            ],
+        ),
+        composite(
+            composite_filter::Operator::And,
+            vec![
+                field_filter("v", F::NotIn, (1, "a"))?,
+                unary_filter("v", U::IsNotNan),
+            ],
+        ),
+        composite(
+            composite_filter::Operator::Or,
+            vec![
+                composite(
+                    composite_filter::Operator::And,
+                    vec![
+                        field_filter("v", F::NotEqual, "a")?,
+                        field_filter("v", F::LessThan, 2)?,
+                    ],
+                ),
+                field_filter("a", F::ArrayContains, 1)?,
+            ],
+        ),
+    ];
+    for filter in filters {
+        let query = StructuredQuery {
+            from: from("c", false),
+            r#where: Some(filter),
+            ..Default::default()
+        };
+        let expected = execute_structured_query("", &query, &documents)?
+            .into_iter()
+            .map(|document| Reference(document.name))
+            .collect::<Vec<_>>();
+
+        #[derive(serde::Deserialize)]
+        struct Row {
+            #[serde(rename = "__name__")]
+            name: Reference,
+        }
+        let pipeline = to_pipeline("", &query)?;
+        let actual = execute_pipeline(&pipeline.clone().select(["__name__"]), &documents)?
+            .iter()
+            .map(|row| Ok(from_value::<Row>(row)?.name))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        assert_eq!(actual, expected, "{:?}", query.r#where);
+        assert_eq!(from_pipeline(&pipeline)?.1.r#where, query.r#where);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_to_pipeline_unsupported() -> anyhow::Result<()> {
+    let query = StructuredQuery {
+        from: from("posts", true),
+        ..Default::default()
+    };
+    assert!(to_pipeline(ROOT, &query).is_ok());
+
+    let query = StructuredQuery::default();
+    assert!(to_pipeline(ROOT, &query).is_err());
+
+    let query = StructuredQuery {
+        from: from("posts", false),
+        r#where: Some(Filter {
+            filter_type: Some(FilterType::FieldFilter(FieldFilter {
+                field: field_reference("likes"),
+                op: field_filter::Operator::Unspecified as i32,
+                value: Some(to_value(&1)?),
+            })),
+        }),
+        ..Default::default()
+    };
+    assert!(to_pipeline(ROOT, &query).is_err());
+
+    // Range filters on NaN have no equivalent.
+    let query = StructuredQuery {
+        r#where: Some(field_filter(
+            "likes",
+            field_filter::Operator::LessThan,
+            f64::NAN,
+        )?),
+        ..query
+    };
+    assert!(to_pipeline(ROOT, &query).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_from_pipeline() -> anyhow::Result<()> {
+    let query = StructuredQuery {
+        from: from("posts", false),
+        r#where: Some(composite(
+            composite_filter::Operator::And,
+            vec![
+                field_filter("likes", field_filter::Operator::GreaterThanOrEqual, 10)?,
+                composite(
+                    composite_filter::Operator::Or,
+                    vec![
+                        field_filter("tags", field_filter::Operator::ArrayContains, "a")?,
+                        field_filter("tags", field_filter::Operator::ArrayContainsAny, ["b"])?,
+                        unary_filter("tag", unary_filter::Operator::IsNotNull),
+                    ],
+                ),
+            ],
+        )),
+        order_by: vec![
+            order("likes", Direction::Descending),
+            order("tag", Direction::Descending),
+        ],
+        offset: 2,
+        limit: Some(10),
+        select: Some(Projection {
+            fields: vec![
+                FieldReference {
+                    field_path: "likes".to_string(),
+                },
+                FieldReference {
+                    field_path: "tag".to_string(),
+                },
+            ],
+        }),
+        ..Default::default()
+    };
+    let pipeline = to_pipeline("users/alice", &query)?;
+    assert_eq!(
+        from_pipeline(&pipeline)?,
+        ("users/alice".to_string(), query)
+    );
+
+    // `limit` followed by `offset`.
+    let pipeline = Pipeline::collection_group("posts")
+        .where_(field("likes").exists())
+        .sort([Ordering::ascending(field("likes"))])
+        .limit(10)
+        .offset(3);
+    assert_eq!(
+        from_pipeline(&pipeline)?,
+        (
+            String::new(),
+            StructuredQuery {
+                from: from("posts", true),
+                order_by: vec![order("likes", Direction::Ascending)],
+                offset: 3,
+                limit: Some(7),
+                ..Default::default()
+            }
+        )
+    );
+
+    // A collection group below a document.
+    let query = StructuredQuery {
+        from: from("posts", true),
+        ..Default::default()
+    };
+    let pipeline = to_pipeline(&format!("{ROOT}/users/alice"), &query)?;
+    assert_eq!(
+        pipeline,
+        Pipeline::collection_group_in("/users/alice", "posts")
+            .sort([Ordering::ascending(field("__name__"))])
+    );
+    assert_eq!(
+        from_pipeline(&pipeline)?,
+        ("users/alice".to_string(), query)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_from_pipeline_cursors() -> anyhow::Result<()> {
+    let query = StructuredQuery {
+        from: from("posts", false),
+        order_by: vec![order("likes", Direction::Ascending)],
+        start_at: Some(Cursor {
+            values: vec![to_value(&5)?],
+            before: false,
+        }),
+        ..Default::default()
+    };
+    let (_, converted) = from_pipeline(&to_pipeline("users/alice", &query)?)?;
+    assert_eq!(
+        converted,
+        StructuredQuery {
+            r#where: Some(field_filter(
+                "likes",
+                field_filter::Operator::GreaterThan,
+                5
+            )?),
+            start_at: None,
+            ..query.clone()
+        }
+    );
+    assert_eq!(likes(&converted)?, likes(&query)?,);
+    Ok(())
+}
+
+#[test]
+fn test_from_pipeline_find_nearest() -> anyhow::Result<()> {
+    #[derive(serde::Serialize)]
+    struct Vector {
+        #[serde(rename = "__type__")]
+        r#type: &'static str,
+        value: Vec<f64>,
+    }
+
+    let query = StructuredQuery {
+        from: from("posts", false),
+        find_nearest: Some(FindNearest {
+            vector_field: field_reference("embedding"),
+            query_vector: Some(to_value(&Vector {
+                r#type: "__vector__",
+                value: vec![1.0, 2.0],
+            })?),
+            distance_measure: find_nearest::DistanceMeasure::DotProduct as i32,
+            limit: Some(3),
+            distance_result_field: "distance".to_string(),
+            distance_threshold: Some(0.5),
+        }),
+        ..Default::default()
+    };
+    let pipeline = to_pipeline("", &query)?;
+    assert_eq!(
+        pipeline,
+        Pipeline::collection("posts")
+            .sort([Ordering::ascending(field("__name__"))])
+            .find_nearest(stage::FindNearest {
+                field: serde_firestore_value::FieldReference("embedding".to_string()),
+                vector: vec![1.0, 2.0],
+                distance_measure: DistanceMeasure::DotProduct,
+                limit: Some(3),
+                distance_field: Some(serde_firestore_value::FieldReference(
+                    "distance".to_string()
+                )),
+            })
+            .where_(field("distance").gte(lit(0.5)))
+    );
+    assert_eq!(from_pipeline(&pipeline)?, (String::new(), query));
+    Ok(())
+}
+
+#[test]
+fn test_from_pipeline_unsupported() -> anyhow::Result<()> {
+    let error = |pipeline: Pipeline| from_pipeline(&pipeline).unwrap_err().to_string();
+    assert_eq!(
+        error(Pipeline::database()),
+        "unsupported conversion: stages[0]: `database` stage is not a collection source"
+    );
+    assert_eq!(
+        error(Pipeline::collection("posts").distinct(["tag"])),
+        "unsupported conversion: stages[1]: `distinct` stage has no equivalent"
+    );
+    assert_eq!(
+        error(
+            Pipeline::collection("posts")
+                .limit(1)
+                .where_(field("a").eq(lit(1)))
+        ),
+        "unsupported conversion: stages[2]: `where` stage is out of order"
+    );
+    assert_eq!(
+        error(Pipeline::collection("posts").where_(field("a").eq(field("b")))),
+        "unsupported conversion: stages[1]: `equal` must compare a field with a constant"
+    );
+    assert_eq!(
+        error(Pipeline::collection("posts").where_(field("a").starts_with(lit("x")))),
+        "unsupported conversion: stages[1]: `starts_with` has no equivalent"
+    );
+    assert_eq!(
+        error(Pipeline::collection("posts").where_(field("a").exists())),
+        "unsupported conversion: stages[1]: `exists` is only supported on a sorted field"
+    );
+    assert_eq!(
+        error(Pipeline::collection("posts").where_(field("a").neq(lit(1)))),
+        "unsupported conversion: the condition on `a` must be combined with `not(is_null(a))`"
+    );
+    assert_eq!(
+        error(Pipeline::collection("posts").sort([Ordering::ascending(field("a"))])),
+        "unsupported conversion: sort by `a` also matches documents without it (add `exists`)"
+    );
+    assert_eq!(
+        error(Pipeline::collection("posts").select([("b", field("a"))])),
+        "unsupported conversion: stages[1]: `select` stage selects a computed or renamed field"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query() -> anyhow::Result<()> {
+    let author = Reference(format!("{ROOT}/users/alice"));
+    let query = Query::collection("posts")
+        .where_field("author", Op::Eq, &author)
+        .where_(query::or([
+            query::field_filter("tags", Op::ArrayContainsAny, &["rust", "serde"])?,
+            query::field_filter("likes", Op::NotIn, &[0, 1])?,
+        ]))
+        .where_unary("deleted_at", UnaryOp::IsNull)
+        .order_by("created", stage::Direction::Descending)
+        .start_after(&(20, &author))
+        .end_before(&(1,))
+        .offset(5)
+        .limit(20)
+        .select(["title"])
+        .build()?;
+    assert_eq!(
+        query,
+        StructuredQuery {
+            from: from("posts", false),
+            r#where: Some(composite(
+                composite_filter::Operator::And,
+                vec![
+                    field_filter("author", field_filter::Operator::Equal, &author)?,
+                    composite(
+                        composite_filter::Operator::Or,
+                        vec![
+                            field_filter(
+                                "tags",
+                                field_filter::Operator::ArrayContainsAny,
+                                ["rust", "serde"]
+                            )?,
+                            field_filter("likes", field_filter::Operator::NotIn, [0, 1])?,
+                        ],
+                    ),
+                    unary_filter("deleted_at", unary_filter::Operator::IsNull),
+                ],
+            )),
+            order_by: vec![order("created", Direction::Descending)],
+            start_at: Some(Cursor {
+                values: vec![to_value(&20)?, to_value(&author)?],
+                before: false,
+            }),
+            end_at: Some(Cursor {
+                values: vec![to_value(&1)?],
+                before: true,
+            }),
+            offset: 5,
+            limit: Some(20),
+            select: Some(Projection {
+                fields: vec![FieldReference {
+                    field_path: "title".to_string(),
+                }],
+            }),
+            ..Default::default()
+        }
+    );
+
+    let query = Query::collection_group("posts")
+        .where_field("likes", Op::Gt, &10)
+        .start_at(&(10,))
+        .end_at(&(20,))
+        .build()?;
+    assert!(query.from[0].all_descendants);
+    assert_eq!(
+        query.r#where,
+        Some(field_filter(
+            "likes",
+            field_filter::Operator::GreaterThan,
+            10
+        )?)
+    );
+    assert_eq!(query.start_at.map(|cursor| cursor.before), Some(true));
+    assert_eq!(query.end_at.map(|cursor| cursor.before), Some(false));
+    Ok(())
+}
+
+#[test]
+fn test_query_null_and_nan() -> anyhow::Result<()> {
+    let filter = |op: Op, value: Option<f64>| query::field_filter("a", op, &value);
+    assert_eq!(
+        filter(Op::Eq, None)?,
+        unary_filter("a", unary_filter::Operator::IsNull)
+    );
+    assert_eq!(
+        filter(Op::Neq, None)?,
+        unary_filter("a", unary_filter::Operator::IsNotNull)
+    );
+    assert_eq!(
+        filter(Op::Eq, Some(f64::NAN))?,
+        unary_filter("a", unary_filter::Operator::IsNan)
+    );
+    assert_eq!(
+        filter(Op::Neq, Some(f64::NAN))?,
+        unary_filter("a", unary_filter::Operator::IsNotNan)
+    );
+    assert_eq!(
+        filter(Op::Lt, None).unwrap_err().to_string(),
+        "invalid query: `a`: null and NaN only support `Eq` and `Neq`"
+    );
+    assert!(filter(Op::Gte, Some(f64::NAN)).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_query_errors() -> anyhow::Result<()> {
+    assert_eq!(
+        Query::collection("posts")
+            .where_field("tags", Op::In, &"rust")
+            .build()
+            .unwrap_err()
+            .to_string(),
+        "invalid query: `tags`: `In` requires a non-empty array"
+    );
+    assert!(
+        Query::collection("posts")
+            .where_field("tags", Op::NotIn, &Vec::<String>::new())
+            .build()
+            .is_err()
+    );
+    // The first error is reported.
+    assert_eq!(
+        Query::collection("posts")
+            .where_field("a", Op::ArrayContainsAny, &1)
+            .where_field("b", Op::Lt, &())
+            .limit(1)
+            .build()
+            .unwrap_err()
+            .to_string(),
+        "invalid query: `a`: `ArrayContainsAny` requires a non-empty array"
+    );
+    assert!(
+        Query::collection("posts")
+            .start_at(&u64::MAX)
+            .build()
+            .is_err()
+    );
+    assert!(
+        Query::collection("posts")
+            .where_field("a", Op::Eq, &u64::MAX)
+            .build()
+            .is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_query_to_pipeline() -> anyhow::Result<()> {
+    let query = Query::collection("posts")
+        .where_field("likes", Op::Gte, &5)
+        .order_by("likes", stage::Direction::Descending)
+        .limit(2)
+        .build()?;
+    assert_eq!(likes(&query)?, [20.0, 20.0]);
+    Ok(())
+}
+
+#[test]
+fn test_limit_to_last() -> anyhow::Result<()> {
+    let query = Query::collection("posts")
+        .where_field("likes", Op::Gt, &1)
+        .order_by("created", stage::Direction::Descending)
+        .start_at(&(10,))
+        .end_before(&(1,))
+        .limit(5)
+        .build()?;
+    assert_eq!(
+        limit_to_last(&query, 2)?,
+        StructuredQuery {
+            order_by: vec![
+                order("created", Direction::Ascending),
+                order("likes", Direction::Ascending),
+                order("__name__", Direction::Ascending),
+            ],
+            start_at: Some(Cursor {
+                values: vec![to_value(&1)?],
+                before: false,
+            }),
+            end_at: Some(Cursor {
+                values: vec![to_value(&10)?],
+                before: false,
+            }),
+            limit: Some(2),
+            ..query
+        }
+    );
+    Ok(())
+}
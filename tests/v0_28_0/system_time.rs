@@ -0,0 +1,100 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_firestore_value::google::firestore::v1::{MapValue, Value, value::ValueType};
+use serde_firestore_value::with::{option_system_time_as_timestamp, system_time_as_timestamp};
+use serde_firestore_value::{Timestamp, from_value, to_value};
+
+#[test]
+fn test_try_from() -> anyhow::Result<()> {
+    for (system_time, timestamp) in [
+        (
+            UNIX_EPOCH + Duration::new(1, 2),
+            Timestamp {
+                seconds: 1,
+                nanos: 2,
+            },
+        ),
+        (
+            UNIX_EPOCH - Duration::new(1, 0),
+            Timestamp {
+                seconds: -1,
+                nanos: 0,
+            },
+        ),
+        (
+            UNIX_EPOCH - Duration::new(1, 250_000_000),
+            Timestamp {
+                seconds: -2,
+                nanos: 750_000_000,
+            },
+        ),
+    ] {
+        assert_eq!(Timestamp::try_from(system_time)?, timestamp);
+        assert_eq!(SystemTime::try_from(timestamp)?, system_time);
+    }
+
+    assert!(
+        SystemTime::try_from(Timestamp {
+            seconds: 0,
+            nanos: 1_000_000_000,
+        })
+        .is_err()
+    );
+    assert!(
+        SystemTime::try_from(Timestamp {
+            seconds: 0,
+            nanos: -1,
+        })
+        .is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_with() -> anyhow::Result<()> {
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct S {
+        #[serde(with = "system_time_as_timestamp")]
+        a: SystemTime,
+        #[serde(with = "option_system_time_as_timestamp")]
+        b: Option<SystemTime>,
+        #[serde(with = "option_system_time_as_timestamp")]
+        c: Option<SystemTime>,
+    }
+
+    let o = S {
+        a: UNIX_EPOCH + Duration::new(1, 2),
+        b: Some(UNIX_EPOCH - Duration::from_millis(500)),
+        c: None,
+    };
+    let s = to_value(&o)?;
+    let ValueType::MapValue(MapValue { fields }) = s.value_type.clone().unwrap() else {
+        anyhow::bail!("not a map");
+    };
+    assert_eq!(
+        fields["a"],
+        Value {
+            value_type: Some(ValueType::TimestampValue(prost_types::Timestamp {
+                seconds: 1,
+                nanos: 2,
+            })),
+        }
+    );
+    assert_eq!(
+        fields["b"],
+        Value {
+            value_type: Some(ValueType::TimestampValue(prost_types::Timestamp {
+                seconds: -1,
+                nanos: 500_000_000,
+            })),
+        }
+    );
+    assert_eq!(
+        fields["c"],
+        Value {
+            value_type: Some(ValueType::NullValue(0)),
+        }
+    );
+    assert_eq!(from_value::<'_, S>(&s)?, o);
+    Ok(())
+}
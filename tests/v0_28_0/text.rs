@@ -0,0 +1,214 @@
+use serde_firestore_value::google::firestore::v1::{Value, value::ValueType};
+use serde_firestore_value::text::{
+    parse_function, parse_pipeline, parse_value, print_function, print_pipeline, print_value,
+};
+use serde_firestore_value::{
+    FieldReference, Function, LatLng, Pipeline, Reference, Stage, Timestamp, VariableReference,
+    from_value, to_value,
+};
+
+#[test]
+fn test_pipeline() -> anyhow::Result<()> {
+    let pipeline =
+        parse_pipeline(r#"collection("users") |> where(gt(field("age"), 18)) |> limit(10)"#)?;
+    assert_eq!(pipeline.stages.len(), 3);
+    assert_eq!(pipeline.stages[0].name, "collection");
+    assert_eq!(
+        pipeline.stages[0].args,
+        vec![Value {
+            value_type: Some(ValueType::ReferenceValue("/users".to_string()))
+        }]
+    );
+    let gt: Function = from_value(&pipeline.stages[1].args[0])?;
+    assert_eq!(gt.name, "gt");
+    assert_eq!(
+        from_value::<FieldReference>(&gt.args[0])?,
+        FieldReference("age".to_string())
+    );
+    assert_eq!(from_value::<i64>(&gt.args[1])?, 18);
+    assert_eq!(from_value::<i64>(&pipeline.stages[2].args[0])?, 10);
+
+    let printed = print_pipeline(&pipeline);
+    assert_eq!(
+        printed,
+        r#"collection("/users") |> where(gt(field("age"), 18)) |> limit(10)"#
+    );
+    assert_eq!(parse_pipeline(&printed)?, pipeline);
+
+    assert_eq!(
+        parse_pipeline(r#"collection_group("posts")"#)?,
+        Pipeline::collection_group("posts")
+    );
+    assert_eq!(
+        print_pipeline(&Pipeline::collection_group("posts")),
+        r#"collection_group("posts")"#
+    );
+    assert_eq!(
+        parse_pipeline(r#"collection_group("users/alice", "posts")"#)?,
+        Pipeline::collection_group_in("/users/alice", "posts")
+    );
+    assert_eq!(
+        print_pipeline(&Pipeline::collection_group_in("users/alice", "posts")),
+        r#"collection_group("/users/alice", "posts")"#
+    );
+    Ok(())
+}
+
+#[test]
+fn test_options_and_quoted_names() -> anyhow::Result<()> {
+    let s = r#"find_nearest(field("embedding"), [1.0, 2.0], "cosine", distance_field: field("d"), limit: 3)"#;
+    let pipeline = parse_pipeline(s)?;
+    let stage = &pipeline.stages[0];
+    assert_eq!(stage.args.len(), 3);
+    assert_eq!(from_value::<i64>(&stage.options["limit"])?, 3);
+    assert_eq!(print_pipeline(&pipeline), s);
+
+    // A stage in a non-shorthand form is printed with a quoted name.
+    let pipeline = Pipeline {
+        stages: vec![Stage {
+            name: "collection".to_string(),
+            args: vec![to_value(&"users")?],
+            options: Default::default(),
+        }],
+    };
+    assert_eq!(print_pipeline(&pipeline), r#"`collection`("users")"#);
+    assert_eq!(parse_pipeline(&print_pipeline(&pipeline))?, pipeline);
+
+    // References without a leading `/` are not printed as shorthand.
+    let reference = |path: &str| Value {
+        value_type: Some(ValueType::ReferenceValue(path.to_string())),
+    };
+    let pipeline = Pipeline {
+        stages: vec![
+            Stage {
+                name: "collection".to_string(),
+                args: vec![reference("users")],
+                options: Default::default(),
+            },
+            Stage {
+                name: "documents".to_string(),
+                args: vec![
+                    reference("/users/alice"),
+                    reference("projects/p/databases/d/documents/users/bob"),
+                ],
+                options: Default::default(),
+            },
+        ],
+    };
+    assert_eq!(
+        print_pipeline(&pipeline),
+        r#"`collection`(ref("users")) |> `documents`(ref("/users/alice"), ref("projects/p/databases/d/documents/users/bob"))"#
+    );
+    assert_eq!(parse_pipeline(&print_pipeline(&pipeline))?, pipeline);
+
+    let function = Function {
+        name: "field".to_string(),
+        args: vec![],
+        options: [("my key".to_string(), to_value(&true)?)]
+            .into_iter()
+            .collect(),
+    };
+    assert_eq!(print_function(&function), r#"`field`("my key": true)"#);
+    assert_eq!(parse_function(&print_function(&function))?, function);
+    Ok(())
+}
+
+#[test]
+fn test_values_round_trip() -> anyhow::Result<()> {
+    #[derive(serde::Serialize)]
+    struct S {
+        null: Option<i64>,
+        boolean: bool,
+        integer: i64,
+        min: i64,
+        double: f64,
+        nan: f64,
+        negative_infinity: f64,
+        negative_zero: f64,
+        string: String,
+        bytes: serde_firestore_value::Bytes,
+        reference: Reference,
+        timestamp: Timestamp,
+        geo_point: LatLng,
+        array: Vec<i64>,
+        field: FieldReference,
+        variable: VariableReference,
+        function: Function,
+        pipeline: Pipeline,
+    }
+    let value = to_value(&S {
+        null: None,
+        boolean: true,
+        integer: 1,
+        min: i64::MIN,
+        double: 1e300,
+        nan: f64::NAN,
+        negative_infinity: f64::NEG_INFINITY,
+        negative_zero: -0.0,
+        string: "a\"\\\n\u{1}あ".to_string(),
+        bytes: serde_firestore_value::Bytes(vec![0, 255]),
+        reference: Reference("projects/p/databases/d/documents/c/1".to_string()),
+        timestamp: Timestamp {
+            seconds: 1,
+            nanos: 2,
+        },
+        geo_point: LatLng {
+            latitude: 1.5,
+            longitude: -2.0,
+        },
+        array: vec![1, 2],
+        field: FieldReference("a.b".to_string()),
+        variable: VariableReference("v".to_string()),
+        function: Function {
+            name: "add".to_string(),
+            args: vec![to_value(&1)?],
+            options: Default::default(),
+        },
+        pipeline: Pipeline::collection("c").limit(1),
+    })?;
+    let printed = print_value(&value);
+    let parsed = parse_value(&printed)?;
+    // NaN != NaN, so compare the printed forms.
+    assert_eq!(print_value(&parsed), printed);
+    assert!(printed.contains(r#""nan": NaN"#));
+    assert!(printed.contains(r#""bytes": bytes("00ff")"#));
+    assert!(printed.contains(r#""timestamp": timestamp("1970-01-01T00:00:01.000000002Z")"#));
+    assert!(printed.contains(r#""pipeline": pipeline(collection("/c") |> limit(1))"#));
+    assert!(printed.contains(r#""negative_zero": -0.0"#));
+
+    assert_eq!(
+        from_value::<VariableReference>(&parse_value(r#"var("x")"#)?)?,
+        VariableReference("x".to_string())
+    );
+    assert_eq!(parse_value("2")?, to_value(&2)?);
+    assert_eq!(parse_value("2.0")?, to_value(&2.0)?);
+    Ok(())
+}
+
+#[test]
+fn test_errors() -> anyhow::Result<()> {
+    let error = parse_pipeline("collection(\"users\")\n  |> limit(1,").unwrap_err();
+    assert_eq!((error.line(), error.column(), error.offset()), (2, 14, 33));
+    assert_eq!(
+        error.to_string(),
+        "2:14: expected a value, found end of input"
+    );
+
+    let error = parse_value("[1, 2 3]").unwrap_err();
+    assert_eq!(error.column(), 7);
+    assert_eq!(error.message(), "expected `,`, found `3`");
+
+    let error = parse_value(r#"field(1)"#).unwrap_err();
+    assert_eq!(error.message(), "`field` expects a string");
+
+    let error = parse_value(r#"bytes("+f")"#).unwrap_err();
+    assert_eq!(error.message(), "`bytes` expects a hex string");
+
+    let error = parse_function("1").unwrap_err();
+    assert_eq!(error.message(), "expected a function");
+
+    let error = parse_value(r#"f(a: 1, 2)"#).unwrap_err();
+    assert_eq!(error.message(), "arguments must precede options");
+    assert_eq!(error.column(), 9);
+    Ok(())
+}
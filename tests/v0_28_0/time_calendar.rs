@@ -0,0 +1,97 @@
+#[cfg(feature = "time")]
+fn v(
+    value_type: serde_firestore_value::google::firestore::v1::value::ValueType,
+) -> serde_firestore_value::google::firestore::v1::Value {
+    serde_firestore_value::google::firestore::v1::Value {
+        value_type: Some(value_type),
+    }
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn test_date() -> anyhow::Result<()> {
+    use serde_firestore_value::google::firestore::v1::value::ValueType;
+    use serde_firestore_value::with::{time_date_as_string, time_date_as_timestamp};
+    use serde_firestore_value::{from_value, to_value};
+
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct S(#[serde(with = "time_date_as_string")] time::Date);
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct T(#[serde(with = "time_date_as_timestamp")] time::Date);
+
+    let date = time::Date::from_calendar_date(2000, time::Month::January, 2)?;
+
+    let s = to_value(&S(date))?;
+    assert_eq!(s, v(ValueType::StringValue("2000-01-02".to_string())));
+    assert_eq!(from_value::<'_, S>(&s)?, S(date));
+    assert!(from_value::<'_, S>(&v(ValueType::StringValue("2000-1-2".to_string()))).is_err());
+
+    let t = to_value(&T(date))?;
+    assert_eq!(
+        t,
+        v(ValueType::TimestampValue(prost_types::Timestamp {
+            seconds: 946_771_200,
+            nanos: 0,
+        }))
+    );
+    assert_eq!(from_value::<'_, T>(&t)?, T(date));
+    assert!(
+        from_value::<'_, T>(&v(ValueType::TimestampValue(prost_types::Timestamp {
+            seconds: 946_771_201,
+            nanos: 0,
+        })))
+        .is_err()
+    );
+    Ok(())
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn test_primitive_date_time() -> anyhow::Result<()> {
+    use serde_firestore_value::google::firestore::v1::value::ValueType;
+    use serde_firestore_value::with::{
+        time_primitive_date_time_as_string, time_primitive_date_time_as_timestamp,
+    };
+    use serde_firestore_value::{from_value, to_value};
+
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct S(#[serde(with = "time_primitive_date_time_as_string")] time::PrimitiveDateTime);
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct T(#[serde(with = "time_primitive_date_time_as_timestamp")] time::PrimitiveDateTime);
+
+    let date = time::Date::from_calendar_date(2000, time::Month::January, 2)?;
+    let date_time = time::PrimitiveDateTime::new(date, time::Time::from_hms_nano(3, 4, 5, 6)?);
+
+    let s = to_value(&S(date_time))?;
+    assert_eq!(
+        s,
+        v(ValueType::StringValue(
+            "2000-01-02T03:04:05.000000006".to_string()
+        ))
+    );
+    assert_eq!(from_value::<'_, S>(&s)?, S(date_time));
+    let whole = time::PrimitiveDateTime::new(date, time::Time::from_hms(3, 4, 5)?);
+    let s = to_value(&S(whole))?;
+    assert_eq!(
+        s,
+        v(ValueType::StringValue("2000-01-02T03:04:05".to_string()))
+    );
+    assert_eq!(from_value::<'_, S>(&s)?, S(whole));
+    assert!(
+        from_value::<'_, S>(&v(ValueType::StringValue(
+            "2000-01-02T03:04:05Z".to_string()
+        )))
+        .is_err()
+    );
+
+    let t = to_value(&T(date_time))?;
+    assert_eq!(
+        t,
+        v(ValueType::TimestampValue(prost_types::Timestamp {
+            seconds: 946_782_245,
+            nanos: 6,
+        }))
+    );
+    assert_eq!(from_value::<'_, T>(&t)?, T(date_time));
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use serde_firestore_value::google::firestore::v1::{Value, value::ValueType};
+use serde_firestore_value::{FieldReference, Function, Stage, to_value};
+
+#[test]
+fn test_function_args() -> anyhow::Result<()> {
+    let function =
+        Function::from_args("greater_than", &(FieldReference("age".to_string()), 18_i64))?;
+    assert_eq!(function.name, "greater_than");
+    assert_eq!(
+        function.args,
+        vec![
+            Value {
+                value_type: Some(ValueType::FieldReferenceValue("age".to_string()))
+            },
+            Value {
+                value_type: Some(ValueType::IntegerValue(18))
+            }
+        ]
+    );
+    assert!(function.options.is_empty());
+
+    let (field, value) = function.args_as::<(FieldReference, i64)>()?;
+    assert_eq!(field, FieldReference("age".to_string()));
+    assert_eq!(value, 18);
+
+    // Tuple structs and `Vec`s are positional, too.
+    #[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct Args(FieldReference, i64);
+    assert_eq!(
+        function.args_as::<Args>()?,
+        Args(FieldReference("age".to_string()), 18)
+    );
+    assert_eq!(
+        Function::from_args("greater_than", &Args(field, 18))?,
+        function
+    );
+    assert_eq!(
+        Function::from_args("f", &vec![1, 2])?.args_as::<Vec<i64>>()?,
+        [1, 2]
+    );
+
+    // Length and type mismatches are errors.
+    assert!(function.args_as::<(FieldReference,)>().is_err());
+    assert!(function.args_as::<(FieldReference, i64, i64)>().is_err());
+    assert!(function.args_as::<(i64, i64)>().is_err());
+    assert!(Function::from_args("f", &1).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_function_options() -> anyhow::Result<()> {
+    #[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct Options<'a> {
+        mode: &'a str,
+        #[serde(default)]
+        precision: Option<i64>,
+    }
+
+    let function = Function::from_args("round", &(1.5,))?.with_options(&Options {
+        mode: "half_even",
+        precision: None,
+    })?;
+    assert_eq!(function.options["mode"], to_value(&"half_even")?);
+    assert_eq!(function.option_as::<&str>("mode")?, Some("half_even"));
+    assert_eq!(function.option_as::<i64>("missing")?, None);
+    assert!(function.option_as::<i64>("mode").is_err());
+    assert_eq!(
+        function.options_as::<Options>()?,
+        Options {
+            mode: "half_even",
+            precision: None
+        }
+    );
+    assert!(Function::from_args("f", &())?.with_options(&1).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_stage() -> anyhow::Result<()> {
+    #[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct Options {
+        limit: i64,
+        distance_field: FieldReference,
+    }
+
+    let stage = Stage::from_args("find_nearest", &(FieldReference("v".to_string()), "cosine"))?
+        .with_options(&Options {
+            limit: 3,
+            distance_field: FieldReference("d".to_string()),
+        })?;
+    assert_eq!(
+        stage.args_as::<(FieldReference, String)>()?,
+        (FieldReference("v".to_string()), "cosine".to_string())
+    );
+    assert_eq!(stage.option_as::<i64>("limit")?, Some(3));
+    assert_eq!(
+        stage.options_as::<Options>()?,
+        Options {
+            limit: 3,
+            distance_field: FieldReference("d".to_string())
+        }
+    );
+
+    // `()` is no arguments.
+    let stage = Stage::from_args("x", &())?;
+    assert!(stage.args.is_empty());
+    stage.args_as::<()>()?;
+    assert!(Stage::from_args("x", &(1,))?.args_as::<()>().is_err());
+    Ok(())
+}
@@ -0,0 +1,132 @@
+#[cfg(feature = "uuid")]
+fn v(
+    value_type: serde_firestore_value::google::firestore::v1::value::ValueType,
+) -> serde_firestore_value::google::firestore::v1::Value {
+    serde_firestore_value::google::firestore::v1::Value {
+        value_type: Some(value_type),
+    }
+}
+
+#[cfg(all(feature = "uuid", feature = "bytes"))]
+fn bytes(data: &'static [u8]) -> prost::bytes::Bytes {
+    prost::bytes::Bytes::from_static(data)
+}
+
+#[cfg(all(feature = "uuid", feature = "vec-u8"))]
+fn bytes(data: &'static [u8]) -> Vec<u8> {
+    data.to_vec()
+}
+
+#[cfg(feature = "uuid")]
+const UUID: uuid::Uuid = uuid::Uuid::from_u128(0x67e55044_10b1_426f_9247_bb680e5fe0c8);
+
+#[cfg(feature = "uuid")]
+#[test]
+fn test_uuid_as_string() -> anyhow::Result<()> {
+    use serde_firestore_value::google::firestore::v1::{ArrayValue, value::ValueType};
+    use serde_firestore_value::with::{option_uuid_as_string, uuid_as_string, vec_uuid_as_string};
+    use serde_firestore_value::{from_value, to_value};
+
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct S(#[serde(with = "uuid_as_string")] uuid::Uuid);
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct O(#[serde(with = "option_uuid_as_string")] Option<uuid::Uuid>);
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct V(#[serde(with = "vec_uuid_as_string")] Vec<uuid::Uuid>);
+
+    let string = || {
+        v(ValueType::StringValue(
+            "67e55044-10b1-426f-9247-bb680e5fe0c8".to_string(),
+        ))
+    };
+
+    let s = to_value(&S(UUID))?;
+    assert_eq!(s, string());
+    assert_eq!(from_value::<'_, S>(&s)?, S(UUID));
+
+    let o = to_value(&O(Some(UUID)))?;
+    assert_eq!(o, string());
+    assert_eq!(from_value::<'_, O>(&o)?, O(Some(UUID)));
+    let o = to_value(&O(None))?;
+    assert_eq!(o, v(ValueType::NullValue(0)));
+    assert_eq!(from_value::<'_, O>(&o)?, O(None));
+
+    let vec = to_value(&V(vec![UUID, uuid::Uuid::nil()]))?;
+    assert_eq!(
+        vec,
+        v(ValueType::ArrayValue(ArrayValue {
+            values: vec![
+                string(),
+                v(ValueType::StringValue(
+                    "00000000-0000-0000-0000-000000000000".to_string()
+                )),
+            ],
+        }))
+    );
+    assert_eq!(from_value::<'_, V>(&vec)?, V(vec![UUID, uuid::Uuid::nil()]));
+
+    for s in [
+        "67E55044-10B1-426F-9247-BB680E5FE0C8",
+        "67e5504410b1426f9247bb680e5fe0c8",
+        "{67e55044-10b1-426f-9247-bb680e5fe0c8}",
+        "urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8",
+    ] {
+        assert_eq!(
+            from_value::<'_, S>(&v(ValueType::StringValue(s.to_string())))?,
+            S(UUID)
+        );
+    }
+    assert!(from_value::<'_, S>(&v(ValueType::StringValue("67e55044".to_string()))).is_err());
+    Ok(())
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn test_uuid_as_bytes() -> anyhow::Result<()> {
+    use serde_firestore_value::google::firestore::v1::{ArrayValue, value::ValueType};
+    use serde_firestore_value::with::{option_uuid_as_bytes, uuid_as_bytes, vec_uuid_as_bytes};
+    use serde_firestore_value::{from_value, to_value};
+
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct S(#[serde(with = "uuid_as_bytes")] uuid::Uuid);
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct O(#[serde(with = "option_uuid_as_bytes")] Option<uuid::Uuid>);
+    #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+    struct V(#[serde(with = "vec_uuid_as_bytes")] Vec<uuid::Uuid>);
+
+    let bytes_value = || {
+        v(ValueType::BytesValue(bytes(&[
+            0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f, 0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f,
+            0xe0, 0xc8,
+        ])))
+    };
+
+    let s = to_value(&S(UUID))?;
+    assert_eq!(s, bytes_value());
+    assert_eq!(from_value::<'_, S>(&s)?, S(UUID));
+
+    let o = to_value(&O(Some(UUID)))?;
+    assert_eq!(o, bytes_value());
+    assert_eq!(from_value::<'_, O>(&o)?, O(Some(UUID)));
+    let o = to_value(&O(None))?;
+    assert_eq!(o, v(ValueType::NullValue(0)));
+    assert_eq!(from_value::<'_, O>(&o)?, O(None));
+
+    let vec = to_value(&V(vec![UUID]))?;
+    assert_eq!(
+        vec,
+        v(ValueType::ArrayValue(ArrayValue {
+            values: vec![bytes_value()],
+        }))
+    );
+    assert_eq!(from_value::<'_, V>(&vec)?, V(vec![UUID]));
+
+    assert!(from_value::<'_, S>(&v(ValueType::BytesValue(bytes(&[0; 15])))).is_err());
+    assert!(
+        from_value::<'_, S>(&v(ValueType::StringValue(
+            "67e55044-10b1-426f-9247-bb680e5fe0c8".to_string()
+        )))
+        .is_err()
+    );
+    Ok(())
+}
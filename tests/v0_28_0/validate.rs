@@ -0,0 +1,118 @@
+use serde_firestore_value::expr::{count_all, field, function, lit, var};
+use serde_firestore_value::stage::{DistanceMeasure, FindNearest, Ordering};
+use serde_firestore_value::text::parse_pipeline;
+use serde_firestore_value::validate::{ArgKind, Catalog, Problem, Signature};
+use serde_firestore_value::{FieldReference, Function, Pipeline, Stage};
+
+fn problems(problems: Vec<Problem>) -> Vec<String> {
+    problems.iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn test_valid() {
+    let pipeline = Pipeline::collection("users")
+        .where_(field("age").gte(lit(18)).and(field("active").eq(lit(true))))
+        .add_fields([("double", field("age").multiply(lit(2)))])
+        .select(["name", "double"])
+        .sort([Ordering::descending(field("double"))])
+        .offset(1)
+        .limit(10)
+        .aggregate(
+            [("count", count_all()), ("total", field("double").sum())],
+            [("name", field("name"))],
+        )
+        .find_nearest(FindNearest {
+            field: FieldReference("embedding".to_string()),
+            vector: vec![1.0],
+            distance_measure: DistanceMeasure::Euclidean,
+            limit: Some(1),
+            distance_field: None,
+        });
+    assert_eq!(Catalog::default().validate_pipeline(&pipeline), vec![]);
+}
+
+#[test]
+fn test_names_and_arguments() -> anyhow::Result<()> {
+    let pipeline = parse_pipeline(
+        r#"collection("users")
+        |> wher(equal(field("a"), 1))
+        |> where(greter_than(field("a"), 1))
+        |> where(equal(field("a")))
+        |> limit("10")
+        |> offset(1, 2, limit: 3)
+        |> sort({"direction": "up", "expression": field("a")})"#,
+    )?;
+    assert_eq!(
+        problems(Catalog::default().validate_pipeline(&pipeline)),
+        vec![
+            "stages[1]: unknown stage `wher` (did you mean `where`?)",
+            "stages[2].args[0]: unknown function `greter_than` (did you mean `greater_than`?)",
+            "stages[3].args[0]: `equal` expects 2 argument(s), got 1",
+            "stages[4].args[0]: expected an integer, found a string value",
+            "stages[5]: `offset` expects 1 argument(s), got 2",
+            "stages[5].options.limit: unknown option `limit` of `offset`",
+            r#"stages[6].args[0]["direction"]: expected "ascending" or "descending""#,
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_accumulators_and_variables() {
+    let pipeline = Pipeline::collection("users")
+        .where_(field("a").sum().gt(lit(1)))
+        .aggregate([("x", field("a"))], std::iter::empty::<&str>())
+        .select([("v", var("v"))]);
+    assert_eq!(
+        problems(Catalog::default().validate_pipeline(&pipeline)),
+        vec![
+            "stages[1].args[0].args[0]: accumulator `sum` is only allowed in `aggregate`",
+            r#"stages[2].args[0]["x"]: expected an accumulator function, found a field reference value"#,
+            r#"stages[3].args[0]["v"]: variable reference `v` is not allowed here"#,
+        ]
+    );
+}
+
+#[test]
+fn test_extensible_catalog() {
+    let function = Function::try_from(function("let", [lit("v"), lit(1), var("v")])).unwrap();
+    let mut catalog = Catalog::default();
+    assert_eq!(
+        problems(catalog.validate_function(&function)),
+        vec![": unknown function `let`"]
+    );
+
+    catalog.insert_function(
+        "let",
+        Signature::new([ArgKind::String, ArgKind::Expr, ArgKind::Expr]).allow_variables(),
+    );
+    assert_eq!(catalog.validate_function(&function), vec![]);
+    let invalid = Function::try_from(serde_firestore_value::expr::function(
+        "let",
+        [lit(1), lit(1), var("v")],
+    ))
+    .unwrap();
+    assert_eq!(
+        problems(catalog.validate_function(&invalid)),
+        vec!["args[0]: expected a string, found an integer value"]
+    );
+
+    catalog.insert_stage(
+        "sample",
+        Signature::new([]).option("documents", ArgKind::Integer),
+    );
+    let stage = Stage {
+        name: "sample".to_string(),
+        args: vec![],
+        options: [(
+            "documents".to_string(),
+            serde_firestore_value::to_value(&"x").unwrap(),
+        )]
+        .into_iter()
+        .collect(),
+    };
+    assert_eq!(
+        problems(catalog.validate_stage(&stage)),
+        vec!["options.documents: expected an integer, found a string value"]
+    );
+}
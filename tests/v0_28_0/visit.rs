@@ -0,0 +1,183 @@
+use serde_firestore_value::google::firestore::v1::{Function, Pipeline, Value, pipeline::Stage};
+use serde_firestore_value::visit::{PathSegment, ValuePath, ValueVisitor};
+
+#[test]
+fn test_collect_paths() -> anyhow::Result<()> {
+    use serde_firestore_value::{Reference, Timestamp, to_value};
+
+    #[derive(serde::Serialize)]
+    struct S {
+        a: Vec<Timestamp>,
+        b: Inner,
+    }
+
+    #[derive(serde::Serialize)]
+    struct Inner {
+        r: Reference,
+        t: Timestamp,
+    }
+
+    #[derive(Default)]
+    struct V(Vec<String>);
+
+    impl<'v> ValueVisitor<'v> for V {
+        fn visit_timestamp(&mut self, path: &ValuePath<'v>, _: &'v prost_types::Timestamp) {
+            self.0.push(format!("timestamp {}", path));
+        }
+
+        fn visit_reference(&mut self, path: &ValuePath<'v>, value: &'v str) {
+            self.0.push(format!("reference {} {}", path, value));
+        }
+    }
+
+    let value = to_value(&S {
+        a: vec![
+            Timestamp {
+                seconds: 1,
+                nanos: 0,
+            },
+            Timestamp {
+                seconds: 2,
+                nanos: 0,
+            },
+        ],
+        b: Inner {
+            r: Reference("projects/p/databases/d/documents/c/1".to_string()),
+            t: Timestamp {
+                seconds: 3,
+                nanos: 0,
+            },
+        },
+    })?;
+    let mut visitor = V::default();
+    visitor.visit_value(&mut ValuePath::new(), &value);
+    visitor.0.sort();
+    assert_eq!(
+        visitor.0,
+        vec![
+            "reference b.r projects/p/databases/d/documents/c/1",
+            "timestamp a[0]",
+            "timestamp a[1]",
+            "timestamp b.t",
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_function_and_pipeline() -> anyhow::Result<()> {
+    use serde_firestore_value::google::firestore::v1::value::ValueType;
+
+    #[derive(Default)]
+    struct V<'v>(Vec<(Vec<PathSegment<'v>>, &'v str)>);
+
+    impl<'v> ValueVisitor<'v> for V<'v> {
+        fn visit_field_reference(&mut self, path: &ValuePath<'v>, value: &'v str) {
+            self.0.push((path.segments().to_vec(), value));
+        }
+
+        fn visit_variable_reference(&mut self, path: &ValuePath<'v>, value: &'v str) {
+            self.0.push((path.segments().to_vec(), value));
+        }
+    }
+
+    let function = Function {
+        name: "eq".to_string(),
+        args: vec![
+            Value {
+                value_type: Some(ValueType::FieldReferenceValue("a".to_string())),
+            },
+            Value {
+                value_type: Some(ValueType::VariableReferenceValue("v".to_string())),
+            },
+        ],
+        options: Default::default(),
+    };
+    let value = Value {
+        value_type: Some(ValueType::PipelineValue(Pipeline {
+            stages: vec![
+                Stage {
+                    name: "collection".to_string(),
+                    args: vec![],
+                    options: Default::default(),
+                },
+                Stage {
+                    name: "where".to_string(),
+                    args: vec![Value {
+                        value_type: Some(ValueType::FunctionValue(function)),
+                    }],
+                    options: Default::default(),
+                },
+            ],
+        })),
+    };
+
+    let mut visitor = V::default();
+    visitor.visit_value(&mut ValuePath::new(), &value);
+    assert_eq!(
+        visitor.0,
+        vec![
+            (
+                vec![
+                    PathSegment::Stage(1),
+                    PathSegment::StageArg(0),
+                    PathSegment::FunctionArg(0)
+                ],
+                "a"
+            ),
+            (
+                vec![
+                    PathSegment::Stage(1),
+                    PathSegment::StageArg(0),
+                    PathSegment::FunctionArg(1)
+                ],
+                "v"
+            ),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_override_recursion() -> anyhow::Result<()> {
+    use serde_firestore_value::google::firestore::v1::MapValue;
+    use serde_firestore_value::to_value;
+
+    #[derive(Default)]
+    struct V {
+        integers: usize,
+    }
+
+    impl<'v> ValueVisitor<'v> for V {
+        fn visit_map(&mut self, path: &mut ValuePath<'v>, value: &'v MapValue) {
+            // skip nested maps
+            if path.is_root() {
+                serde_firestore_value::visit::walk_map(self, path, value);
+            }
+        }
+
+        fn visit_integer(&mut self, _: &ValuePath<'v>, _: i64) {
+            self.integers += 1;
+        }
+    }
+
+    #[derive(serde::Serialize)]
+    struct S {
+        a: i64,
+        b: Inner,
+    }
+
+    #[derive(serde::Serialize)]
+    struct Inner {
+        c: i64,
+    }
+
+    let value = to_value(&S {
+        a: 1,
+        b: Inner { c: 2 },
+    })?;
+    let mut visitor = V::default();
+    visitor.visit_value(&mut ValuePath::new(), &value);
+    assert_eq!(visitor.integers, 1);
+    Ok(())
+}
@@ -21,6 +21,6 @@ fn test() -> anyhow::Result<()> {
     assert_eq!(d, o);
 
     let json = serde_json::to_string(&o)?;
-    assert_eq!(json, r#"{"latitude":1.0,"longitude":2.0}"#);
+    assert_eq!(json, r#"{"lat":1.0,"lng":2.0}"#);
     Ok(())
 }
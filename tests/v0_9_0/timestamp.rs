@@ -19,6 +19,6 @@ fn test() -> anyhow::Result<()> {
     assert_eq!(d, o);
 
     let json = serde_json::to_string(&o)?;
-    assert_eq!(json, r#"{"seconds":1,"nanos":2}"#);
+    assert_eq!(json, r#""1970-01-01T00:00:01.000000002Z""#);
     Ok(())
 }